@@ -0,0 +1,318 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use edc_connector_client::api::ConflictStrategy;
+
+use crate::{config::ApiVersion, output::OutputFormat};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "edc",
+    version,
+    about = "Command-line tool for the EDC management API"
+)]
+pub struct Cli {
+    #[command(flatten)]
+    pub global: GlobalArgs,
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Default, Args)]
+pub struct GlobalArgs {
+    /// Path of the config file, `~/.config/edc/config.toml` by default.
+    #[arg(long, global = true, env = "EDC_CONFIG")]
+    pub config: Option<PathBuf>,
+    /// Profile of the config file to use.
+    #[arg(short, long, global = true, env = "EDC_PROFILE")]
+    pub profile: Option<String>,
+    #[arg(long, global = true, env = "EDC_MANAGEMENT_URL")]
+    pub management_url: Option<String>,
+    #[arg(long, global = true, env = "EDC_API_KEY", hide_env_values = true)]
+    pub api_key: Option<String>,
+    #[arg(long, global = true, value_enum)]
+    pub api_version: Option<ApiVersion>,
+    #[arg(long, global = true)]
+    pub participant_context: Option<String>,
+    #[arg(short, long, global = true, value_enum)]
+    pub output: Option<OutputFormat>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    #[command(subcommand)]
+    Assets(AssetsCommand),
+    #[command(subcommand)]
+    Policies(PoliciesCommand),
+    #[command(subcommand)]
+    Contractdefs(ContractDefinitionsCommand),
+    #[command(subcommand)]
+    Catalog(CatalogCommand),
+    #[command(subcommand)]
+    Negotiate(NegotiateCommand),
+    #[command(subcommand)]
+    Agreements(AgreementsCommand),
+    #[command(subcommand)]
+    Transfer(TransferCommand),
+    #[command(subcommand)]
+    Edrs(EdrsCommand),
+    #[command(subcommand)]
+    Secrets(SecretsCommand),
+    #[command(subcommand)]
+    Participants(ParticipantsCommand),
+    /// Converges the connector to a YAML or JSON manifest.
+    Apply(ApplyArgs),
+    /// Exports assets, policies, contract definitions and secrets to an archive.
+    Export(ExportArgs),
+    /// Imports an archive written by `export`.
+    Import(ImportArgs),
+}
+
+/// Reads the entity from a json file, `-` for stdin.
+#[derive(Debug, Args)]
+pub struct FileArgs {
+    #[arg(short, long)]
+    pub file: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct QueryArgs {
+    /// Criterion as `<left> <operator> <right>`, e.g. `id = 1`, can be repeated.
+    #[arg(long)]
+    pub filter: Vec<String>,
+    #[arg(long, default_value_t = 50)]
+    pub limit: u32,
+    #[arg(long, default_value_t = 0)]
+    pub offset: u32,
+    /// Fetches all the pages.
+    #[arg(long)]
+    pub all: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ApplyArgs {
+    #[command(flatten)]
+    pub file: FileArgs,
+    /// Only shows the changes without applying them.
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Deletes the assets, policies and contract definitions missing from the manifest.
+    #[arg(long)]
+    pub prune: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    /// Path of the archive, `-` for stdout.
+    #[arg(short, long)]
+    pub file: PathBuf,
+    /// Id of a secret to export, can be repeated.
+    #[arg(long = "secret", value_name = "ID")]
+    pub secrets: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct ImportArgs {
+    #[command(flatten)]
+    pub file: FileArgs,
+    #[arg(long, value_enum, default_value_t = OnConflict::Fail)]
+    pub on_conflict: OnConflict,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum OnConflict {
+    Fail,
+    Skip,
+    Overwrite,
+}
+
+impl From<OnConflict> for ConflictStrategy {
+    fn from(on_conflict: OnConflict) -> Self {
+        match on_conflict {
+            OnConflict::Fail => ConflictStrategy::Fail,
+            OnConflict::Skip => ConflictStrategy::Skip,
+            OnConflict::Overwrite => ConflictStrategy::Overwrite,
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AssetsCommand {
+    Create(FileArgs),
+    Update(FileArgs),
+    Get { id: String },
+    List(QueryArgs),
+    Delete { id: String },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum PoliciesCommand {
+    Create(FileArgs),
+    Update(FileArgs),
+    Get { id: String },
+    List(QueryArgs),
+    Delete { id: String },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ContractDefinitionsCommand {
+    Create(FileArgs),
+    Update(FileArgs),
+    Get { id: String },
+    List(QueryArgs),
+    Delete { id: String },
+}
+
+#[derive(Debug, Args)]
+pub struct CounterPartyArgs {
+    #[arg(long)]
+    pub counter_party_address: String,
+    #[arg(long)]
+    pub counter_party_id: String,
+    #[arg(long)]
+    pub protocol: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CatalogCommand {
+    /// Requests the catalog of a provider.
+    Request {
+        #[command(flatten)]
+        counter_party: CounterPartyArgs,
+        #[command(flatten)]
+        query: QueryArgs,
+    },
+    /// Requests a single dataset of a provider.
+    Dataset {
+        id: String,
+        #[command(flatten)]
+        counter_party: CounterPartyArgs,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum NegotiateCommand {
+    /// Negotiates an offer of the asset from the provider's catalog.
+    Start {
+        #[arg(long)]
+        asset_id: String,
+        /// The offer to negotiate, the first one of the dataset by default.
+        #[arg(long)]
+        offer_id: Option<String>,
+        #[command(flatten)]
+        counter_party: CounterPartyArgs,
+        /// Waits for the negotiation to be finalized.
+        #[arg(long)]
+        wait: bool,
+    },
+    Get {
+        id: String,
+    },
+    List(QueryArgs),
+    Terminate {
+        id: String,
+        #[arg(long)]
+        reason: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AgreementsCommand {
+    Get { id: String },
+    List(QueryArgs),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TransferCommand {
+    /// Starts a transfer process for a contract agreement.
+    Start {
+        #[arg(long)]
+        contract_id: String,
+        #[arg(long)]
+        counter_party_address: String,
+        #[arg(long)]
+        protocol: Option<String>,
+        #[arg(long, default_value = "HttpData-PULL")]
+        transfer_type: String,
+        /// Type of the data destination, required by push transfers.
+        #[arg(long)]
+        destination_type: Option<String>,
+        /// Waits for the transfer process to be started.
+        #[arg(long)]
+        wait: bool,
+    },
+    Get {
+        id: String,
+    },
+    List(QueryArgs),
+    Terminate {
+        id: String,
+        #[arg(long)]
+        reason: String,
+    },
+    Suspend {
+        id: String,
+        #[arg(long)]
+        reason: String,
+    },
+    Resume {
+        id: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum EdrsCommand {
+    Get {
+        transfer_process_id: String,
+    },
+    List(QueryArgs),
+    /// Shows the data address of the EDR, including the access token.
+    DataAddress {
+        transfer_process_id: String,
+        /// Refreshes the token first.
+        #[arg(long)]
+        refresh: bool,
+    },
+    Delete {
+        transfer_process_id: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SecretsCommand {
+    Create {
+        #[arg(long)]
+        id: String,
+        #[arg(long)]
+        value: String,
+    },
+    Update {
+        #[arg(long)]
+        id: String,
+        #[arg(long)]
+        value: String,
+    },
+    Get {
+        id: String,
+    },
+    Delete {
+        id: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ParticipantsCommand {
+    Create {
+        #[arg(long)]
+        id: String,
+        #[arg(long)]
+        identity: String,
+    },
+    /// Saves the configuration entries of a participant context.
+    Config {
+        id: String,
+        /// Entry as `<key>=<value>`, can be repeated.
+        #[arg(long = "entry", value_name = "KEY=VALUE")]
+        entries: Vec<String>,
+    },
+}
@@ -0,0 +1,545 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufWriter, Read, Write},
+    path::Path,
+};
+
+use edc_connector_client::{
+    api::{ExportOptions, ImportOptions, ReconcileOptions},
+    types::{
+        archive::Archive,
+        catalog::{CatalogRequest, DatasetRequest},
+        contract_negotiation::{ContractNegotiationState, ContractRequest},
+        data_address::DataAddress,
+        manifest::Manifest,
+        participants::{NewParticipantContext, ParticipantContextConfig},
+        query::{Criterion, Operator, Query},
+        secret::{NewSecret, Secret},
+        transfer_process::{TransferProcessState, TransferRequest},
+        Protocol,
+    },
+    EdcConnectorClient, Error,
+};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    cli::{
+        AgreementsCommand, ApplyArgs, AssetsCommand, CatalogCommand, Cli, Command,
+        ContractDefinitionsCommand, CounterPartyArgs, EdrsCommand, ExportArgs, FileArgs,
+        ImportArgs, NegotiateCommand, ParticipantsCommand, PoliciesCommand, QueryArgs,
+        SecretsCommand, TransferCommand,
+    },
+    config::Config,
+    error::{CliError, CliResult},
+    output::{PlannedChange, Printer},
+};
+
+/// Runs the command with the resolved profile, writing the result to `out`.
+pub async fn run(cli: Cli, out: &mut dyn Write) -> CliResult<()> {
+    let profile = Config::load(cli.global.config.as_deref())?
+        .profile(cli.global.profile.as_deref())?
+        .merge(&cli.global);
+
+    let client = profile.client()?;
+    let mut printer = Printer::new(profile.output(), out);
+
+    execute(&client, cli.command, &mut printer).await
+}
+
+pub async fn execute(
+    client: &EdcConnectorClient,
+    command: Command,
+    printer: &mut Printer<'_>,
+) -> CliResult<()> {
+    match command {
+        Command::Assets(command) => assets(client, command, printer).await,
+        Command::Policies(command) => policies(client, command, printer).await,
+        Command::Contractdefs(command) => contract_definitions(client, command, printer).await,
+        Command::Catalog(command) => catalog(client, command, printer).await,
+        Command::Negotiate(command) => negotiate(client, command, printer).await,
+        Command::Agreements(command) => agreements(client, command, printer).await,
+        Command::Transfer(command) => transfer(client, command, printer).await,
+        Command::Edrs(command) => edrs(client, command, printer).await,
+        Command::Secrets(command) => secrets(client, command, printer).await,
+        Command::Participants(command) => participants(client, command, printer).await,
+        Command::Apply(args) => apply(client, args, printer).await,
+        Command::Export(args) => export(client, args, printer).await,
+        Command::Import(args) => import(client, args, printer).await,
+    }
+}
+
+async fn assets(
+    client: &EdcConnectorClient,
+    command: AssetsCommand,
+    printer: &mut Printer<'_>,
+) -> CliResult<()> {
+    let api = client.assets();
+    match command {
+        AssetsCommand::Create(file) => printer.one(&api.create(&read(&file)?).await?),
+        AssetsCommand::Update(file) => {
+            api.update(&read(&file)?).await?;
+            printer.message("Asset updated")
+        }
+        AssetsCommand::Get { id } => printer.one(&api.get(&id).await?),
+        AssetsCommand::List(args) => {
+            let query = query(&args)?;
+            let assets = if args.all {
+                api.query_all(query).await?
+            } else {
+                api.query(query).await?
+            };
+            printer.many(&assets)
+        }
+        AssetsCommand::Delete { id } => {
+            api.delete(&id).await?;
+            printer.message("Asset deleted")
+        }
+    }
+}
+
+async fn policies(
+    client: &EdcConnectorClient,
+    command: PoliciesCommand,
+    printer: &mut Printer<'_>,
+) -> CliResult<()> {
+    let api = client.policies();
+    match command {
+        PoliciesCommand::Create(file) => printer.one(&api.create(&read(&file)?).await?),
+        PoliciesCommand::Update(file) => {
+            api.update(&read(&file)?).await?;
+            printer.message("Policy definition updated")
+        }
+        PoliciesCommand::Get { id } => printer.one(&api.get(&id).await?),
+        PoliciesCommand::List(args) => {
+            let query = query(&args)?;
+            let policies = if args.all {
+                api.query_all(query).await?
+            } else {
+                api.query(query).await?
+            };
+            printer.many(&policies)
+        }
+        PoliciesCommand::Delete { id } => {
+            api.delete(&id).await?;
+            printer.message("Policy definition deleted")
+        }
+    }
+}
+
+async fn contract_definitions(
+    client: &EdcConnectorClient,
+    command: ContractDefinitionsCommand,
+    printer: &mut Printer<'_>,
+) -> CliResult<()> {
+    let api = client.contract_definitions();
+    match command {
+        ContractDefinitionsCommand::Create(file) => printer.one(&api.create(&read(&file)?).await?),
+        ContractDefinitionsCommand::Update(file) => {
+            api.update(&read(&file)?).await?;
+            printer.message("Contract definition updated")
+        }
+        ContractDefinitionsCommand::Get { id } => printer.one(&api.get(&id).await?),
+        ContractDefinitionsCommand::List(args) => {
+            let query = query(&args)?;
+            let definitions = if args.all {
+                api.query_all(query).await?
+            } else {
+                api.query(query).await?
+            };
+            printer.many(&definitions)
+        }
+        ContractDefinitionsCommand::Delete { id } => {
+            api.delete(&id).await?;
+            printer.message("Contract definition deleted")
+        }
+    }
+}
+
+async fn catalog(
+    client: &EdcConnectorClient,
+    command: CatalogCommand,
+    printer: &mut Printer<'_>,
+) -> CliResult<()> {
+    match command {
+        CatalogCommand::Request {
+            counter_party,
+            query: args,
+        } => {
+            let request = CatalogRequest::builder()
+                .counter_party_address(&counter_party.counter_party_address)
+                .counter_party_id(&counter_party.counter_party_id)
+                .protocol(protocol(counter_party.protocol.as_deref()))
+                .query_spec(query(&args)?)
+                .build();
+
+            let catalog = client.catalogue().request(&request).await?;
+            printer.many(catalog.datasets())
+        }
+        CatalogCommand::Dataset { id, counter_party } => {
+            let dataset = client
+                .catalogue()
+                .dataset(&dataset_request(&id, &counter_party))
+                .await?;
+            printer.one(&dataset)
+        }
+    }
+}
+
+async fn negotiate(
+    client: &EdcConnectorClient,
+    command: NegotiateCommand,
+    printer: &mut Printer<'_>,
+) -> CliResult<()> {
+    let api = client.contract_negotiations();
+    match command {
+        NegotiateCommand::Start {
+            asset_id,
+            offer_id,
+            counter_party,
+            wait,
+        } => {
+            let dataset = client
+                .catalogue()
+                .dataset(&dataset_request(&asset_id, &counter_party))
+                .await?;
+
+            let offer = dataset
+                .offers()
+                .iter()
+                .find(|offer| match &offer_id {
+                    Some(offer_id) => offer.id() == Some(offer_id),
+                    None => true,
+                })
+                .ok_or_else(|| {
+                    CliError::InvalidArgument(format!("no matching offer for asset {asset_id}"))
+                })?;
+
+            let request = ContractRequest::builder()
+                .counter_party_address(&counter_party.counter_party_address)
+                .counter_party_id(&counter_party.counter_party_id)
+                .protocol(protocol(counter_party.protocol.as_deref()))
+                .policy(offer.to_contract_offer(&counter_party.counter_party_id, &asset_id))
+                .build();
+
+            let response = api.initiate(&request).await?;
+            if wait {
+                let negotiation = api
+                    .wait_for_state(response.id(), ContractNegotiationState::Finalized)
+                    .await?;
+                printer.one(&negotiation)
+            } else {
+                printer.one(&response)
+            }
+        }
+        NegotiateCommand::Get { id } => printer.one(&api.get(&id).await?),
+        NegotiateCommand::List(args) => {
+            let query = query(&args)?;
+            let negotiations = if args.all {
+                api.query_all(query).await?
+            } else {
+                api.query(query).await?
+            };
+            printer.many(&negotiations)
+        }
+        NegotiateCommand::Terminate { id, reason } => {
+            api.terminate(&id, &reason).await?;
+            printer.message("Contract negotiation terminated")
+        }
+    }
+}
+
+async fn agreements(
+    client: &EdcConnectorClient,
+    command: AgreementsCommand,
+    printer: &mut Printer<'_>,
+) -> CliResult<()> {
+    let api = client.contract_agreements();
+    match command {
+        AgreementsCommand::Get { id } => printer.one(&api.get(&id).await?),
+        AgreementsCommand::List(args) => {
+            let query = query(&args)?;
+            let agreements = if args.all {
+                api.query_all(query).await?
+            } else {
+                api.query(query).await?
+            };
+            printer.many(&agreements)
+        }
+    }
+}
+
+async fn transfer(
+    client: &EdcConnectorClient,
+    command: TransferCommand,
+    printer: &mut Printer<'_>,
+) -> CliResult<()> {
+    let api = client.transfer_processes();
+    match command {
+        TransferCommand::Start {
+            contract_id,
+            counter_party_address,
+            protocol: transfer_protocol,
+            transfer_type,
+            destination_type,
+            wait,
+        } => {
+            let mut request = TransferRequest::builder()
+                .counter_party_address(counter_party_address)
+                .contract_id(contract_id)
+                .transfer_type(transfer_type)
+                .protocol(protocol(transfer_protocol.as_deref()));
+            if let Some(destination_type) = destination_type {
+                request =
+                    request.destination(DataAddress::builder().kind(&destination_type).build()?);
+            }
+
+            let response = api.initiate(&request.build()).await?;
+            if wait {
+                let transfer = api
+                    .wait_for_state(response.id(), TransferProcessState::Started)
+                    .await?;
+                printer.one(&transfer)
+            } else {
+                printer.one(&response)
+            }
+        }
+        TransferCommand::Get { id } => printer.one(&api.get(&id).await?),
+        TransferCommand::List(args) => {
+            let query = query(&args)?;
+            let transfers = if args.all {
+                api.query_all(query).await?
+            } else {
+                api.query(query).await?
+            };
+            printer.many(&transfers)
+        }
+        TransferCommand::Terminate { id, reason } => {
+            api.terminate(&id, &reason).await?;
+            printer.message("Transfer process terminated")
+        }
+        TransferCommand::Suspend { id, reason } => {
+            api.suspend(&id, &reason).await?;
+            printer.message("Transfer process suspended")
+        }
+        TransferCommand::Resume { id } => {
+            api.resume(&id).await?;
+            printer.message("Transfer process resumed")
+        }
+    }
+}
+
+async fn edrs(
+    client: &EdcConnectorClient,
+    command: EdrsCommand,
+    printer: &mut Printer<'_>,
+) -> CliResult<()> {
+    let api = client.edrs();
+    match command {
+        EdrsCommand::Get {
+            transfer_process_id,
+        } => printer.one(&api.get_entry(&transfer_process_id).await?),
+        EdrsCommand::List(args) => {
+            let query = query(&args)?;
+            let entries = if args.all {
+                api.query_all(query).await?
+            } else {
+                api.query(query).await?
+            };
+            printer.many(&entries)
+        }
+        EdrsCommand::DataAddress {
+            transfer_process_id,
+            refresh,
+        } => {
+            let data_address = if refresh {
+                api.refresh(&transfer_process_id).await?
+            } else {
+                api.get_data_address(&transfer_process_id).await?
+            };
+            printer.one(&data_address)
+        }
+        EdrsCommand::Delete {
+            transfer_process_id,
+        } => {
+            api.delete(&transfer_process_id).await?;
+            printer.message("EDR deleted")
+        }
+    }
+}
+
+async fn secrets(
+    client: &EdcConnectorClient,
+    command: SecretsCommand,
+    printer: &mut Printer<'_>,
+) -> CliResult<()> {
+    let api = client.secrets();
+    match command {
+        SecretsCommand::Create { id, value } => {
+            let secret = NewSecret::builder().id(id).value(value).build();
+            printer.one(&api.create(&secret).await?)
+        }
+        SecretsCommand::Update { id, value } => {
+            api.update(&Secret::builder().id(id).value(value).build())
+                .await?;
+            printer.message("Secret updated")
+        }
+        SecretsCommand::Get { id } => printer.one(&api.get(&id).await?),
+        SecretsCommand::Delete { id } => {
+            api.delete(&id).await?;
+            printer.message("Secret deleted")
+        }
+    }
+}
+
+async fn participants(
+    client: &EdcConnectorClient,
+    command: ParticipantsCommand,
+    printer: &mut Printer<'_>,
+) -> CliResult<()> {
+    match command {
+        ParticipantsCommand::Create { id, identity } => {
+            let participant = NewParticipantContext::builder()
+                .id(id)
+                .identity(identity)
+                .build();
+            printer.one(&client.participants().create(&participant).await?)
+        }
+        ParticipantsCommand::Config { id, entries } => {
+            let entries = entries
+                .iter()
+                .map(|entry| {
+                    entry
+                        .split_once('=')
+                        .map(|(key, value)| (key.to_string(), value.to_string()))
+                        .ok_or_else(|| {
+                            CliError::InvalidArgument(format!(
+                                "entry {entry} is not in the form <key>=<value>"
+                            ))
+                        })
+                })
+                .collect::<CliResult<HashMap<_, _>>>()?;
+
+            client
+                .participant_configs()
+                .save(
+                    &id,
+                    &ParticipantContextConfig::builder().entries(entries).build(),
+                )
+                .await?;
+            printer.message("Participant context configuration saved")
+        }
+    }
+}
+
+async fn apply(
+    client: &EdcConnectorClient,
+    args: ApplyArgs,
+    printer: &mut Printer<'_>,
+) -> CliResult<()> {
+    let manifest = Manifest::from_yaml(&read_content(&args.file)?).map_err(Error::from)?;
+    let options = ReconcileOptions::builder().prune(args.prune).build();
+
+    let api = client.provisioning();
+    let plan = if args.dry_run {
+        api.plan_with_opts(&manifest, options).await?
+    } else {
+        api.apply_with_opts(&manifest, options).await?
+    };
+
+    let changes = plan
+        .changes()
+        .iter()
+        .map(PlannedChange::from)
+        .collect::<Vec<_>>();
+    printer.many(&changes)?;
+    printer.message(&format!(
+        "{} {}, {} unchanged",
+        changes.len(),
+        if args.dry_run { "to change" } else { "changed" },
+        plan.unchanged().len()
+    ))
+}
+
+async fn export(
+    client: &EdcConnectorClient,
+    args: ExportArgs,
+    printer: &mut Printer<'_>,
+) -> CliResult<()> {
+    let options = ExportOptions::builder().secrets(args.secrets).build();
+    let archive = client.archive().export_with_opts(options).await?;
+
+    // The archive itself is the output when written to stdout, so that it
+    // can be piped to `import`.
+    if args.file == Path::new("-") {
+        return Ok(archive.write_to(printer.writer()).map_err(Error::from)?);
+    }
+
+    archive
+        .write_to(BufWriter::new(File::create(&args.file)?))
+        .map_err(Error::from)?;
+    printer.message(&format!("Exported {} entities", archive.entries().len()))
+}
+
+async fn import(
+    client: &EdcConnectorClient,
+    args: ImportArgs,
+    printer: &mut Printer<'_>,
+) -> CliResult<()> {
+    let archive = Archive::read_from(read_content(&args.file)?.as_bytes()).map_err(Error::from)?;
+    let options = ImportOptions::builder()
+        .on_conflict(args.on_conflict.into())
+        .build();
+
+    printer.one(&client.archive().import_with_opts(&archive, options).await?)
+}
+
+fn read<T: DeserializeOwned>(args: &FileArgs) -> CliResult<T> {
+    Ok(serde_json::from_str(&read_content(args)?)?)
+}
+
+fn read_content(args: &FileArgs) -> CliResult<String> {
+    if args.file == Path::new("-") {
+        let mut content = String::new();
+        std::io::stdin().read_to_string(&mut content)?;
+        Ok(content)
+    } else {
+        Ok(std::fs::read_to_string(&args.file)?)
+    }
+}
+
+fn query(args: &QueryArgs) -> CliResult<Query> {
+    let mut query = Query::builder().limit(args.limit).offset(args.offset);
+    for filter in &args.filter {
+        query = query.criterion(criterion(filter)?);
+    }
+    Ok(query.build())
+}
+
+fn criterion(filter: &str) -> CliResult<Criterion> {
+    let mut parts = filter.trim().splitn(3, ' ');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(left), Some(operator), Some(right)) if !left.is_empty() => {
+            let operator = operator
+                .parse::<Operator>()
+                .map_err(|err| CliError::InvalidArgument(format!("filter {filter}: {err}")))?;
+            Ok(Criterion::new(left, operator, right.trim()))
+        }
+        _ => Err(CliError::InvalidArgument(format!(
+            "filter {filter} is not in the form <left> <operator> <right>"
+        ))),
+    }
+}
+
+fn protocol(protocol: Option<&str>) -> Protocol {
+    protocol.map(Protocol::from).unwrap_or_default()
+}
+
+fn dataset_request(id: &str, counter_party: &CounterPartyArgs) -> DatasetRequest {
+    DatasetRequest::builder()
+        .id(id)
+        .counter_party_address(&counter_party.counter_party_address)
+        .counter_party_id(&counter_party.counter_party_id)
+        .protocol(protocol(counter_party.protocol.as_deref()))
+        .build()
+}
@@ -0,0 +1,272 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    path::{Path, PathBuf},
+};
+
+use clap::ValueEnum;
+use edc_connector_client::{Auth, EdcConnectorApiVersion, EdcConnectorClient, OAuth2Config};
+use serde::Deserialize;
+
+use crate::{
+    cli::GlobalArgs,
+    error::{CliError, CliResult},
+    output::OutputFormat,
+};
+
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// The content of the config file, by default `~/.config/edc/config.toml`.
+///
+/// ```toml
+/// default_profile = "local"
+///
+/// [profiles.local]
+/// management_url = "http://localhost:29193/management"
+/// api_key = "password"
+///
+/// [profiles.virtual]
+/// management_url = "http://localhost:8081/api/mgmt"
+/// version = "v4"
+/// participant_context = "consumer"
+/// output = "json"
+///
+/// [profiles.virtual.oauth2]
+/// client_id = "consumer"
+/// client_secret = "secret"
+/// token_url = "http://localhost:8080/realms/edc/protocol/openid-connect/token"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    default_profile: Option<String>,
+    #[serde(default)]
+    profiles: BTreeMap<String, Profile>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    management_url: Option<String>,
+    version: Option<ApiVersion>,
+    participant_context: Option<String>,
+    api_key: Option<String>,
+    oauth2: Option<OAuth2Profile>,
+    output: Option<OutputFormat>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OAuth2Profile {
+    client_id: String,
+    client_secret: String,
+    token_url: String,
+    scopes: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiVersion {
+    V3,
+    V4,
+}
+
+impl From<ApiVersion> for EdcConnectorApiVersion {
+    fn from(version: ApiVersion) -> Self {
+        match version {
+            ApiVersion::V3 => EdcConnectorApiVersion::V3,
+            ApiVersion::V4 => EdcConnectorApiVersion::V4,
+        }
+    }
+}
+
+impl Config {
+    pub fn parse(content: &str) -> CliResult<Config> {
+        Ok(toml::from_str(content)?)
+    }
+
+    /// Loads the config at `path`, or at the default location when not given.
+    ///
+    /// A missing file at the default location yields an empty config.
+    pub fn load(path: Option<&Path>) -> CliResult<Config> {
+        let (path, explicit) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => match default_path() {
+                Some(path) => (path, false),
+                None => return Ok(Config::default()),
+            },
+        };
+
+        match std::fs::read_to_string(&path) {
+            Ok(content) => Config::parse(&content),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound && !explicit => {
+                Ok(Config::default())
+            }
+            Err(err) => Err(CliError::Config(format!(
+                "cannot read {}: {err}",
+                path.display()
+            ))),
+        }
+    }
+
+    pub fn profiles(&self) -> &BTreeMap<String, Profile> {
+        &self.profiles
+    }
+
+    /// Resolves the profile by name, falling back to `default_profile` and then
+    /// to the `default` one.
+    ///
+    /// Only an explicitly named profile has to exist.
+    pub fn profile(&self, name: Option<&str>) -> CliResult<Profile> {
+        match name {
+            Some(name) => self
+                .profiles
+                .get(name)
+                .cloned()
+                .ok_or_else(|| CliError::Config(format!("unknown profile {name}"))),
+            None => {
+                let name = self.default_profile.as_deref().unwrap_or(DEFAULT_PROFILE);
+                Ok(self.profiles.get(name).cloned().unwrap_or_default())
+            }
+        }
+    }
+}
+
+impl Profile {
+    /// Applies the command line overrides on top of the profile.
+    pub fn merge(mut self, args: &GlobalArgs) -> Profile {
+        if let Some(url) = &args.management_url {
+            self.management_url = Some(url.clone());
+        }
+        if let Some(version) = args.api_version {
+            self.version = Some(version);
+        }
+        if let Some(participant_context) = &args.participant_context {
+            self.participant_context = Some(participant_context.clone());
+        }
+        if let Some(api_key) = &args.api_key {
+            self.api_key = Some(api_key.clone());
+            self.oauth2 = None;
+        }
+        if let Some(output) = args.output {
+            self.output = Some(output);
+        }
+        self
+    }
+
+    pub fn output(&self) -> OutputFormat {
+        self.output.unwrap_or_default()
+    }
+
+    pub fn client(&self) -> CliResult<EdcConnectorClient> {
+        let management_url = self.management_url.as_deref().ok_or_else(|| {
+            CliError::Config(
+                "missing management url, set it in the profile or with --management-url"
+                    .to_string(),
+            )
+        })?;
+
+        let auth = match (&self.api_key, &self.oauth2) {
+            (Some(_), Some(_)) => {
+                return Err(CliError::Config(
+                    "api_key and oauth2 cannot be both set".to_string(),
+                ))
+            }
+            (Some(api_key), None) => Some(Auth::api_token(api_key)),
+            (None, Some(oauth2)) => Some(Auth::oauth(
+                OAuth2Config::builder()
+                    .client_id(&oauth2.client_id)
+                    .client_secret(&oauth2.client_secret)
+                    .token_url(&oauth2.token_url)
+                    .maybe_scopes(oauth2.scopes.clone())
+                    .build(),
+            )?),
+            (None, None) => None,
+        };
+
+        let mut builder = EdcConnectorClient::builder()
+            .management_url(management_url)
+            .version(self.version.unwrap_or(ApiVersion::V3).into())
+            .maybe_participant_context(self.participant_context.clone());
+        if let Some(auth) = auth {
+            builder = builder.with_auth(auth);
+        }
+
+        Ok(builder.build()?)
+    }
+}
+
+fn default_path() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|dir| dir.join("edc").join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+        default_profile = "local"
+
+        [profiles.local]
+        management_url = "http://localhost/management"
+        api_key = "password"
+
+        [profiles.virtual]
+        management_url = "http://localhost/api/mgmt"
+        version = "v4"
+        participant_context = "consumer"
+        output = "json"
+
+        [profiles.virtual.oauth2]
+        client_id = "consumer"
+        client_secret = "secret"
+        token_url = "http://localhost/token"
+    "#;
+
+    #[test]
+    fn should_resolve_profiles() {
+        let config = Config::parse(CONFIG).unwrap();
+
+        let local = config.profile(None).unwrap();
+        assert_eq!(
+            local.management_url.as_deref(),
+            Some("http://localhost/management")
+        );
+        assert_eq!(local.output(), OutputFormat::Table);
+
+        let virtual_profile = config.profile(Some("virtual")).unwrap();
+        assert_eq!(virtual_profile.version, Some(ApiVersion::V4));
+        assert_eq!(virtual_profile.output(), OutputFormat::Json);
+        assert!(virtual_profile.oauth2.is_some());
+
+        assert!(config.profile(Some("missing")).is_err());
+    }
+
+    #[test]
+    fn should_override_profile_with_arguments() {
+        let config = Config::parse(CONFIG).unwrap();
+        let args = GlobalArgs {
+            management_url: Some("http://other".to_string()),
+            api_key: Some("key".to_string()),
+            ..GlobalArgs::default()
+        };
+
+        let profile = config.profile(Some("virtual")).unwrap().merge(&args);
+
+        assert_eq!(profile.management_url.as_deref(), Some("http://other"));
+        assert_eq!(profile.api_key.as_deref(), Some("key"));
+        assert!(profile.oauth2.is_none());
+        assert!(profile.client().is_ok());
+    }
+
+    #[test]
+    fn should_require_management_url() {
+        assert!(matches!(
+            Profile::default().client(),
+            Err(CliError::Config(_))
+        ));
+    }
+}
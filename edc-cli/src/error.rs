@@ -0,0 +1,22 @@
+use edc_connector_client::{BuilderError, Error};
+use thiserror::Error;
+
+pub type CliResult<T> = Result<T, CliError>;
+
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error("Configuration error: {0}")]
+    Config(String),
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse the config file: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("Invalid json: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Builder(#[from] BuilderError),
+    #[error(transparent)]
+    Client(#[from] Error),
+}
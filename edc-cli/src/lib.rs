@@ -0,0 +1,8 @@
+pub mod cli;
+pub mod commands;
+pub mod config;
+mod error;
+pub mod output;
+
+pub use commands::run;
+pub use error::{CliError, CliResult};
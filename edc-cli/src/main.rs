@@ -0,0 +1,37 @@
+use std::process::ExitCode;
+
+use clap::Parser;
+use edc_cli::{cli::Cli, CliError};
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match edc_cli::run(cli, &mut std::io::stdout()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            report(&err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn report(err: &CliError) {
+    eprintln!("error: {err}");
+    let CliError::Client(err) = err else {
+        return;
+    };
+    for detail in err
+        .management_api_error()
+        .map(|err| err.details())
+        .unwrap_or_default()
+    {
+        match (&detail.path, &detail.invalid_value) {
+            (Some(path), Some(value)) => {
+                eprintln!("  {}: {} ({path} = {value})", detail.kind, detail.message)
+            }
+            (Some(path), None) => eprintln!("  {}: {} ({path})", detail.kind, detail.message),
+            _ => eprintln!("  {}: {}", detail.kind, detail.message),
+        }
+    }
+}
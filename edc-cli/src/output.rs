@@ -0,0 +1,292 @@
+use std::io::Write;
+
+use clap::ValueEnum;
+use comfy_table::{presets::UTF8_FULL_CONDENSED, Table};
+use edc_connector_client::{
+    api::{Change, ImportSummary},
+    types::{
+        asset::Asset, catalog::Dataset, contract_agreement::ContractAgreement,
+        contract_definition::ContractDefinition, contract_negotiation::ContractNegotiation,
+        data_address::DataAddress, edr::EndpointDataReferenceEntry, policy::PolicyDefinition,
+        response::IdResponse, secret::Secret, transfer_process::TransferProcess,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use crate::error::CliResult;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+}
+
+/// Rendering of an entity as a table row.
+pub trait Tabular {
+    fn headers() -> Vec<&'static str>;
+
+    fn row(&self) -> Vec<String>;
+}
+
+pub struct Printer<'a> {
+    format: OutputFormat,
+    out: &'a mut dyn Write,
+}
+
+impl<'a> Printer<'a> {
+    pub fn new(format: OutputFormat, out: &'a mut dyn Write) -> Printer<'a> {
+        Printer { format, out }
+    }
+
+    pub fn one<T: Serialize + Tabular>(&mut self, item: &T) -> CliResult<()> {
+        match self.format {
+            OutputFormat::Json => {
+                serde_json::to_writer_pretty(&mut self.out, item)?;
+                writeln!(self.out)?;
+                Ok(())
+            }
+            OutputFormat::Table => self.many(std::slice::from_ref(item)),
+        }
+    }
+
+    pub fn many<T: Serialize + Tabular>(&mut self, items: &[T]) -> CliResult<()> {
+        match self.format {
+            OutputFormat::Json => {
+                serde_json::to_writer_pretty(&mut self.out, items)?;
+                writeln!(self.out)?;
+            }
+            OutputFormat::Table => {
+                let mut table = Table::new();
+                table
+                    .load_preset(UTF8_FULL_CONDENSED)
+                    .set_header(T::headers())
+                    .add_rows(items.iter().map(Tabular::row));
+                writeln!(self.out, "{table}")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The raw output, for content written as is whatever the format.
+    pub fn writer(&mut self) -> &mut dyn Write {
+        self.out
+    }
+
+    pub fn message(&mut self, message: &str) -> CliResult<()> {
+        if self.format == OutputFormat::Table {
+            writeln!(self.out, "{message}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Tabular for IdResponse<String> {
+    fn headers() -> Vec<&'static str> {
+        vec!["ID", "CREATED AT"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.id().clone(), self.created_at().to_string()]
+    }
+}
+
+impl Tabular for Asset {
+    fn headers() -> Vec<&'static str> {
+        vec!["ID", "DATA ADDRESS TYPE", "PROPERTIES"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id().to_string(),
+            self.data_address().kind().unwrap_or_default().to_string(),
+            json(self.properties()),
+        ]
+    }
+}
+
+impl Tabular for PolicyDefinition {
+    fn headers() -> Vec<&'static str> {
+        vec!["ID", "PERMISSIONS", "PROHIBITIONS", "OBLIGATIONS"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        let policy = self.policy();
+        vec![
+            self.id().to_string(),
+            policy.permissions().len().to_string(),
+            policy.prohibitions().len().to_string(),
+            policy.obligations().len().to_string(),
+        ]
+    }
+}
+
+impl Tabular for ContractDefinition {
+    fn headers() -> Vec<&'static str> {
+        vec!["ID", "ACCESS POLICY", "CONTRACT POLICY", "ASSETS SELECTOR"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id().to_string(),
+            self.access_policy_id().to_string(),
+            self.contract_policy_id().to_string(),
+            json(self.assets_selector()),
+        ]
+    }
+}
+
+impl Tabular for Dataset {
+    fn headers() -> Vec<&'static str> {
+        vec!["DATASET", "OFFERS", "FORMATS"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        let offers = self
+            .offers()
+            .iter()
+            .filter_map(|offer| offer.id().cloned())
+            .collect::<Vec<_>>();
+        vec![
+            self.id().to_string(),
+            offers.join("\n"),
+            self.formats().join("\n"),
+        ]
+    }
+}
+
+impl Tabular for ContractNegotiation {
+    fn headers() -> Vec<&'static str> {
+        vec!["ID", "STATE", "COUNTER PARTY", "AGREEMENT"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id().to_string(),
+            self.state().as_str().to_string(),
+            self.counter_party_id().to_string(),
+            self.contract_agreement_id().cloned().unwrap_or_default(),
+        ]
+    }
+}
+
+impl Tabular for ContractAgreement {
+    fn headers() -> Vec<&'static str> {
+        vec!["ID", "ASSET", "PROVIDER", "CONSUMER", "SIGNED AT"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id().to_string(),
+            self.asset_id().to_string(),
+            self.provider_id().to_string(),
+            self.consumer_id().to_string(),
+            self.contract_signing_date().to_string(),
+        ]
+    }
+}
+
+impl Tabular for TransferProcess {
+    fn headers() -> Vec<&'static str> {
+        vec!["ID", "STATE", "ASSET", "CONTRACT", "TRANSFER TYPE"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id().to_string(),
+            self.state().as_str().to_string(),
+            self.asset_id().to_string(),
+            self.contract_id().to_string(),
+            self.transfer_type().to_string(),
+        ]
+    }
+}
+
+impl Tabular for EndpointDataReferenceEntry {
+    fn headers() -> Vec<&'static str> {
+        vec!["TRANSFER PROCESS", "ASSET", "AGREEMENT", "PROVIDER"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.transfer_process_id().to_string(),
+            self.asset_id().to_string(),
+            self.agreement_id().to_string(),
+            self.provider_id().to_string(),
+        ]
+    }
+}
+
+impl Tabular for DataAddress {
+    fn headers() -> Vec<&'static str> {
+        vec!["TYPE", "ENDPOINT", "AUTHORIZATION", "EXPIRES IN"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.kind().unwrap_or_default().to_string(),
+            self.endpoint().unwrap_or_default().to_string(),
+            self.authorization().unwrap_or_default().to_string(),
+            self.expires_in()
+                .map(|ttl| format!("{}s", ttl.as_secs()))
+                .unwrap_or_default(),
+        ]
+    }
+}
+
+impl Tabular for Secret {
+    fn headers() -> Vec<&'static str> {
+        vec!["ID", "VALUE"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.id().to_string(), self.value().to_string()]
+    }
+}
+
+impl Tabular for ImportSummary {
+    fn headers() -> Vec<&'static str> {
+        vec!["CREATED", "OVERWRITTEN", "SKIPPED"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.created().to_string(),
+            self.overwritten().to_string(),
+            self.skipped().to_string(),
+        ]
+    }
+}
+
+/// A change of the reconciliation plan of a manifest.
+#[derive(Debug, Serialize)]
+pub struct PlannedChange {
+    operation: String,
+    kind: String,
+    id: String,
+}
+
+impl From<&Change> for PlannedChange {
+    fn from(change: &Change) -> Self {
+        PlannedChange {
+            operation: change.operation().to_string(),
+            kind: change.kind().to_string(),
+            id: change.id().to_string(),
+        }
+    }
+}
+
+impl Tabular for PlannedChange {
+    fn headers() -> Vec<&'static str> {
+        vec!["OPERATION", "KIND", "ID"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.operation.clone(), self.kind.clone(), self.id.clone()]
+    }
+}
+
+fn json<T: Serialize + ?Sized>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
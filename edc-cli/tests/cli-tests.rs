@@ -0,0 +1,297 @@
+#[allow(clippy::unwrap_used)]
+mod cli {
+    use std::path::PathBuf;
+
+    use clap::Parser;
+    use edc_cli::{cli::Cli, CliError};
+    use edc_connector_client::{
+        mock::MockConnector,
+        types::{
+            archive::Archive,
+            contract_definition::NewContractDefinition,
+            policy::{Action, NewPolicyDefinition, Permission, Policy},
+            query::Criterion,
+        },
+        EDC_NAMESPACE,
+    };
+    use serde_json::{json, Value};
+
+    async fn run(connector: &MockConnector, args: &[&str]) -> Result<Value, CliError> {
+        let out = run_raw(connector, &[&["-o", "json"], args].concat()).await?;
+        Ok(serde_json::from_slice(&out).unwrap_or(Value::Null))
+    }
+
+    async fn run_raw(connector: &MockConnector, args: &[&str]) -> Result<Vec<u8>, CliError> {
+        let url = connector.url();
+        let cli = Cli::try_parse_from(["edc", "--management-url", url].iter().chain(args)).unwrap();
+
+        let mut out = Vec::new();
+        edc_cli::run(cli, &mut out).await?;
+        Ok(out)
+    }
+
+    fn write_temp(name: &str, content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("{}-{}", uuid::Uuid::new_v4(), name));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn should_create_and_list_assets() {
+        let connector = MockConnector::start().await.unwrap();
+        let file = write_temp(
+            "asset.json",
+            &json!({
+                "@id": "asset",
+                "@type": "Asset",
+                "properties": { "group": "a" },
+                "dataAddress": { "@type": "DataAddress", "type": "HttpData" }
+            })
+            .to_string(),
+        );
+
+        let created = run(
+            &connector,
+            &["assets", "create", "-f", file.to_str().unwrap()],
+        )
+        .await
+        .unwrap();
+        assert_eq!(created["@id"], "asset");
+
+        let assets = run(
+            &connector,
+            &[
+                "assets",
+                "list",
+                "--filter",
+                "https://w3id.org/edc/v0.0.1/ns/group = a",
+            ],
+        )
+        .await
+        .unwrap();
+        assert_eq!(assets.as_array().unwrap().len(), 1);
+
+        let asset = run(&connector, &["assets", "get", "asset"]).await.unwrap();
+        assert_eq!(asset["@id"], "asset");
+
+        let missing = run(&connector, &["assets", "get", "missing"]).await;
+        assert!(matches!(missing, Err(CliError::Client(_))));
+
+        let invalid = run(&connector, &["assets", "list", "--filter", "id"]).await;
+        assert!(matches!(invalid, Err(CliError::InvalidArgument(_))));
+    }
+
+    #[tokio::test]
+    async fn should_negotiate_and_transfer() {
+        let connector = MockConnector::start().await.unwrap();
+        let client = connector.client().unwrap();
+        let file = write_temp(
+            "asset.json",
+            &json!({ "@id": "asset", "@type": "Asset", "properties": {}, "dataAddress": { "@type": "DataAddress", "type": "HttpData" } }).to_string(),
+        );
+        run(
+            &connector,
+            &["assets", "create", "-f", file.to_str().unwrap()],
+        )
+        .await
+        .unwrap();
+
+        let policy = NewPolicyDefinition::builder()
+            .id("policy")
+            .policy(
+                Policy::builder()
+                    .permission(Permission::builder().action(Action::simple("use")).build())
+                    .build(),
+            )
+            .build();
+        client.policies().create(&policy).await.unwrap();
+        let definition = NewContractDefinition::builder()
+            .id("definition")
+            .asset_selector(Criterion::new(format!("{}id", EDC_NAMESPACE), "=", "asset"))
+            .access_policy_id("policy")
+            .contract_policy_id("policy")
+            .build();
+        client
+            .contract_definitions()
+            .create(&definition)
+            .await
+            .unwrap();
+
+        let counter_party = [
+            "--counter-party-address",
+            "http://provider/protocol",
+            "--counter-party-id",
+            "provider",
+        ];
+
+        let negotiation = run(
+            &connector,
+            &[
+                &["negotiate", "start", "--asset-id", "asset", "--wait"][..],
+                &counter_party[..],
+            ]
+            .concat(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(negotiation["state"], "FINALIZED");
+        let agreement_id = negotiation["contractAgreementId"].as_str().unwrap();
+
+        let transfer = run(
+            &connector,
+            &[
+                "transfer",
+                "start",
+                "--contract-id",
+                agreement_id,
+                "--counter-party-address",
+                "http://provider/protocol",
+                "--wait",
+            ],
+        )
+        .await
+        .unwrap();
+        assert_eq!(transfer["state"], "STARTED");
+        let transfer_id = transfer["@id"].as_str().unwrap();
+
+        let data_address = run(&connector, &["edrs", "data-address", transfer_id])
+            .await
+            .unwrap();
+        assert!(data_address["endpoint"].is_string());
+    }
+
+    #[tokio::test]
+    async fn should_use_profile_of_config_file() {
+        let connector = MockConnector::start().await.unwrap();
+        let config = write_temp(
+            "config.toml",
+            &format!(
+                "[profiles.mock]\nmanagement_url = \"{}\"\noutput = \"json\"\n",
+                connector.url()
+            ),
+        );
+
+        let cli = Cli::try_parse_from([
+            "edc",
+            "--config",
+            config.to_str().unwrap(),
+            "-p",
+            "mock",
+            "secrets",
+            "create",
+            "--id",
+            "secret",
+            "--value",
+            "value",
+        ])
+        .unwrap();
+        let mut out = Vec::new();
+        edc_cli::run(cli, &mut out).await.unwrap();
+
+        let created: Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(created["@id"], "secret");
+
+        let cli = Cli::try_parse_from([
+            "edc",
+            "--config",
+            config.to_str().unwrap(),
+            "-p",
+            "unknown",
+            "secrets",
+            "get",
+            "secret",
+        ])
+        .unwrap();
+        let result = edc_cli::run(cli, &mut Vec::new()).await;
+        assert!(matches!(result, Err(CliError::Config(_))));
+    }
+
+    #[tokio::test]
+    async fn should_apply_manifest() {
+        let connector = MockConnector::start().await.unwrap();
+        let manifest = write_temp(
+            "manifest.yaml",
+            r#"
+assets:
+  - "@id": asset
+    properties: {}
+    dataAddress:
+      type: HttpData
+policyDefinitions:
+  - "@id": policy
+    policy:
+      permission:
+        - action: use
+"#,
+        );
+        let file = manifest.to_str().unwrap();
+
+        let plan = run(&connector, &["apply", "-f", file, "--dry-run"])
+            .await
+            .unwrap();
+        assert_eq!(plan.as_array().unwrap().len(), 2);
+        assert_eq!(plan[0]["operation"], "create");
+        assert_eq!(plan[0]["kind"], "asset");
+
+        run(&connector, &["apply", "-f", file]).await.unwrap();
+        let applied = run(&connector, &["apply", "-f", file]).await.unwrap();
+        assert!(applied.as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_export_and_import_archive() {
+        let source = MockConnector::start().await.unwrap();
+        let asset = write_temp(
+            "asset.json",
+            &json!({ "@id": "asset", "@type": "Asset", "properties": {}, "dataAddress": { "@type": "DataAddress", "type": "HttpData" } }).to_string(),
+        );
+        run(
+            &source,
+            &["assets", "create", "-f", asset.to_str().unwrap()],
+        )
+        .await
+        .unwrap();
+
+        let archive = std::env::temp_dir().join(format!("{}-archive.jsonl", uuid::Uuid::new_v4()));
+        let archive = archive.to_str().unwrap();
+        run(&source, &["export", "-f", archive]).await.unwrap();
+
+        let target = MockConnector::start().await.unwrap();
+        let summary = run(&target, &["import", "-f", archive]).await.unwrap();
+        assert_eq!(summary["created"], 1);
+
+        let summary = run(&target, &["import", "-f", archive, "--on-conflict", "skip"])
+            .await
+            .unwrap();
+        assert_eq!(summary["skipped"], 1);
+    }
+
+    #[tokio::test]
+    async fn should_export_archive_to_stdout_for_piping() {
+        let source = MockConnector::start().await.unwrap();
+        let asset = write_temp(
+            "asset.json",
+            &json!({ "@id": "asset", "@type": "Asset", "properties": {}, "dataAddress": { "@type": "DataAddress", "type": "HttpData" } }).to_string(),
+        );
+        run(
+            &source,
+            &["assets", "create", "-f", asset.to_str().unwrap()],
+        )
+        .await
+        .unwrap();
+
+        let exported = run_raw(&source, &["export", "-f", "-"]).await.unwrap();
+        let archive = Archive::read_from(exported.as_slice()).unwrap();
+        assert_eq!(archive.entries().len(), 1);
+
+        let piped = write_temp("archive.jsonl", std::str::from_utf8(&exported).unwrap());
+        let target = MockConnector::start().await.unwrap();
+        let summary = run(&target, &["import", "-f", piped.to_str().unwrap()])
+            .await
+            .unwrap();
+        assert_eq!(summary["created"], 1);
+
+        let asset = run(&target, &["assets", "get", "asset"]).await.unwrap();
+        assert_eq!(asset["@id"], "asset");
+    }
+}
@@ -1,23 +1,39 @@
+mod archive;
 mod assets;
 mod catalog;
+mod consumer;
 mod contract_agreement;
 mod contract_definitions;
 mod contract_negotiations;
+mod crawler;
 mod dataplanes;
 mod edrs;
+mod pagination;
 mod participants;
 mod policies;
+mod provisioning;
 mod secrets;
 mod transfer_process;
+mod wait;
 
+pub use archive::{ArchiveApi, ConflictStrategy, ExportOptions, ImportOptions, ImportSummary};
 pub use assets::AssetApi;
 pub use catalog::CatalogApi;
+pub use consumer::{
+    AcquireOptions, AcquiredData, ConsumerApi, OfferSelection, OfferSelector, Provider,
+};
 pub use contract_agreement::ContractAgreementApi;
 pub use contract_definitions::ContractDefinitionApi;
 pub use contract_negotiations::ContractNegotiationApi;
+pub use crawler::{
+    CatalogCrawler, CrawlFailure, CrawlerOptions, FederatedCatalog, ParticipantRegistry,
+    ProviderCatalog,
+};
 pub use dataplanes::DataPlaneApi;
-pub use edrs::EdrApi;
+pub use edrs::{EdrApi, EdrCache};
 pub use participants::{ParticipantContextApi, ParticipantContextConfigApi};
 pub use policies::PolicyApi;
+pub use provisioning::{Change, Operation, Plan, ProvisioningApi, ReconcileOptions, Resource};
 pub use secrets::SecretsApi;
 pub use transfer_process::TransferProcessApi;
+pub use wait::WaitOptions;
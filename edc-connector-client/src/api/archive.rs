@@ -0,0 +1,197 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bon::Builder;
+use serde::Serialize;
+
+use crate::{
+    api::{AssetApi, ContractDefinitionApi, PolicyApi, SecretsApi},
+    client::{EdcConnectorApiVersion, EdcConnectorClientInternal},
+    error::ArchiveError,
+    types::{
+        archive::{Archive, ArchiveEntry, ArchiveHeader},
+        query::Query,
+    },
+    EdcResult,
+};
+
+/// Export and import of the management state of a connector.
+pub struct ArchiveApi<'a>(&'a EdcConnectorClientInternal);
+
+#[derive(Debug, Clone, Builder)]
+pub struct ExportOptions {
+    /// Ids of the secrets to export, since secrets cannot be listed.
+    #[builder(default, into)]
+    secrets: Vec<String>,
+    #[builder(default = 100)]
+    page_size: u32,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        ExportOptions::builder().build()
+    }
+}
+
+/// What to do when an entity of the archive already exists in the connector.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictStrategy {
+    #[default]
+    Fail,
+    Skip,
+    Overwrite,
+}
+
+#[derive(Debug, Clone, Default, Builder)]
+pub struct ImportOptions {
+    #[builder(default)]
+    on_conflict: ConflictStrategy,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportSummary {
+    created: usize,
+    overwritten: usize,
+    skipped: usize,
+}
+
+impl ImportSummary {
+    pub fn created(&self) -> usize {
+        self.created
+    }
+
+    pub fn overwritten(&self) -> usize {
+        self.overwritten
+    }
+
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+}
+
+impl<'a> ArchiveApi<'a> {
+    pub(crate) fn new(client: &'a EdcConnectorClientInternal) -> ArchiveApi<'a> {
+        ArchiveApi(client)
+    }
+
+    /// Exports all the assets, policy definitions and contract definitions.
+    pub async fn export(&self) -> EdcResult<Archive> {
+        self.export_with_opts(ExportOptions::default()).await
+    }
+
+    pub async fn export_with_opts(&self, options: ExportOptions) -> EdcResult<Archive> {
+        let query = Query::builder().limit(options.page_size).build();
+        let mut entries = Vec::new();
+
+        let secrets = SecretsApi::new(self.0);
+        for id in &options.secrets {
+            entries.push(ArchiveEntry::Secret(secrets.get(id).await?));
+        }
+
+        let assets = AssetApi::new(self.0).query_all(query.clone()).await?;
+        entries.extend(assets.into_iter().map(ArchiveEntry::Asset));
+
+        let policy_definitions = PolicyApi::new(self.0).query_all(query.clone()).await?;
+        entries.extend(
+            policy_definitions
+                .into_iter()
+                .map(ArchiveEntry::PolicyDefinition),
+        );
+
+        let contract_definitions = ContractDefinitionApi::new(self.0).query_all(query).await?;
+        entries.extend(
+            contract_definitions
+                .into_iter()
+                .map(ArchiveEntry::ContractDefinition),
+        );
+
+        let api_version = match self.0.version {
+            EdcConnectorApiVersion::V3 => "v3",
+            EdcConnectorApiVersion::V4 => "v4",
+        };
+        let exported_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as i64)
+            .unwrap_or_default();
+        let header =
+            ArchiveHeader::new(api_version, self.0.participant_context.clone(), exported_at);
+
+        Ok(Archive::new(header, entries))
+    }
+
+    /// Imports the archive, failing on the first entity that already exists.
+    pub async fn import(&self, archive: &Archive) -> EdcResult<ImportSummary> {
+        self.import_with_opts(archive, ImportOptions::default())
+            .await
+    }
+
+    /// Imports the entities in dependency order: secrets, assets, policy
+    /// definitions and then contract definitions.
+    pub async fn import_with_opts(
+        &self,
+        archive: &Archive,
+        options: ImportOptions,
+    ) -> EdcResult<ImportSummary> {
+        let mut entries = archive.entries().iter().collect::<Vec<_>>();
+        entries.sort_by_key(|entry| entry.kind());
+
+        let mut summary = ImportSummary::default();
+        for entry in entries {
+            match self.create(entry).await {
+                Ok(()) => summary.created += 1,
+                Err(err) if err.is_conflict() => match options.on_conflict {
+                    ConflictStrategy::Fail => {
+                        return Err(ArchiveError::Conflict {
+                            kind: entry.kind(),
+                            id: entry.id().to_string(),
+                        }
+                        .into())
+                    }
+                    ConflictStrategy::Skip => summary.skipped += 1,
+                    ConflictStrategy::Overwrite => {
+                        self.update(entry).await?;
+                        summary.overwritten += 1;
+                    }
+                },
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(summary)
+    }
+
+    async fn create(&self, entry: &ArchiveEntry) -> EdcResult<()> {
+        match entry.clone() {
+            ArchiveEntry::Secret(secret) => {
+                SecretsApi::new(self.0).create(&secret.into()).await?;
+            }
+            ArchiveEntry::Asset(asset) => {
+                AssetApi::new(self.0).create(&asset.into()).await?;
+            }
+            ArchiveEntry::PolicyDefinition(policy_definition) => {
+                PolicyApi::new(self.0)
+                    .create(&policy_definition.into())
+                    .await?;
+            }
+            ArchiveEntry::ContractDefinition(contract_definition) => {
+                ContractDefinitionApi::new(self.0)
+                    .create(&contract_definition.into())
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn update(&self, entry: &ArchiveEntry) -> EdcResult<()> {
+        match entry {
+            ArchiveEntry::Secret(secret) => SecretsApi::new(self.0).update(secret).await,
+            ArchiveEntry::Asset(asset) => AssetApi::new(self.0).update(asset).await,
+            ArchiveEntry::PolicyDefinition(policy_definition) => {
+                PolicyApi::new(self.0).update(policy_definition).await
+            }
+            ArchiveEntry::ContractDefinition(contract_definition) => {
+                ContractDefinitionApi::new(self.0)
+                    .update(contract_definition)
+                    .await
+            }
+        }
+    }
+}
@@ -1,9 +1,12 @@
+use futures::{Stream, TryStreamExt};
+
 use crate::{
+    api::pagination::paginate,
     client::EdcConnectorClientInternal,
     types::{
         asset::{Asset, NewAsset},
         context::WithContext,
-        query::Query,
+        query::{Pagination, Query},
         response::IdResponse,
     },
     EdcResult,
@@ -40,11 +43,30 @@ impl<'a> AssetApi<'a> {
     pub async fn query(&self, query: Query) -> EdcResult<Vec<Asset>> {
         let url = self.0.path_for(&["assets", "request"]);
         self.0
-            .post::<_, Vec<WithContext<Asset>>>(url, &self.0.context_for(&query))
+            .post_idempotent::<_, Vec<WithContext<Asset>>>(url, &self.0.context_for(&query))
             .await
             .map(|results| results.into_iter().map(|ctx| ctx.inner).collect())
     }
 
+    pub fn query_stream(&self, query: Query) -> impl Stream<Item = EdcResult<Asset>> + 'a {
+        self.query_stream_with_opts(query, Pagination::default())
+    }
+
+    pub fn query_stream_with_opts(
+        &self,
+        query: Query,
+        pagination: Pagination,
+    ) -> impl Stream<Item = EdcResult<Asset>> + 'a {
+        let client = self.0;
+        paginate(query, pagination, move |query| async move {
+            AssetApi::new(client).query(query).await
+        })
+    }
+
+    pub async fn query_all(&self, query: Query) -> EdcResult<Vec<Asset>> {
+        self.query_stream(query).try_collect().await
+    }
+
     pub async fn delete(&self, id: &str) -> EdcResult<()> {
         let url = self.0.path_for(&["assets", id]);
         self.0.del(url).await
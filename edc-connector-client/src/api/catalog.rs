@@ -1,8 +1,14 @@
+use std::{collections::HashSet, future};
+
+use futures::{stream, Stream, StreamExt};
+
 use crate::{
+    api::pagination::paginate_until,
     client::EdcConnectorClientInternal,
     types::{
         catalog::{Catalog, CatalogRequest, Dataset, DatasetRequest},
         context::WithContext,
+        query::Pagination,
     },
     EdcResult,
 };
@@ -18,16 +24,124 @@ impl<'a> CatalogApi<'a> {
         let url = self.0.path_for(&["catalog", "request"]);
 
         self.0
-            .post::<_, WithContext<Catalog>>(url, &self.0.context_for(request))
+            .post_idempotent::<_, WithContext<Catalog>>(url, &self.0.context_for(request))
             .await
             .map(|ctx| ctx.inner)
     }
 
+    /// Walks the datasets of the remote catalog page by page, following the
+    /// offset and limit of the request's `query_spec`.
+    ///
+    /// Datasets of nested catalogs are yielded after those of their parent,
+    /// and datasets already seen with the same `@id` are skipped. The
+    /// `max_items` of the pagination caps the datasets yielded.
+    pub fn datasets_stream(
+        &self,
+        request: &CatalogRequest,
+    ) -> impl Stream<Item = EdcResult<Dataset>> + 'a {
+        self.datasets_stream_with_opts(request, Pagination::default())
+    }
+
+    pub fn datasets_stream_with_opts(
+        &self,
+        request: &CatalogRequest,
+        pagination: Pagination,
+    ) -> impl Stream<Item = EdcResult<Dataset>> + 'a {
+        let client = self.0;
+        let query = request.query_spec().clone();
+        let request = request.clone();
+        let max_items = pagination.max_items().unwrap_or(usize::MAX);
+        let mut seen = HashSet::new();
+        let mut paged = HashSet::new();
+
+        paginate_until(
+            query,
+            pagination,
+            move |query| {
+                let request = request.with_query_spec(query);
+                async move {
+                    let catalog = CatalogApi::new(client).request(&request).await?;
+                    Ok(entries(catalog))
+                }
+            },
+            // a provider ignoring the offset keeps returning the same page
+            move |entries| {
+                let mut exhausted = true;
+                for id in entries.iter().flat_map(Entry::ids) {
+                    exhausted &= !paged.insert(id);
+                }
+                exhausted
+            },
+        )
+        .flat_map(|entry| {
+            let datasets = match entry {
+                Ok(entry) => entry.into_datasets().into_iter().map(Ok).collect(),
+                Err(err) => vec![Err(err)],
+            };
+            stream::iter(datasets)
+        })
+        .filter(move |dataset| {
+            future::ready(match dataset {
+                Ok(dataset) => seen.insert(dataset.id().to_string()),
+                Err(_) => true,
+            })
+        })
+        .take(max_items)
+    }
+
     pub async fn dataset(&self, request: &DatasetRequest) -> EdcResult<Dataset> {
         let url = self.0.path_for(&["catalog", "dataset", "request"]);
         self.0
-            .post::<_, WithContext<Dataset>>(url, &self.0.context_for(request))
+            .post_idempotent::<_, WithContext<Dataset>>(url, &self.0.context_for(request))
             .await
             .map(|ctx| ctx.inner)
     }
 }
+
+/// An item of a catalog page, as the limit applies to both datasets and
+/// nested catalogs.
+enum Entry {
+    Dataset(Dataset),
+    Catalog(Catalog),
+}
+
+impl Entry {
+    /// The ids of the dataset, or of the catalog and everything it contains.
+    fn ids(&self) -> Vec<String> {
+        match self {
+            Entry::Dataset(dataset) => vec![dataset.id().to_string()],
+            Entry::Catalog(catalog) => catalog_ids(catalog),
+        }
+    }
+
+    fn into_datasets(self) -> Vec<Dataset> {
+        match self {
+            Entry::Dataset(dataset) => vec![dataset],
+            Entry::Catalog(catalog) => catalog.into_datasets(),
+        }
+    }
+}
+
+fn catalog_ids(catalog: &Catalog) -> Vec<String> {
+    catalog
+        .id()
+        .map(str::to_string)
+        .into_iter()
+        .chain(
+            catalog
+                .datasets()
+                .iter()
+                .map(|dataset| dataset.id().to_string()),
+        )
+        .chain(catalog.catalogs().iter().flat_map(catalog_ids))
+        .collect()
+}
+
+fn entries(catalog: Catalog) -> Vec<Entry> {
+    let (datasets, catalogs) = catalog.into_parts();
+    datasets
+        .into_iter()
+        .map(Entry::Dataset)
+        .chain(catalogs.into_iter().map(Entry::Catalog))
+        .collect()
+}
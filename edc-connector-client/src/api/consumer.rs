@@ -0,0 +1,317 @@
+use std::{fmt, ops::ControlFlow, sync::Arc};
+
+use bon::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::{
+        wait::{poll_until, WaitOptions},
+        CatalogApi, ContractNegotiationApi, EdrApi, TransferProcessApi,
+    },
+    client::EdcConnectorClientInternal,
+    error::{WorkflowError, WorkflowStep},
+    types::{
+        catalog::DatasetRequest,
+        contract_negotiation::{ContractNegotiationState, ContractRequest},
+        data_address::DataAddress,
+        policy::Policy,
+        transfer_process::{TransferProcessState, TransferRequest},
+        Protocol,
+    },
+    EdcResult, Error,
+};
+
+/// High level consumer workflows built on top of the management api.
+pub struct ConsumerApi<'a>(&'a EdcConnectorClientInternal);
+
+/// The provider connector the data is acquired from.
+#[derive(Debug, Clone, Builder, Deserialize, Serialize)]
+pub struct Provider {
+    #[builder(into)]
+    id: String,
+    #[builder(into)]
+    address: String,
+    #[builder(default, into)]
+    #[serde(default)]
+    protocol: Protocol,
+}
+
+impl Provider {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn protocol(&self) -> &Protocol {
+        &self.protocol
+    }
+}
+
+pub type OfferSelector = Arc<dyn Fn(&[Policy]) -> Option<Policy> + Send + Sync>;
+
+/// Strategy for picking the offer to negotiate among the ones of a dataset.
+#[derive(Clone, Default)]
+pub enum OfferSelection {
+    #[default]
+    First,
+    ById(String),
+    Custom(OfferSelector),
+}
+
+impl OfferSelection {
+    pub fn custom(f: impl Fn(&[Policy]) -> Option<Policy> + Send + Sync + 'static) -> Self {
+        OfferSelection::Custom(Arc::new(f))
+    }
+
+    pub fn select(&self, offers: &[Policy]) -> Option<Policy> {
+        match self {
+            OfferSelection::First => offers.first().cloned(),
+            OfferSelection::ById(id) => offers.iter().find(|offer| offer.id() == Some(id)).cloned(),
+            OfferSelection::Custom(f) => f(offers),
+        }
+    }
+}
+
+impl fmt::Debug for OfferSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OfferSelection::First => f.write_str("First"),
+            OfferSelection::ById(id) => f.debug_tuple("ById").field(id).finish(),
+            OfferSelection::Custom(_) => f.write_str("Custom"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Builder)]
+pub struct AcquireOptions {
+    #[builder(default = "HttpData-PULL".to_string(), into)]
+    transfer_type: String,
+    #[builder(default)]
+    offer_selection: OfferSelection,
+    destination: Option<DataAddress>,
+    #[builder(default)]
+    negotiation_wait: WaitOptions,
+    #[builder(default)]
+    transfer_wait: WaitOptions,
+    #[builder(default)]
+    edr_wait: WaitOptions,
+    /// Resumes the workflow from an already started negotiation.
+    #[builder(into)]
+    negotiation_id: Option<String>,
+    /// Resumes the workflow from an existing contract agreement, skipping the negotiation.
+    #[builder(into)]
+    agreement_id: Option<String>,
+}
+
+impl Default for AcquireOptions {
+    fn default() -> Self {
+        AcquireOptions::builder().build()
+    }
+}
+
+/// Outcome of a successful [`ConsumerApi::acquire`].
+#[derive(Debug, Clone)]
+pub struct AcquiredData {
+    negotiation_id: Option<String>,
+    agreement_id: String,
+    transfer_process_id: String,
+    data_address: DataAddress,
+}
+
+impl AcquiredData {
+    pub fn negotiation_id(&self) -> Option<&String> {
+        self.negotiation_id.as_ref()
+    }
+
+    pub fn agreement_id(&self) -> &str {
+        &self.agreement_id
+    }
+
+    pub fn transfer_process_id(&self) -> &str {
+        &self.transfer_process_id
+    }
+
+    pub fn data_address(&self) -> &DataAddress {
+        &self.data_address
+    }
+}
+
+#[derive(Default)]
+struct Progress {
+    negotiation_id: Option<String>,
+    agreement_id: Option<String>,
+    transfer_process_id: Option<String>,
+}
+
+impl Progress {
+    fn fail(&self, step: WorkflowStep, source: Option<Error>) -> Error {
+        Error::Workflow(Box::new(WorkflowError {
+            step,
+            negotiation_id: self.negotiation_id.clone(),
+            agreement_id: self.agreement_id.clone(),
+            transfer_process_id: self.transfer_process_id.clone(),
+            source,
+        }))
+    }
+}
+
+impl<'a> ConsumerApi<'a> {
+    pub(crate) fn new(client: &'a EdcConnectorClientInternal) -> ConsumerApi<'a> {
+        ConsumerApi(client)
+    }
+
+    pub async fn acquire(&self, asset_id: &str, provider: &Provider) -> EdcResult<AcquiredData> {
+        self.acquire_with_opts(asset_id, provider, AcquireOptions::default())
+            .await
+    }
+
+    /// Negotiates a contract for `asset_id`, starts a transfer and returns the
+    /// data address of the resulting EDR.
+    pub async fn acquire_with_opts(
+        &self,
+        asset_id: &str,
+        provider: &Provider,
+        options: AcquireOptions,
+    ) -> EdcResult<AcquiredData> {
+        let mut progress = Progress {
+            negotiation_id: options.negotiation_id.clone(),
+            agreement_id: options.agreement_id.clone(),
+            ..Default::default()
+        };
+
+        let agreement_id = match options.agreement_id.clone() {
+            Some(agreement_id) => agreement_id,
+            None => {
+                self.negotiate(asset_id, provider, &options, &mut progress)
+                    .await?
+            }
+        };
+        progress.agreement_id = Some(agreement_id.clone());
+
+        let transfer_processes = TransferProcessApi::new(self.0);
+
+        let mut request = TransferRequest::builder()
+            .counter_party_address(provider.address())
+            .contract_id(&agreement_id)
+            .transfer_type(&options.transfer_type)
+            .protocol(provider.protocol().clone());
+        if let Some(destination) = options.destination.clone() {
+            request = request.destination(destination);
+        }
+        let request = request.build();
+
+        let transfer_process_id = transfer_processes
+            .initiate(&request)
+            .await
+            .map_err(|e| progress.fail(WorkflowStep::Transfer, Some(e)))?
+            .id()
+            .to_string();
+        progress.transfer_process_id = Some(transfer_process_id.clone());
+
+        transfer_processes
+            .wait_for_state_with_opts(
+                &transfer_process_id,
+                TransferProcessState::Started,
+                options.transfer_wait.clone(),
+            )
+            .await
+            .map_err(|e| progress.fail(WorkflowStep::Transfer, Some(e)))?;
+
+        let data_address = self
+            .wait_for_edr(&transfer_process_id, &options.edr_wait)
+            .await
+            .map_err(|e| progress.fail(WorkflowStep::Edr, Some(e)))?;
+
+        Ok(AcquiredData {
+            negotiation_id: progress.negotiation_id,
+            agreement_id,
+            transfer_process_id,
+            data_address,
+        })
+    }
+
+    async fn negotiate(
+        &self,
+        asset_id: &str,
+        provider: &Provider,
+        options: &AcquireOptions,
+        progress: &mut Progress,
+    ) -> EdcResult<String> {
+        let negotiations = ContractNegotiationApi::new(self.0);
+
+        let negotiation_id = match options.negotiation_id.clone() {
+            Some(negotiation_id) => negotiation_id,
+            None => {
+                let dataset_request = DatasetRequest::builder()
+                    .id(asset_id)
+                    .counter_party_address(provider.address())
+                    .counter_party_id(provider.id())
+                    .protocol(provider.protocol().clone())
+                    .build();
+
+                let dataset = CatalogApi::new(self.0)
+                    .dataset(&dataset_request)
+                    .await
+                    .map_err(|e| progress.fail(WorkflowStep::Catalog, Some(e)))?;
+
+                let offer = options
+                    .offer_selection
+                    .select(dataset.offers())
+                    .ok_or_else(|| progress.fail(WorkflowStep::OfferSelection, None))?;
+
+                let request = ContractRequest::builder()
+                    .counter_party_address(provider.address())
+                    .counter_party_id(provider.id())
+                    .protocol(provider.protocol().clone())
+                    .policy(offer.to_contract_offer(provider.id(), asset_id))
+                    .build();
+
+                negotiations
+                    .initiate(&request)
+                    .await
+                    .map_err(|e| progress.fail(WorkflowStep::Negotiation, Some(e)))?
+                    .id()
+                    .to_string()
+            }
+        };
+        progress.negotiation_id = Some(negotiation_id.clone());
+
+        let negotiation = negotiations
+            .wait_for_state_with_opts(
+                &negotiation_id,
+                ContractNegotiationState::Finalized,
+                options.negotiation_wait.clone(),
+            )
+            .await
+            .map_err(|e| progress.fail(WorkflowStep::Negotiation, Some(e)))?;
+
+        negotiation
+            .contract_agreement_id()
+            .cloned()
+            .ok_or_else(|| progress.fail(WorkflowStep::Agreement, None))
+    }
+
+    async fn wait_for_edr(
+        &self,
+        transfer_process_id: &str,
+        options: &WaitOptions,
+    ) -> EdcResult<DataAddress> {
+        let edrs = &EdrApi::new(self.0);
+
+        poll_until(
+            options,
+            move || async move {
+                match edrs.get_data_address(transfer_process_id).await {
+                    Ok(data_address) => Ok(ControlFlow::Break(data_address)),
+                    Err(err) if err.is_not_found() => Ok(ControlFlow::Continue(err)),
+                    Err(err) => Err(err),
+                }
+            },
+            |err, _| err,
+        )
+        .await
+    }
+}
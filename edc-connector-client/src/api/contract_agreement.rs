@@ -1,6 +1,13 @@
+use futures::{Stream, TryStreamExt};
+
 use crate::{
+    api::pagination::paginate,
     client::EdcConnectorClientInternal,
-    types::{context::WithContext, contract_agreement::ContractAgreement, query::Query},
+    types::{
+        context::WithContext,
+        contract_agreement::ContractAgreement,
+        query::{Pagination, Query},
+    },
     EdcResult,
 };
 
@@ -22,8 +29,33 @@ impl<'a> ContractAgreementApi<'a> {
     pub async fn query(&self, query: Query) -> EdcResult<Vec<ContractAgreement>> {
         let url = self.0.path_for(&["contractagreements", "request"]);
         self.0
-            .post::<_, Vec<WithContext<ContractAgreement>>>(url, &self.0.context_for(&query))
+            .post_idempotent::<_, Vec<WithContext<ContractAgreement>>>(
+                url,
+                &self.0.context_for(&query),
+            )
             .await
             .map(|results| results.into_iter().map(|ctx| ctx.inner).collect())
     }
+
+    pub fn query_stream(
+        &self,
+        query: Query,
+    ) -> impl Stream<Item = EdcResult<ContractAgreement>> + 'a {
+        self.query_stream_with_opts(query, Pagination::default())
+    }
+
+    pub fn query_stream_with_opts(
+        &self,
+        query: Query,
+        pagination: Pagination,
+    ) -> impl Stream<Item = EdcResult<ContractAgreement>> + 'a {
+        let client = self.0;
+        paginate(query, pagination, move |query| async move {
+            ContractAgreementApi::new(client).query(query).await
+        })
+    }
+
+    pub async fn query_all(&self, query: Query) -> EdcResult<Vec<ContractAgreement>> {
+        self.query_stream(query).try_collect().await
+    }
 }
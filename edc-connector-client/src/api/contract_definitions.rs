@@ -1,9 +1,12 @@
+use futures::{Stream, TryStreamExt};
+
 use crate::{
+    api::pagination::paginate,
     client::EdcConnectorClientInternal,
     types::{
         context::WithContext,
         contract_definition::{ContractDefinition, NewContractDefinition},
-        query::Query,
+        query::{Pagination, Query},
         response::IdResponse,
     },
     EdcResult,
@@ -48,11 +51,36 @@ impl<'a> ContractDefinitionApi<'a> {
     pub async fn query(&self, query: Query) -> EdcResult<Vec<ContractDefinition>> {
         let url = self.0.path_for(&["contractdefinitions", "request"]);
         self.0
-            .post::<_, Vec<WithContext<ContractDefinition>>>(url, &self.0.context_for(&query))
+            .post_idempotent::<_, Vec<WithContext<ContractDefinition>>>(
+                url,
+                &self.0.context_for(&query),
+            )
             .await
             .map(|results| results.into_iter().map(|ctx| ctx.inner).collect())
     }
 
+    pub fn query_stream(
+        &self,
+        query: Query,
+    ) -> impl Stream<Item = EdcResult<ContractDefinition>> + 'a {
+        self.query_stream_with_opts(query, Pagination::default())
+    }
+
+    pub fn query_stream_with_opts(
+        &self,
+        query: Query,
+        pagination: Pagination,
+    ) -> impl Stream<Item = EdcResult<ContractDefinition>> + 'a {
+        let client = self.0;
+        paginate(query, pagination, move |query| async move {
+            ContractDefinitionApi::new(client).query(query).await
+        })
+    }
+
+    pub async fn query_all(&self, query: Query) -> EdcResult<Vec<ContractDefinition>> {
+        self.query_stream(query).try_collect().await
+    }
+
     pub async fn delete(&self, id: &str) -> EdcResult<()> {
         let url = self.0.path_for(&["contractdefinitions", id]);
         self.0.del(url).await
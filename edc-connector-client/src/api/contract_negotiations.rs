@@ -1,4 +1,10 @@
+use futures::{Stream, TryStreamExt};
+
 use crate::{
+    api::{
+        pagination::paginate,
+        wait::{wait_for_state, WaitOptions},
+    },
     client::EdcConnectorClientInternal,
     types::{
         context::WithContext,
@@ -6,7 +12,7 @@ use crate::{
             ContractNegotiation, ContractNegotiationState, ContractRequest, NegotiationState,
             TerminateNegotiation,
         },
-        query::Query,
+        query::{Pagination, Query},
         response::IdResponse,
     },
     EdcResult,
@@ -49,6 +55,31 @@ impl<'a> ContractNegotiationApi<'a> {
             .map(|ctx| ctx.inner.state().clone())
     }
 
+    pub async fn wait_for_state(
+        &self,
+        id: &str,
+        state: ContractNegotiationState,
+    ) -> EdcResult<ContractNegotiation> {
+        self.wait_for_state_with_opts(id, state, WaitOptions::default())
+            .await
+    }
+
+    /// Polls the negotiation until it reaches `state`, failing early if it gets terminated.
+    pub async fn wait_for_state_with_opts(
+        &self,
+        id: &str,
+        state: ContractNegotiationState,
+        options: WaitOptions,
+    ) -> EdcResult<ContractNegotiation> {
+        wait_for_state(
+            &state,
+            &options,
+            || self.get(id),
+            ContractNegotiation::state,
+        )
+        .await
+    }
+
     pub async fn terminate(&self, id: &str, reason: &str) -> EdcResult<()> {
         let url = self.0.path_for(&["contractnegotiations", id, "terminate"]);
         let request = TerminateNegotiation {
@@ -64,8 +95,33 @@ impl<'a> ContractNegotiationApi<'a> {
     pub async fn query(&self, query: Query) -> EdcResult<Vec<ContractNegotiation>> {
         let url = self.0.path_for(&["contractnegotiations", "request"]);
         self.0
-            .post::<_, Vec<WithContext<ContractNegotiation>>>(url, &self.0.context_for(&query))
+            .post_idempotent::<_, Vec<WithContext<ContractNegotiation>>>(
+                url,
+                &self.0.context_for(&query),
+            )
             .await
             .map(|results| results.into_iter().map(|ctx| ctx.inner).collect())
     }
+
+    pub fn query_stream(
+        &self,
+        query: Query,
+    ) -> impl Stream<Item = EdcResult<ContractNegotiation>> + 'a {
+        self.query_stream_with_opts(query, Pagination::default())
+    }
+
+    pub fn query_stream_with_opts(
+        &self,
+        query: Query,
+        pagination: Pagination,
+    ) -> impl Stream<Item = EdcResult<ContractNegotiation>> + 'a {
+        let client = self.0;
+        paginate(query, pagination, move |query| async move {
+            ContractNegotiationApi::new(client).query(query).await
+        })
+    }
+
+    pub async fn query_all(&self, query: Query) -> EdcResult<Vec<ContractNegotiation>> {
+        self.query_stream(query).try_collect().await
+    }
 }
@@ -0,0 +1,372 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+
+use bon::Builder;
+use futures::{stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+
+use crate::{
+    api::Provider,
+    types::{
+        catalog::{index::CatalogIndex, CatalogRequest, Dataset},
+        query::{Pagination, Query},
+    },
+    EdcConnectorClient, EdcResult, Error,
+};
+
+/// Crawls the catalogs of many providers, caching them for a while.
+///
+/// Providers are keyed by their id. A provider failing keeps its last cached
+/// catalog, if any, next to the failure.
+#[derive(Clone)]
+pub struct CatalogCrawler {
+    client: EdcConnectorClient,
+    options: CrawlerOptions,
+    state: Arc<Mutex<CrawlerState>>,
+}
+
+#[derive(Default)]
+struct CrawlerState {
+    catalogs: HashMap<String, ProviderCatalog>,
+    failures: HashMap<String, CrawlFailure>,
+}
+
+#[derive(Debug, Clone, Builder)]
+pub struct CrawlerOptions {
+    /// Number of providers crawled concurrently.
+    #[builder(default = 4)]
+    concurrency: usize,
+    /// How long a catalog is served from the cache.
+    #[builder(default = Duration::from_secs(300))]
+    ttl: Duration,
+    /// Query sent to every provider, paged through.
+    #[builder(default)]
+    query_spec: Query,
+    #[builder(default)]
+    pagination: Pagination,
+}
+
+impl CrawlerOptions {
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    pub fn query_spec(&self) -> &Query {
+        &self.query_spec
+    }
+
+    pub fn pagination(&self) -> &Pagination {
+        &self.pagination
+    }
+}
+
+impl Default for CrawlerOptions {
+    fn default() -> Self {
+        CrawlerOptions::builder().build()
+    }
+}
+
+impl CatalogCrawler {
+    pub fn new(client: EdcConnectorClient) -> CatalogCrawler {
+        CatalogCrawler::new_with_opts(client, CrawlerOptions::default())
+    }
+
+    pub fn new_with_opts(client: EdcConnectorClient, options: CrawlerOptions) -> CatalogCrawler {
+        CatalogCrawler {
+            client,
+            options,
+            state: Arc::default(),
+        }
+    }
+
+    /// Requests the catalogs of the providers not cached or expired, and
+    /// returns the merged view of all the given providers.
+    pub async fn crawl(&self, providers: &[Provider]) -> FederatedCatalog {
+        let stale = {
+            let state = self.state();
+            let mut seen = HashSet::new();
+            providers
+                .iter()
+                .filter(|provider| seen.insert(provider.id()))
+                .filter(|provider| {
+                    state
+                        .catalogs
+                        .get(provider.id())
+                        .is_none_or(|catalog| catalog.fetched_at.elapsed() >= self.options.ttl)
+                })
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+
+        let results = stream::iter(stale)
+            .map(|provider| async move {
+                let result = self.fetch(&provider).await;
+                (provider, result)
+            })
+            .buffer_unordered(self.options.concurrency.max(1))
+            .collect::<Vec<_>>()
+            .await;
+
+        let mut state = self.state();
+        for (provider, result) in results {
+            let id = provider.id().to_string();
+            match result {
+                Ok(datasets) => {
+                    state.failures.remove(&id);
+                    state
+                        .catalogs
+                        .insert(id, ProviderCatalog::new(provider, datasets));
+                }
+                Err(error) => {
+                    state.failures.insert(
+                        id,
+                        CrawlFailure {
+                            provider,
+                            error: Arc::new(error),
+                        },
+                    );
+                }
+            }
+        }
+
+        let ids = providers.iter().map(Provider::id).collect::<HashSet<_>>();
+        state.view(|id| ids.contains(id))
+    }
+
+    /// Crawls the participants of the registry.
+    pub async fn crawl_registry(&self, registry: &ParticipantRegistry) -> FederatedCatalog {
+        self.crawl(registry.participants()).await
+    }
+
+    /// The merged view of everything crawled so far, expired catalogs
+    /// included.
+    pub fn catalog(&self) -> FederatedCatalog {
+        self.state().view(|_| true)
+    }
+
+    pub fn invalidate(&self, provider_id: &str) {
+        let mut state = self.state();
+        state.catalogs.remove(provider_id);
+        state.failures.remove(provider_id);
+    }
+
+    pub fn clear(&self) {
+        *self.state() = CrawlerState::default();
+    }
+
+    async fn fetch(&self, provider: &Provider) -> EdcResult<Vec<Dataset>> {
+        let request = CatalogRequest::builder()
+            .counter_party_address(provider.address())
+            .counter_party_id(provider.id())
+            .protocol(provider.protocol().clone())
+            .query_spec(self.options.query_spec.clone())
+            .build();
+
+        self.client
+            .catalogue()
+            .datasets_stream_with_opts(&request, self.options.pagination.clone())
+            .try_collect()
+            .await
+    }
+
+    fn state(&self) -> MutexGuard<'_, CrawlerState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl CrawlerState {
+    fn view(&self, include: impl Fn(&str) -> bool) -> FederatedCatalog {
+        FederatedCatalog {
+            catalogs: self
+                .catalogs
+                .iter()
+                .filter(|(id, _)| include(id))
+                .map(|(id, catalog)| (id.clone(), catalog.clone()))
+                .collect(),
+            failures: self
+                .failures
+                .iter()
+                .filter(|(id, _)| include(id))
+                .map(|(id, failure)| (id.clone(), failure.clone()))
+                .collect(),
+        }
+    }
+}
+
+/// The datasets of a provider, keyed by their id.
+#[derive(Debug, Clone)]
+pub struct ProviderCatalog {
+    provider: Provider,
+    datasets: BTreeMap<String, Dataset>,
+    fetched_at: Instant,
+}
+
+impl ProviderCatalog {
+    fn new(provider: Provider, datasets: Vec<Dataset>) -> ProviderCatalog {
+        ProviderCatalog {
+            provider,
+            datasets: datasets
+                .into_iter()
+                .map(|dataset| (dataset.id().to_string(), dataset))
+                .collect(),
+            fetched_at: Instant::now(),
+        }
+    }
+
+    pub fn provider(&self) -> &Provider {
+        &self.provider
+    }
+
+    pub fn datasets(&self) -> impl Iterator<Item = &Dataset> {
+        self.datasets.values()
+    }
+
+    pub fn dataset(&self, id: &str) -> Option<&Dataset> {
+        self.datasets.get(id)
+    }
+
+    pub fn fetched_at(&self) -> Instant {
+        self.fetched_at
+    }
+}
+
+/// A provider whose catalog could not be requested on the last crawl.
+#[derive(Debug, Clone)]
+pub struct CrawlFailure {
+    provider: Provider,
+    error: Arc<Error>,
+}
+
+impl CrawlFailure {
+    pub fn provider(&self) -> &Provider {
+        &self.provider
+    }
+
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+}
+
+/// The catalogs of many providers merged, keyed by provider id and dataset id.
+#[derive(Debug, Clone, Default)]
+pub struct FederatedCatalog {
+    catalogs: BTreeMap<String, ProviderCatalog>,
+    failures: BTreeMap<String, CrawlFailure>,
+}
+
+impl FederatedCatalog {
+    pub fn providers(&self) -> impl Iterator<Item = &ProviderCatalog> {
+        self.catalogs.values()
+    }
+
+    pub fn provider(&self, provider_id: &str) -> Option<&ProviderCatalog> {
+        self.catalogs.get(provider_id)
+    }
+
+    pub fn dataset(&self, provider_id: &str, dataset_id: &str) -> Option<&Dataset> {
+        self.provider(provider_id)?.dataset(dataset_id)
+    }
+
+    /// Every dataset along with its provider, ordered by provider id and
+    /// dataset id.
+    pub fn datasets(&self) -> impl Iterator<Item = (&Provider, &Dataset)> {
+        self.catalogs.values().flat_map(|catalog| {
+            catalog
+                .datasets()
+                .map(move |dataset| (catalog.provider(), dataset))
+        })
+    }
+
+    /// The providers offering the dataset.
+    pub fn providers_of(&self, dataset_id: &str) -> Vec<&Provider> {
+        self.catalogs
+            .values()
+            .filter(|catalog| catalog.dataset(dataset_id).is_some())
+            .map(ProviderCatalog::provider)
+            .collect()
+    }
+
+    /// The datasets whose id, name or description contains the text, ignoring
+    /// the case.
+    pub fn search(&self, text: &str) -> Vec<(&Provider, &Dataset)> {
+        let text = text.to_lowercase();
+        self.datasets()
+            .filter(|(_, dataset)| {
+                [
+                    Some(dataset.id().to_string()),
+                    dataset.name(),
+                    dataset.description(),
+                ]
+                .into_iter()
+                .flatten()
+                .any(|field| field.to_lowercase().contains(&text))
+            })
+            .collect()
+    }
+
+    /// Indexes the datasets for searching them by property and policy.
+    pub fn to_index(&self) -> serde_json::Result<CatalogIndex> {
+        let mut index = CatalogIndex::default();
+        for (provider, dataset) in self.datasets() {
+            index.insert(provider.id(), dataset.clone())?;
+        }
+        Ok(index)
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &CrawlFailure> {
+        self.failures.values()
+    }
+
+    pub fn failure(&self, provider_id: &str) -> Option<&CrawlFailure> {
+        self.failures.get(provider_id)
+    }
+}
+
+/// The providers of a dataspace, as read from a registry file.
+///
+/// ```rust
+/// use edc_connector_client::api::ParticipantRegistry;
+///
+/// let registry = ParticipantRegistry::from_json(
+///     r#"{ "participants": [{ "id": "provider", "address": "http://provider/protocol" }] }"#,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(registry.participants()[0].id(), "provider");
+/// ```
+#[derive(Debug, Clone, Default, Builder, Deserialize, Serialize)]
+pub struct ParticipantRegistry {
+    #[builder(field)]
+    #[serde(default)]
+    participants: Vec<Provider>,
+}
+
+impl<S: participant_registry_builder::State> ParticipantRegistryBuilder<S> {
+    pub fn participant(mut self, participant: Provider) -> Self {
+        self.participants.push(participant);
+        self
+    }
+}
+
+impl ParticipantRegistry {
+    pub fn from_json(content: &str) -> Result<ParticipantRegistry, serde_json::Error> {
+        serde_json::from_str(content)
+    }
+
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(content: &str) -> Result<ParticipantRegistry, serde_yaml::Error> {
+        serde_yaml::from_str(content)
+    }
+
+    pub fn participants(&self) -> &[Provider] {
+        &self.participants
+    }
+}
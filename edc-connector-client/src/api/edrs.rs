@@ -1,14 +1,22 @@
+use futures::{Stream, TryStreamExt};
 use reqwest::StatusCode;
 
 use crate::{
+    api::pagination::paginate,
     client::EdcConnectorClientInternal,
     types::{
-        context::WithContext, data_address::DataAddress, edr::EndpointDataReferenceEntry,
-        query::Query,
+        context::WithContext,
+        data_address::DataAddress,
+        edr::EndpointDataReferenceEntry,
+        query::{fields, Operator, Pagination, Query},
     },
     EdcResult,
 };
 
+mod cache;
+
+pub use cache::EdrCache;
+
 pub struct EdrApi<'a>(&'a EdcConnectorClientInternal);
 
 impl<'a> EdrApi<'a> {
@@ -18,12 +26,12 @@ impl<'a> EdrApi<'a> {
 
     pub async fn get_entry(&self, id: &str) -> EdcResult<EndpointDataReferenceEntry> {
         let query = Query::builder()
-            .filter("transferProcessId", "=", id)
+            .filter(fields::edr::TRANSFER_PROCESS_ID, Operator::Eq, id)
             .build();
 
         self.query(query).await.and_then(|edrs| {
             edrs.into_iter().next().ok_or_else(|| {
-                crate::Error::ManagementApi(crate::ManagementApiError {
+                crate::Error::NotFound(crate::ManagementApiError {
                     status_code: StatusCode::NOT_FOUND,
                     error_detail: crate::ManagementApiErrorDetailKind::Raw(format!(
                         "EDR entry with id {} not found",
@@ -42,10 +50,38 @@ impl<'a> EdrApi<'a> {
             .map(|ctx| ctx.inner)
     }
 
+    /// Fetches the data address, letting the connector refresh the token first
+    /// when `auto_refresh` is set and the token is expired.
+    pub async fn get_data_address_with_refresh(
+        &self,
+        id: &str,
+        auto_refresh: bool,
+    ) -> EdcResult<DataAddress> {
+        let url = format!(
+            "{}?auto_refresh={}",
+            self.0.path_for(&["edrs", id, "dataaddress"]),
+            auto_refresh
+        );
+        self.0
+            .get::<WithContext<DataAddress>>(url)
+            .await
+            .map(|ctx| ctx.inner)
+    }
+
+    /// Forces the connector to refresh the token of the EDR, returning the
+    /// updated data address.
+    pub async fn refresh(&self, id: &str) -> EdcResult<DataAddress> {
+        let url = self.0.path_for(&["edrs", id, "refresh"]);
+        self.0
+            .post_without_body::<WithContext<DataAddress>>(url)
+            .await
+            .map(|ctx| ctx.inner)
+    }
+
     pub async fn query(&self, query: Query) -> EdcResult<Vec<EndpointDataReferenceEntry>> {
         let url = self.0.path_for(&["edrs", "request"]);
         self.0
-            .post::<_, Vec<WithContext<EndpointDataReferenceEntry>>>(
+            .post_idempotent::<_, Vec<WithContext<EndpointDataReferenceEntry>>>(
                 url,
                 &self.0.context_for(&query),
             )
@@ -53,6 +89,28 @@ impl<'a> EdrApi<'a> {
             .map(|results| results.into_iter().map(|ctx| ctx.inner).collect())
     }
 
+    pub fn query_stream(
+        &self,
+        query: Query,
+    ) -> impl Stream<Item = EdcResult<EndpointDataReferenceEntry>> + 'a {
+        self.query_stream_with_opts(query, Pagination::default())
+    }
+
+    pub fn query_stream_with_opts(
+        &self,
+        query: Query,
+        pagination: Pagination,
+    ) -> impl Stream<Item = EdcResult<EndpointDataReferenceEntry>> + 'a {
+        let client = self.0;
+        paginate(query, pagination, move |query| async move {
+            EdrApi::new(client).query(query).await
+        })
+    }
+
+    pub async fn query_all(&self, query: Query) -> EdcResult<Vec<EndpointDataReferenceEntry>> {
+        self.query_stream(query).try_collect().await
+    }
+
     pub async fn delete(&self, id: &str) -> EdcResult<()> {
         let url = self.0.path_for(&["edrs", id]);
         self.0.del(url).await
@@ -0,0 +1,108 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
+
+use tokio::time::Instant;
+
+use crate::{
+    error::DataPlaneError, types::data_address::DataAddress, EdcConnectorClient, EdcResult,
+};
+
+type Slot = Arc<tokio::sync::Mutex<Option<CachedEdr>>>;
+
+/// Caches the EDRs of transfer processes, refreshing their tokens through the
+/// connector before they expire.
+#[derive(Clone)]
+pub struct EdrCache {
+    client: EdcConnectorClient,
+    refresh_margin: Duration,
+    entries: Arc<Mutex<HashMap<String, Slot>>>,
+}
+
+struct CachedEdr {
+    data_address: DataAddress,
+    expires_at: Option<Instant>,
+}
+
+impl CachedEdr {
+    fn new(data_address: DataAddress) -> CachedEdr {
+        CachedEdr {
+            // An expiry too far away to be represented is taken as no expiry.
+            expires_at: data_address
+                .expires_in()
+                .and_then(|ttl| Instant::now().checked_add(ttl)),
+            data_address,
+        }
+    }
+
+    fn expires_within(&self, margin: Duration) -> bool {
+        self.expires_at.is_some_and(|expires_at| {
+            expires_at.saturating_duration_since(Instant::now()) <= margin
+        })
+    }
+}
+
+impl EdrCache {
+    pub fn new(client: EdcConnectorClient) -> EdrCache {
+        EdrCache {
+            client,
+            refresh_margin: Duration::from_secs(30),
+            entries: Arc::default(),
+        }
+    }
+
+    /// How long before the expiry a token gets refreshed, 30 seconds by default.
+    pub fn refresh_margin(mut self, refresh_margin: Duration) -> EdrCache {
+        self.refresh_margin = refresh_margin;
+        self
+    }
+
+    /// Returns a data address of the transfer process with a token valid for
+    /// at least the refresh margin, whenever the EDR carries an expiry.
+    pub async fn data_address(&self, transfer_process_id: &str) -> EdcResult<DataAddress> {
+        let slot = self
+            .entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(transfer_process_id.to_string())
+            .or_default()
+            .clone();
+
+        let mut cached = slot.lock().await;
+
+        let edrs = self.client.edrs();
+        let data_address = match cached.as_ref() {
+            Some(edr) if !edr.expires_within(self.refresh_margin) => {
+                return Ok(edr.data_address.clone());
+            }
+            Some(edr) if edr.data_address.is_refreshable() => {
+                edrs.refresh(transfer_process_id).await?
+            }
+            _ => {
+                edrs.get_data_address_with_refresh(transfer_process_id, true)
+                    .await?
+            }
+        };
+
+        *cached = Some(CachedEdr::new(data_address.clone()));
+        Ok(data_address)
+    }
+
+    /// Returns a valid token for the data plane of the transfer process.
+    pub async fn token(&self, transfer_process_id: &str) -> EdcResult<String> {
+        self.data_address(transfer_process_id)
+            .await?
+            .authorization()
+            .map(str::to_string)
+            .ok_or_else(|| DataPlaneError::InvalidDataAddress("authorization".to_string()).into())
+    }
+
+    pub fn invalidate(&self, transfer_process_id: &str) {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(transfer_process_id);
+    }
+}
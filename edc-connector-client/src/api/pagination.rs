@@ -0,0 +1,72 @@
+use std::future::Future;
+
+use futures::{stream, Stream, StreamExt};
+
+use crate::{
+    types::query::{Pagination, Query},
+    EdcResult,
+};
+
+/// Walks the pages of `query` lazily by advancing its offset until a page
+/// shorter than the limit is returned.
+pub(crate) fn paginate<'a, T, F, Fut>(
+    query: Query,
+    pagination: Pagination,
+    fetch: F,
+) -> impl Stream<Item = EdcResult<T>> + 'a
+where
+    T: Send + 'a,
+    F: Fn(Query) -> Fut + Send + 'a,
+    Fut: Future<Output = EdcResult<Vec<T>>> + Send + 'a,
+{
+    let max_items = pagination.max_items().unwrap_or(usize::MAX);
+    paginate_until(query, pagination, fetch, |_| false).take(max_items)
+}
+
+/// Like [`paginate`], also stopping after a full page for which `exhausted`
+/// returns true, for servers that may not honor the offset. The items are not
+/// capped to `max_items`, which is left to the caller.
+pub(crate) fn paginate_until<'a, T, F, Fut, E>(
+    query: Query,
+    pagination: Pagination,
+    fetch: F,
+    exhausted: E,
+) -> impl Stream<Item = EdcResult<T>> + 'a
+where
+    T: Send + 'a,
+    F: Fn(Query) -> Fut + Send + 'a,
+    Fut: Future<Output = EdcResult<Vec<T>>> + Send + 'a,
+    E: FnMut(&[T]) -> bool + Send + 'a,
+{
+    let limit = if query.limit() == 0 {
+        Query::default().limit()
+    } else {
+        query.limit()
+    };
+    let start = query.offset();
+
+    let pages = stream::iter(0u32..)
+        .map(move |page| {
+            let offset = start.saturating_add(page.saturating_mul(limit));
+            fetch(query.to_builder().offset(offset).limit(limit).build())
+        })
+        .buffered(pagination.concurrency().max(1))
+        .boxed();
+
+    stream::unfold(Some((pages, exhausted)), move |state| async move {
+        let (mut pages, mut exhausted) = state?;
+        let page = pages.next().await?;
+        let last = match &page {
+            Ok(items) => items.len() < limit as usize || exhausted(items),
+            Err(_) => true,
+        };
+        Some((page, if last { None } else { Some((pages, exhausted)) }))
+    })
+    .flat_map(|page| {
+        let items = match page {
+            Ok(items) => items.into_iter().map(Ok).collect(),
+            Err(err) => vec![Err(err)],
+        };
+        stream::iter(items)
+    })
+}
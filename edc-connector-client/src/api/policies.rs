@@ -1,9 +1,12 @@
+use futures::{Stream, TryStreamExt};
+
 use crate::{
+    api::pagination::paginate,
     client::EdcConnectorClientInternal,
     types::{
         context::WithContext,
         policy::{NewPolicyDefinition, PolicyDefinition},
-        query::Query,
+        query::{Pagination, Query},
         response::IdResponse,
     },
     EdcResult,
@@ -50,11 +53,36 @@ impl<'a> PolicyApi<'a> {
     pub async fn query(&self, query: Query) -> EdcResult<Vec<PolicyDefinition>> {
         let url = self.0.path_for(&["policydefinitions", "request"]);
         self.0
-            .post::<_, Vec<WithContext<PolicyDefinition>>>(url, &self.0.context_for(&query))
+            .post_idempotent::<_, Vec<WithContext<PolicyDefinition>>>(
+                url,
+                &self.0.context_for(&query),
+            )
             .await
             .map(|results| results.into_iter().map(|ctx| ctx.inner).collect())
     }
 
+    pub fn query_stream(
+        &self,
+        query: Query,
+    ) -> impl Stream<Item = EdcResult<PolicyDefinition>> + 'a {
+        self.query_stream_with_opts(query, Pagination::default())
+    }
+
+    pub fn query_stream_with_opts(
+        &self,
+        query: Query,
+        pagination: Pagination,
+    ) -> impl Stream<Item = EdcResult<PolicyDefinition>> + 'a {
+        let client = self.0;
+        paginate(query, pagination, move |query| async move {
+            PolicyApi::new(client).query(query).await
+        })
+    }
+
+    pub async fn query_all(&self, query: Query) -> EdcResult<Vec<PolicyDefinition>> {
+        self.query_stream(query).try_collect().await
+    }
+
     pub async fn delete(&self, id: &str) -> EdcResult<()> {
         let url = self.0.path_for(&["policydefinitions", id]);
         self.0.del(url).await
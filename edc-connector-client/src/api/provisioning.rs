@@ -0,0 +1,446 @@
+use std::{collections::HashMap, fmt};
+
+use bon::Builder;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::{
+    api::{AssetApi, ContractDefinitionApi, PolicyApi, SecretsApi},
+    client::EdcConnectorClientInternal,
+    error::{ApplyError, ManifestError},
+    types::{
+        asset::NewAsset,
+        contract_definition::NewContractDefinition,
+        manifest::{Manifest, ResourceKind},
+        policy::NewPolicyDefinition,
+        query::Query,
+        secret::NewSecret,
+    },
+    EdcResult, EDC_NAMESPACE,
+};
+
+/// Reconciles the connector with a declarative [`Manifest`].
+pub struct ProvisioningApi<'a>(&'a EdcConnectorClientInternal);
+
+#[derive(Debug, Clone, Default, Builder)]
+pub struct ReconcileOptions {
+    /// Deletes the assets, policy definitions and contract definitions of the
+    /// connector that are not part of the manifest.
+    #[builder(default)]
+    prune: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Create,
+    Update,
+    Delete,
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operation = match self {
+            Operation::Create => "create",
+            Operation::Update => "update",
+            Operation::Delete => "delete",
+        };
+        f.write_str(operation)
+    }
+}
+
+/// An entity of a [`Manifest`].
+#[derive(Debug, Clone)]
+pub enum Resource {
+    Secret(NewSecret),
+    Asset(NewAsset),
+    PolicyDefinition(NewPolicyDefinition),
+    ContractDefinition(NewContractDefinition),
+}
+
+impl Resource {
+    pub fn kind(&self) -> ResourceKind {
+        match self {
+            Resource::Secret(_) => ResourceKind::Secret,
+            Resource::Asset(_) => ResourceKind::Asset,
+            Resource::PolicyDefinition(_) => ResourceKind::PolicyDefinition,
+            Resource::ContractDefinition(_) => ResourceKind::ContractDefinition,
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        let id = match self {
+            Resource::Secret(secret) => secret.id(),
+            Resource::Asset(asset) => asset.id(),
+            Resource::PolicyDefinition(policy_definition) => policy_definition.id(),
+            Resource::ContractDefinition(contract_definition) => contract_definition.id(),
+        };
+        id.unwrap_or_default()
+    }
+
+    fn to_value(&self) -> Result<Value, ManifestError> {
+        let value = match self {
+            Resource::Secret(secret) => serde_json::to_value(secret),
+            Resource::Asset(asset) => serde_json::to_value(asset),
+            Resource::PolicyDefinition(policy_definition) => {
+                serde_json::to_value(policy_definition)
+            }
+            Resource::ContractDefinition(contract_definition) => {
+                serde_json::to_value(contract_definition)
+            }
+        };
+        Ok(value?)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Change {
+    Create(Resource),
+    Update(Resource),
+    Delete { kind: ResourceKind, id: String },
+}
+
+impl Change {
+    pub fn operation(&self) -> Operation {
+        match self {
+            Change::Create(_) => Operation::Create,
+            Change::Update(_) => Operation::Update,
+            Change::Delete { .. } => Operation::Delete,
+        }
+    }
+
+    pub fn kind(&self) -> ResourceKind {
+        match self {
+            Change::Create(resource) | Change::Update(resource) => resource.kind(),
+            Change::Delete { kind, .. } => *kind,
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        match self {
+            Change::Create(resource) | Change::Update(resource) => resource.id(),
+            Change::Delete { id, .. } => id,
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self.operation() {
+            Operation::Create => '+',
+            Operation::Update => '~',
+            Operation::Delete => '-',
+        };
+        write!(f, "{symbol} {} {}", self.kind(), self.id())
+    }
+}
+
+/// The ordered changes converging the connector to a manifest.
+///
+/// Creations and updates come first in dependency order (secrets, assets,
+/// policy definitions and contract definitions), then the deletions in the
+/// reverse order.
+#[derive(Debug, Clone, Default)]
+pub struct Plan {
+    changes: Vec<Change>,
+    unchanged: Vec<(ResourceKind, String)>,
+}
+
+impl Plan {
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    pub fn unchanged(&self) -> &[(ResourceKind, String)] {
+        &self.unchanged
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    fn reconcile<A: Serialize>(&mut self, resource: Resource, actual: Option<&A>) -> EdcResult<()> {
+        match actual {
+            None => self.changes.push(Change::Create(resource)),
+            Some(actual) => {
+                let actual = serde_json::to_value(actual).map_err(ManifestError::from)?;
+                if is_up_to_date(&resource.to_value()?, &actual) {
+                    self.unchanged
+                        .push((resource.kind(), resource.id().to_string()));
+                } else {
+                    self.changes.push(Change::Update(resource));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        write!(
+            f,
+            "{} to change, {} unchanged",
+            self.changes.len(),
+            self.unchanged.len()
+        )
+    }
+}
+
+impl<'a> ProvisioningApi<'a> {
+    pub(crate) fn new(client: &'a EdcConnectorClientInternal) -> ProvisioningApi<'a> {
+        ProvisioningApi(client)
+    }
+
+    /// Computes the changes required by the manifest without applying them.
+    pub async fn plan(&self, manifest: &Manifest) -> EdcResult<Plan> {
+        self.plan_with_opts(manifest, ReconcileOptions::default())
+            .await
+    }
+
+    pub async fn plan_with_opts(
+        &self,
+        manifest: &Manifest,
+        options: ReconcileOptions,
+    ) -> EdcResult<Plan> {
+        manifest.validate()?;
+        let mut plan = Plan::default();
+
+        let secrets = SecretsApi::new(self.0);
+        for secret in manifest.secrets() {
+            let actual = match secrets.get(secret.id().unwrap_or_default()).await {
+                Ok(actual) => Some(actual),
+                Err(err) if err.is_not_found() => None,
+                Err(err) => return Err(err),
+            };
+            plan.reconcile(Resource::Secret(secret.clone()), actual.as_ref())?;
+        }
+
+        let mut assets = index(
+            AssetApi::new(self.0).query_all(Query::default()).await?,
+            |a| a.id(),
+        );
+        for asset in manifest.assets() {
+            let actual = assets.remove(asset.id().unwrap_or_default());
+            plan.reconcile(Resource::Asset(asset.clone()), actual.as_ref())?;
+        }
+
+        let mut policy_definitions = index(
+            PolicyApi::new(self.0).query_all(Query::default()).await?,
+            |p| p.id(),
+        );
+        for policy_definition in manifest.policy_definitions() {
+            let actual = policy_definitions.remove(policy_definition.id().unwrap_or_default());
+            plan.reconcile(
+                Resource::PolicyDefinition(policy_definition.clone()),
+                actual.as_ref(),
+            )?;
+        }
+
+        let mut contract_definitions = index(
+            ContractDefinitionApi::new(self.0)
+                .query_all(Query::default())
+                .await?,
+            |c| c.id(),
+        );
+        for contract_definition in manifest.contract_definitions() {
+            let actual = contract_definitions.remove(contract_definition.id().unwrap_or_default());
+            plan.reconcile(
+                Resource::ContractDefinition(contract_definition.clone()),
+                actual.as_ref(),
+            )?;
+        }
+
+        if options.prune {
+            let stale = [
+                (
+                    ResourceKind::ContractDefinition,
+                    sorted_keys(contract_definitions),
+                ),
+                (
+                    ResourceKind::PolicyDefinition,
+                    sorted_keys(policy_definitions),
+                ),
+                (ResourceKind::Asset, sorted_keys(assets)),
+            ];
+            for (kind, ids) in stale {
+                plan.changes
+                    .extend(ids.into_iter().map(|id| Change::Delete { kind, id }));
+            }
+        }
+
+        Ok(plan)
+    }
+
+    /// Applies the changes of the plan in order, stopping at the first failure.
+    pub async fn apply_plan(&self, plan: &Plan) -> EdcResult<()> {
+        for (applied, change) in plan.changes().iter().enumerate() {
+            self.apply_change(change).await.map_err(|source| {
+                Box::new(ApplyError {
+                    operation: change.operation(),
+                    kind: change.kind(),
+                    id: change.id().to_string(),
+                    applied,
+                    source,
+                })
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Converges the connector to the manifest, returning the applied plan.
+    pub async fn apply(&self, manifest: &Manifest) -> EdcResult<Plan> {
+        self.apply_with_opts(manifest, ReconcileOptions::default())
+            .await
+    }
+
+    pub async fn apply_with_opts(
+        &self,
+        manifest: &Manifest,
+        options: ReconcileOptions,
+    ) -> EdcResult<Plan> {
+        let plan = self.plan_with_opts(manifest, options).await?;
+        self.apply_plan(&plan).await?;
+        Ok(plan)
+    }
+
+    async fn apply_change(&self, change: &Change) -> EdcResult<()> {
+        match change {
+            Change::Create(resource) => match resource {
+                Resource::Secret(secret) => SecretsApi::new(self.0).create(secret).await.map(drop),
+                Resource::Asset(asset) => AssetApi::new(self.0).create(asset).await.map(drop),
+                Resource::PolicyDefinition(policy_definition) => PolicyApi::new(self.0)
+                    .create(policy_definition)
+                    .await
+                    .map(drop),
+                Resource::ContractDefinition(contract_definition) => {
+                    ContractDefinitionApi::new(self.0)
+                        .create(contract_definition)
+                        .await
+                        .map(drop)
+                }
+            },
+            Change::Update(resource) => match resource {
+                Resource::Secret(secret) => SecretsApi::new(self.0).update(&convert(secret)?).await,
+                Resource::Asset(asset) => AssetApi::new(self.0).update(&convert(asset)?).await,
+                Resource::PolicyDefinition(policy_definition) => {
+                    PolicyApi::new(self.0)
+                        .update(&convert(policy_definition)?)
+                        .await
+                }
+                Resource::ContractDefinition(contract_definition) => {
+                    ContractDefinitionApi::new(self.0)
+                        .update(&convert(contract_definition)?)
+                        .await
+                }
+            },
+            Change::Delete { kind, id } => match kind {
+                ResourceKind::Secret => SecretsApi::new(self.0).delete(id).await,
+                ResourceKind::Asset => AssetApi::new(self.0).delete(id).await,
+                ResourceKind::PolicyDefinition => PolicyApi::new(self.0).delete(id).await,
+                ResourceKind::ContractDefinition => {
+                    ContractDefinitionApi::new(self.0).delete(id).await
+                }
+            },
+        }
+    }
+}
+
+fn index<T>(entities: Vec<T>, id: impl Fn(&T) -> &str) -> HashMap<String, T> {
+    entities
+        .into_iter()
+        .map(|entity| (id(&entity).to_string(), entity))
+        .collect()
+}
+
+fn sorted_keys<T>(entities: HashMap<String, T>) -> Vec<String> {
+    let mut ids = entities.into_keys().collect::<Vec<_>>();
+    ids.sort();
+    ids
+}
+
+/// Turns a new entity of the manifest into the one expected by the update endpoints.
+fn convert<N: Serialize, E: DeserializeOwned>(entity: &N) -> Result<E, ManifestError> {
+    Ok(serde_json::from_value(serde_json::to_value(entity)?)?)
+}
+
+/// Whether every field set in the manifest has the same value on the connector.
+///
+/// Fields only known by the connector, like generated ids, are ignored and the
+/// keys are compared without the EDC namespace.
+fn is_up_to_date(desired: &Value, actual: &Value) -> bool {
+    match (desired, actual) {
+        (Value::Null, _) => true,
+        (Value::Object(desired), Value::Object(actual)) => desired.iter().all(|(key, value)| {
+            if key == "@context" {
+                return true;
+            }
+            let actual = actual
+                .iter()
+                .find(|(actual_key, _)| compact(actual_key) == compact(key))
+                .map(|(_, value)| value);
+            match actual {
+                Some(actual) => is_up_to_date(value, actual),
+                None => value.is_null() || is_empty(value),
+            }
+        }),
+        (Value::Array(desired), Value::Array(actual)) => {
+            desired.len() == actual.len()
+                && desired
+                    .iter()
+                    .zip(actual)
+                    .all(|(desired, actual)| is_up_to_date(desired, actual))
+        }
+        (Value::Array(desired), actual) if desired.len() == 1 => is_up_to_date(&desired[0], actual),
+        (desired, actual) => desired == actual,
+    }
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Array(values) => values.is_empty(),
+        Value::Object(values) => values.is_empty(),
+        _ => false,
+    }
+}
+
+fn compact(key: &str) -> &str {
+    key.strip_prefix(EDC_NAMESPACE)
+        .or_else(|| key.strip_prefix("edc:"))
+        .unwrap_or(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn should_compare_desired_fields_only() {
+        let desired = json!({
+            "@id": "asset",
+            "properties": { "name": "Asset" },
+            "privateProperties": {},
+            "dataAddress": { "type": "HttpData" }
+        });
+        let actual = json!({
+            "@id": "asset",
+            "properties": { "https://w3id.org/edc/v0.0.1/ns/name": "Asset", "id": "asset" },
+            "dataAddress": { "@type": "DataAddress", "edc:type": "HttpData" },
+            "createdAt": 1
+        });
+
+        assert!(is_up_to_date(&desired, &actual));
+        assert!(!is_up_to_date(
+            &json!({ "properties": { "name": "Other" } }),
+            &actual
+        ));
+        assert!(!is_up_to_date(
+            &json!({ "properties": { "version": "1" } }),
+            &actual
+        ));
+    }
+}
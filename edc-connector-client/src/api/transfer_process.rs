@@ -1,8 +1,14 @@
+use futures::{Stream, TryStreamExt};
+
 use crate::{
+    api::{
+        pagination::paginate,
+        wait::{wait_for_state, WaitOptions},
+    },
     client::EdcConnectorClientInternal,
     types::{
         context::WithContext,
-        query::Query,
+        query::{Pagination, Query},
         response::IdResponse,
         transfer_process::{
             SuspendTransfer, TerminateTransfer, TransferProcess, TransferProcessState,
@@ -46,15 +52,59 @@ impl<'a> TransferProcessApi<'a> {
             .map(|ctx| ctx.inner.state().clone())
     }
 
+    pub async fn wait_for_state(
+        &self,
+        id: &str,
+        state: TransferProcessState,
+    ) -> EdcResult<TransferProcess> {
+        self.wait_for_state_with_opts(id, state, WaitOptions::default())
+            .await
+    }
+
+    /// Polls the transfer process until it reaches `state`, failing early if it gets terminated.
+    pub async fn wait_for_state_with_opts(
+        &self,
+        id: &str,
+        state: TransferProcessState,
+        options: WaitOptions,
+    ) -> EdcResult<TransferProcess> {
+        wait_for_state(&state, &options, || self.get(id), TransferProcess::state).await
+    }
+
     pub async fn query(&self, query: Query) -> EdcResult<Vec<TransferProcess>> {
         let url = self.0.path_for(&["transferprocesses", "request"]);
 
         self.0
-            .post::<_, Vec<WithContext<TransferProcess>>>(url, &self.0.context_for(&query))
+            .post_idempotent::<_, Vec<WithContext<TransferProcess>>>(
+                url,
+                &self.0.context_for(&query),
+            )
             .await
             .map(|results| results.into_iter().map(|ctx| ctx.inner).collect())
     }
 
+    pub fn query_stream(
+        &self,
+        query: Query,
+    ) -> impl Stream<Item = EdcResult<TransferProcess>> + 'a {
+        self.query_stream_with_opts(query, Pagination::default())
+    }
+
+    pub fn query_stream_with_opts(
+        &self,
+        query: Query,
+        pagination: Pagination,
+    ) -> impl Stream<Item = EdcResult<TransferProcess>> + 'a {
+        let client = self.0;
+        paginate(query, pagination, move |query| async move {
+            TransferProcessApi::new(client).query(query).await
+        })
+    }
+
+    pub async fn query_all(&self, query: Query) -> EdcResult<Vec<TransferProcess>> {
+        self.query_stream(query).try_collect().await
+    }
+
     pub async fn terminate(&self, id: &str, reason: &str) -> EdcResult<()> {
         let url = self.0.path_for(&["transferprocesses", id, "terminate"]);
 
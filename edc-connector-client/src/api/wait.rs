@@ -0,0 +1,165 @@
+use std::{future::Future, ops::ControlFlow, time::Duration};
+
+use bon::Builder;
+use tokio::time::Instant;
+
+use crate::{
+    error::WaitError,
+    retry::InvalidMultiplier,
+    types::{
+        contract_negotiation::ContractNegotiationState, transfer_process::TransferProcessState,
+    },
+    EdcResult, Error,
+};
+
+/// Options for polling the state of a contract negotiation or a transfer process.
+#[derive(Debug, Clone, Builder)]
+pub struct WaitOptions {
+    #[builder(default = Duration::from_millis(500))]
+    interval: Duration,
+    #[builder(default = Duration::from_secs(30))]
+    timeout: Duration,
+    /// Factor applied to the interval after each poll, rejected when not a
+    /// finite number of at least 1.
+    #[builder(
+        default = 1.0,
+        with = |backoff: f64| -> Result<_, InvalidMultiplier> {
+            if backoff.is_finite() && backoff >= 1.0 {
+                Ok(backoff)
+            } else {
+                Err(InvalidMultiplier(backoff))
+            }
+        }
+    )]
+    backoff: f64,
+    #[builder(default = Duration::from_secs(5))]
+    max_interval: Duration,
+}
+
+impl WaitOptions {
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub fn backoff(&self) -> f64 {
+        self.backoff
+    }
+
+    pub fn max_interval(&self) -> Duration {
+        self.max_interval
+    }
+}
+
+impl Default for WaitOptions {
+    fn default() -> Self {
+        WaitOptions::builder().build()
+    }
+}
+
+pub(crate) trait State: PartialEq {
+    fn name(&self) -> &str;
+    fn is_terminal(&self) -> bool;
+}
+
+impl State for ContractNegotiationState {
+    fn name(&self) -> &str {
+        self.as_str()
+    }
+
+    fn is_terminal(&self) -> bool {
+        matches!(self, ContractNegotiationState::Terminated)
+    }
+}
+
+impl State for TransferProcessState {
+    fn name(&self) -> &str {
+        self.as_str()
+    }
+
+    fn is_terminal(&self) -> bool {
+        matches!(self, TransferProcessState::Terminated)
+    }
+}
+
+/// Polls the entity until its state, as read by `state_of`, is the expected
+/// one, returning the last polled entity.
+pub(crate) async fn wait_for_state<S, T, F, Fut>(
+    expected: &S,
+    options: &WaitOptions,
+    poll: F,
+    state_of: impl Fn(&T) -> &S,
+) -> EdcResult<T>
+where
+    S: State,
+    F: Fn() -> Fut,
+    Fut: Future<Output = EdcResult<T>>,
+{
+    let state_of = &state_of;
+    poll_until(
+        options,
+        move || {
+            let entity = poll();
+            async move {
+                let entity = entity.await?;
+                let current = state_of(&entity);
+
+                if current == expected {
+                    Ok(ControlFlow::Break(entity))
+                } else if current.is_terminal() {
+                    Err(WaitError::UnexpectedState {
+                        expected: expected.name().to_string(),
+                        actual: current.name().to_string(),
+                    }
+                    .into())
+                } else {
+                    Ok(ControlFlow::Continue(current.name().to_string()))
+                }
+            }
+        },
+        |last, elapsed| {
+            WaitError::Timeout {
+                expected: expected.name().to_string(),
+                last,
+                elapsed,
+            }
+            .into()
+        },
+    )
+    .await
+}
+
+/// Polls until `poll` breaks with a value, waiting between polls with the
+/// backoff of the options. Once the timeout elapses, the last pending value
+/// is turned into the returned error by `on_timeout`.
+pub(crate) async fn poll_until<T, P, F, Fut>(
+    options: &WaitOptions,
+    poll: F,
+    on_timeout: impl FnOnce(P, Duration) -> Error,
+) -> EdcResult<T>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = EdcResult<ControlFlow<T, P>>>,
+{
+    let started = Instant::now();
+    let mut interval = options.interval;
+
+    loop {
+        let pending = match poll().await? {
+            ControlFlow::Break(value) => return Ok(value),
+            ControlFlow::Continue(pending) => pending,
+        };
+
+        let elapsed = started.elapsed();
+        if elapsed >= options.timeout {
+            return Err(on_timeout(pending, elapsed));
+        }
+
+        tokio::time::sleep(interval.min(options.timeout - elapsed)).await;
+        interval = Duration::try_from_secs_f64(interval.as_secs_f64() * options.backoff)
+            .map_or(options.max_interval, |next| next.min(options.max_interval));
+    }
+}
@@ -1,6 +1,14 @@
-use crate::EdcResult;
+use crate::{
+    middleware::{Middleware, Next},
+    EdcResult, Error,
+};
+use async_trait::async_trait;
 use oauth::OAuth2;
 pub use oauth::OAuth2Config;
+use reqwest::{
+    header::{HeaderValue, AUTHORIZATION},
+    Client, Request, Response,
+};
 
 mod oauth;
 
@@ -19,4 +27,36 @@ impl Auth {
     pub fn oauth(cfg: OAuth2Config) -> EdcResult<Auth> {
         Ok(Auth::OAuth2(OAuth2::init(cfg)?))
     }
+
+    pub(crate) fn with_http_client(self, http_client: &Client) -> Auth {
+        match self {
+            Auth::OAuth2(oauth) => Auth::OAuth2(oauth.with_http_client(http_client)),
+            auth => auth,
+        }
+    }
+}
+
+#[async_trait]
+impl Middleware for Auth {
+    async fn handle(&self, mut request: Request, next: Next<'_>) -> EdcResult<Response> {
+        match self {
+            Auth::NoAuth => {}
+            Auth::ApiToken(token) => {
+                request
+                    .headers_mut()
+                    .insert("X-Api-Key", header_value(token.clone())?);
+            }
+            Auth::OAuth2(client) => {
+                let token = client.token().await?;
+                request
+                    .headers_mut()
+                    .insert(AUTHORIZATION, header_value(format!("Bearer {}", token))?);
+            }
+        }
+        next.run(request).await
+    }
+}
+
+fn header_value(value: String) -> EdcResult<HeaderValue> {
+    HeaderValue::try_from(value).map_err(|e| Error::Auth(Box::new(e)))
 }
@@ -40,6 +40,7 @@ pub struct OAuth2Internal {
     oauth_client: OAuthClient,
     session: Mutex<Option<OAuthTokenSession>>,
     http_client: Client,
+    custom_http_client: bool,
     scopes: Vec<String>,
 }
 
@@ -85,6 +86,7 @@ pub struct OAuth2Config {
     token_url: String,
     #[builder(default = vec!["management-api:read".to_string(), "management-api:write".to_string()])]
     scopes: Vec<String>,
+    http_client: Option<Client>,
 }
 
 impl OAuth2 {
@@ -100,11 +102,28 @@ impl OAuth2 {
         Ok(OAuth2(Arc::new(OAuth2Internal {
             oauth_client: client,
             session: Mutex::default(),
-            http_client: Client::new(),
+            custom_http_client: cfg.http_client.is_some(),
+            http_client: cfg.http_client.unwrap_or_default(),
             scopes: cfg.scopes,
         })))
     }
 
+    /// Rebinds the token exchange to the connector http client, unless
+    /// a dedicated one was configured in [`OAuth2Config`].
+    pub(crate) fn with_http_client(&self, http_client: &Client) -> OAuth2 {
+        if self.0.custom_http_client {
+            self.clone()
+        } else {
+            OAuth2(Arc::new(OAuth2Internal {
+                oauth_client: self.0.oauth_client.clone(),
+                session: Mutex::default(),
+                http_client: http_client.clone(),
+                custom_http_client: false,
+                scopes: self.0.scopes.clone(),
+            }))
+        }
+    }
+
     pub async fn token(&self) -> EdcResult<String> {
         self.0.token().await
     }
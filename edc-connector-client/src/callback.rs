@@ -0,0 +1,175 @@
+//! Embeddable HTTP receiver for the events the connector delivers to a
+//! [`CallbackAddress`](crate::types::callback_address::CallbackAddress).
+//!
+//! ```rust,no_run
+//! use edc_connector_client::{callback::{self, CallbackReceiver}, types::event::Event};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let (handler, mut events) = callback::channel(16);
+//!
+//!     let receiver = CallbackReceiver::builder()
+//!         .auth("X-Api-Key", "secret")
+//!         .handler(handler)
+//!         .build();
+//!
+//!     let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await?;
+//!     tokio::spawn(receiver.serve(listener));
+//!
+//!     while let Some(envelope) = events.recv().await {
+//!         if let Event::TransferProcess(event) = envelope.event() {
+//!             println!("{:?} {}", event.kind(), event.transfer_process_id());
+//!         }
+//!     }
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use std::{error::Error as StdError, future::Future, sync::Arc};
+
+use async_trait::async_trait;
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+    Router,
+};
+use bon::Builder;
+use subtle::ConstantTimeEq;
+use tokio::{net::TcpListener, sync::mpsc};
+
+use crate::types::event::EventEnvelope;
+
+pub type HandlerError = Box<dyn StdError + Send + Sync>;
+
+/// Receives the events accepted by a [`CallbackReceiver`].
+///
+/// Returning an error answers the connector with `500`, which makes it retry
+/// transactional callbacks.
+#[async_trait]
+pub trait EventHandler: Send + Sync + 'static {
+    async fn handle(&self, envelope: EventEnvelope) -> Result<(), HandlerError>;
+}
+
+/// [`EventHandler`] forwarding events to a tokio channel, see [`channel`].
+pub struct ChannelHandler(mpsc::Sender<EventEnvelope>);
+
+#[async_trait]
+impl EventHandler for ChannelHandler {
+    async fn handle(&self, envelope: EventEnvelope) -> Result<(), HandlerError> {
+        self.0
+            .send(envelope)
+            .await
+            .map_err(|e| e.to_string().into())
+    }
+}
+
+/// Creates a bounded channel along with the handler feeding it.
+pub fn channel(buffer: usize) -> (ChannelHandler, mpsc::Receiver<EventEnvelope>) {
+    let (tx, rx) = mpsc::channel(buffer);
+    (ChannelHandler(tx), rx)
+}
+
+/// [`EventHandler`] backed by an async function, see [`handler_fn`].
+pub struct FnHandler<F>(F);
+
+#[async_trait]
+impl<F, Fut> EventHandler for FnHandler<F>
+where
+    F: Fn(EventEnvelope) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), HandlerError>> + Send,
+{
+    async fn handle(&self, envelope: EventEnvelope) -> Result<(), HandlerError> {
+        (self.0)(envelope).await
+    }
+}
+
+pub fn handler_fn<F, Fut>(f: F) -> FnHandler<F>
+where
+    F: Fn(EventEnvelope) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), HandlerError>> + Send,
+{
+    FnHandler(f)
+}
+
+#[derive(Clone, Builder)]
+pub struct CallbackReceiver {
+    #[builder(field)]
+    auth: Option<(String, String)>,
+    #[builder(with = |handler: impl EventHandler| Arc::new(handler) as Arc<dyn EventHandler>)]
+    handler: Arc<dyn EventHandler>,
+    #[builder(default = "/".to_string(), into)]
+    path: String,
+}
+
+impl<S: callback_receiver_builder::State> CallbackReceiverBuilder<S> {
+    /// Requires the `header` sent by the connector to match `value`.
+    ///
+    /// `header` is the `authKey` of the callback address and `value` the secret
+    /// stored in the vault under its `authCodeId`.
+    pub fn auth(mut self, header: &str, value: &str) -> Self {
+        self.auth = Some((header.to_string(), value.to_string()));
+        self
+    }
+}
+
+impl CallbackReceiver {
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns a router accepting events on the configured path, which can be
+    /// merged in an existing axum application.
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route(&self.path, post(receive))
+            .with_state(self.clone())
+    }
+
+    pub async fn serve(self, listener: TcpListener) -> std::io::Result<()> {
+        axum::serve(listener, self.router()).await
+    }
+
+    pub async fn serve_with_shutdown<F>(
+        self,
+        listener: TcpListener,
+        signal: F,
+    ) -> std::io::Result<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        axum::serve(listener, self.router())
+            .with_graceful_shutdown(signal)
+            .await
+    }
+
+    fn authorized(&self, headers: &HeaderMap) -> bool {
+        match &self.auth {
+            Some((header, value)) => headers
+                .get(header)
+                .is_some_and(|received| bool::from(received.as_bytes().ct_eq(value.as_bytes()))),
+            None => true,
+        }
+    }
+}
+
+async fn receive(
+    State(receiver): State<CallbackReceiver>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    if !receiver.authorized(&headers) {
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    let Ok(envelope) = serde_json::from_slice::<EventEnvelope>(&body) else {
+        return StatusCode::BAD_REQUEST;
+    };
+
+    match receiver.handler.handle(envelope).await {
+        Ok(()) => StatusCode::NO_CONTENT,
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
@@ -1,18 +1,21 @@
-use std::{future::Future, sync::Arc};
+use std::{future::Future, sync::Arc, time::Duration};
 
-use reqwest::{Client, RequestBuilder, Response};
+use reqwest::{Certificate, Client, Identity, Method, Response};
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
     api::{
-        AssetApi, CatalogApi, ContractAgreementApi, ContractDefinitionApi, ContractNegotiationApi,
-        DataPlaneApi, EdrApi, ParticipantContextApi, ParticipantContextConfigApi, PolicyApi,
-        SecretsApi, TransferProcessApi,
+        ArchiveApi, AssetApi, CatalogApi, ConsumerApi, ContractAgreementApi, ContractDefinitionApi,
+        ContractNegotiationApi, DataPlaneApi, EdrApi, ParticipantContextApi,
+        ParticipantContextConfigApi, PolicyApi, ProvisioningApi, SecretsApi, TransferProcessApi,
     },
+    data_plane::DataPlaneClient,
     error::{
         BuilderError, ManagementApiError, ManagementApiErrorDetail, ManagementApiErrorDetailKind,
     },
-    types::context::WithContextRef,
+    middleware::{Middleware, Next},
+    retry::RetryPolicy,
+    types::{context::WithContextRef, data_address::DataAddress},
     Auth, EdcResult, Error,
 };
 
@@ -42,36 +45,49 @@ impl EdcConnectorApiVersion {
 
 pub(crate) struct EdcConnectorClientInternal {
     client: Client,
+    data_plane_client: Client,
     pub(crate) management_url: String,
-    pub(crate) auth: Auth,
+    middlewares: Vec<Arc<dyn Middleware>>,
     pub(crate) version: EdcConnectorApiVersion,
     pub(crate) participant_context: Option<String>,
+    retry: RetryPolicy,
 }
 
 impl EdcConnectorClientInternal {
     pub(crate) fn new(
         client: Client,
         management_url: String,
-        auth: Auth,
+        middlewares: Vec<Arc<dyn Middleware>>,
         version: EdcConnectorApiVersion,
         participant_context: Option<String>,
+        retry: RetryPolicy,
+        data_plane_client: Client,
     ) -> Self {
         Self {
             client,
+            data_plane_client,
             management_url,
-            auth,
+            middlewares,
             version,
             participant_context,
+            retry,
         }
     }
 
     pub(crate) async fn get<R: DeserializeOwned>(&self, path: impl AsRef<str>) -> EdcResult<R> {
         let response = self
-            .client
-            .get(path.as_ref())
-            .authenticated(&self.auth)
-            .await?
-            .send()
+            .send(Method::GET, path.as_ref(), Option::<&()>::None, true)
+            .await?;
+
+        self.handle_response(response, as_json).await
+    }
+
+    pub(crate) async fn post_without_body<R: DeserializeOwned>(
+        &self,
+        path: impl AsRef<str>,
+    ) -> EdcResult<R> {
+        let response = self
+            .send(Method::POST, path.as_ref(), Option::<&()>::None, false)
             .await?;
 
         self.handle_response(response, as_json).await
@@ -79,12 +95,7 @@ impl EdcConnectorClientInternal {
 
     pub(crate) async fn put(&self, path: impl AsRef<str>, body: &impl Serialize) -> EdcResult<()> {
         let response = self
-            .client
-            .put(path.as_ref())
-            .json(body)
-            .authenticated(&self.auth)
-            .await?
-            .send()
+            .send(Method::PUT, path.as_ref(), Some(body), true)
             .await?;
 
         self.handle_response(response, empty).await
@@ -92,11 +103,7 @@ impl EdcConnectorClientInternal {
 
     pub(crate) async fn del(&self, path: impl AsRef<str>) -> EdcResult<()> {
         let response = self
-            .client
-            .delete(path.as_ref())
-            .authenticated(&self.auth)
-            .await?
-            .send()
+            .send(Method::DELETE, path.as_ref(), Option::<&()>::None, true)
             .await?;
 
         self.handle_response(response, empty).await
@@ -107,7 +114,17 @@ impl EdcConnectorClientInternal {
         path: impl AsRef<str>,
         body: &I,
     ) -> EdcResult<R> {
-        self.internal_post(path, body, as_json).await
+        self.internal_post(path, body, as_json, false).await
+    }
+
+    /// Post request that does not change the connector state (e.g. queries),
+    /// thus can be safely retried.
+    pub(crate) async fn post_idempotent<I: Serialize, R: DeserializeOwned>(
+        &self,
+        path: impl AsRef<str>,
+        body: &I,
+    ) -> EdcResult<R> {
+        self.internal_post(path, body, as_json, true).await
     }
 
     pub(crate) async fn put_no_response<I: Serialize>(
@@ -123,7 +140,7 @@ impl EdcConnectorClientInternal {
         path: impl AsRef<str>,
         body: &I,
     ) -> EdcResult<()> {
-        self.internal_post(path, body, empty).await
+        self.internal_post(path, body, empty, false).await
     }
 
     async fn internal_put<I, F, Fut, R>(
@@ -138,12 +155,7 @@ impl EdcConnectorClientInternal {
         Fut: Future<Output = EdcResult<R>>,
     {
         let response = self
-            .client
-            .put(path.as_ref())
-            .json(body)
-            .authenticated(&self.auth)
-            .await?
-            .send()
+            .send(Method::PUT, path.as_ref(), Some(body), true)
             .await?;
 
         self.handle_response(response, handler).await
@@ -154,6 +166,7 @@ impl EdcConnectorClientInternal {
         path: impl AsRef<str>,
         body: &I,
         handler: F,
+        idempotent: bool,
     ) -> EdcResult<R>
     where
         I: Serialize,
@@ -161,17 +174,48 @@ impl EdcConnectorClientInternal {
         Fut: Future<Output = EdcResult<R>>,
     {
         let response = self
-            .client
-            .post(path.as_ref())
-            .json(body)
-            .authenticated(&self.auth)
-            .await?
-            .send()
+            .send(Method::POST, path.as_ref(), Some(body), idempotent)
             .await?;
 
         self.handle_response(response, handler).await
     }
 
+    async fn send<I: Serialize>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&I>,
+        idempotent: bool,
+    ) -> EdcResult<Response> {
+        let mut attempt = 1;
+        loop {
+            let mut request = self.client.request(method.clone(), path);
+            if let Some(body) = body {
+                request = request.json(body);
+            }
+            let result = Next::new(&self.client, &self.middlewares)
+                .run(request.build()?)
+                .await;
+
+            let retry = match &result {
+                Ok(response) => self
+                    .retry
+                    .should_retry_status(response.status(), idempotent),
+                Err(Error::Reqwest(err) | Error::Timeout(err)) => {
+                    self.retry.should_retry_error(err, idempotent)
+                }
+                Err(_) => false,
+            };
+
+            if !retry || attempt >= self.retry.max_attempts() {
+                return result;
+            }
+
+            tokio::time::sleep(self.retry.backoff(attempt)).await;
+            attempt += 1;
+        }
+    }
+
     async fn handle_response<F, Fut, R>(&self, response: Response, handler: F) -> EdcResult<R>
     where
         F: Fn(Response) -> Fut,
@@ -188,10 +232,11 @@ impl EdcConnectorClientInternal {
                 Err(_) => ManagementApiErrorDetailKind::Raw(text),
             };
 
-            Err(Error::ManagementApi(ManagementApiError {
+            Err(ManagementApiError {
                 status_code: status,
                 error_detail: err,
-            }))
+            }
+            .into())
         }
     }
 
@@ -243,7 +288,8 @@ impl EdcConnectorClientInternal {
 }
 
 async fn as_json<R: DeserializeOwned>(response: Response) -> EdcResult<R> {
-    response.json().await.map(Ok)?
+    let body = response.text().await?;
+    serde_json::from_str(&body).map_err(|source| Error::Deserialization { source, body })
 }
 
 async fn empty(_response: Response) -> EdcResult<()> {
@@ -254,16 +300,20 @@ impl EdcConnectorClient {
     pub(crate) fn new(
         client: Client,
         management_url: String,
-        auth: Auth,
+        middlewares: Vec<Arc<dyn Middleware>>,
         version: EdcConnectorApiVersion,
         participant_context: Option<String>,
+        retry: RetryPolicy,
+        data_plane_client: Client,
     ) -> Self {
         Self(Arc::new(EdcConnectorClientInternal::new(
             client,
             management_url,
-            auth,
+            middlewares,
             version,
             participant_context,
+            retry,
+            data_plane_client,
         )))
     }
 
@@ -319,6 +369,34 @@ impl EdcConnectorClient {
         ParticipantContextConfigApi::new(&self.0)
     }
 
+    pub fn consumer(&self) -> ConsumerApi<'_> {
+        ConsumerApi::new(&self.0)
+    }
+
+    pub fn provisioning(&self) -> ProvisioningApi<'_> {
+        ProvisioningApi::new(&self.0)
+    }
+
+    pub fn archive(&self) -> ArchiveApi<'_> {
+        ArchiveApi::new(&self.0)
+    }
+
+    /// Creates a data plane client for the EDR `data_address`.
+    pub fn data_plane(&self, data_address: &DataAddress) -> EdcResult<DataPlaneClient> {
+        DataPlaneClient::with_http_client(self.0.data_plane_client.clone(), data_address, None)
+    }
+
+    /// Creates a data plane client for the EDR of a transfer process, refreshing
+    /// its token through the connector when expired or rejected.
+    pub async fn data_plane_for(&self, transfer_process_id: &str) -> EdcResult<DataPlaneClient> {
+        let data_address = self.edrs().get_data_address(transfer_process_id).await?;
+        DataPlaneClient::with_http_client(
+            self.0.data_plane_client.clone(),
+            &data_address,
+            Some((self.clone(), transfer_process_id.to_string())),
+        )
+    }
+
     pub fn api_version(&self) -> EdcConnectorApiVersion {
         self.0.version.clone()
     }
@@ -329,6 +407,14 @@ pub struct EdcClientConnectorBuilder {
     auth: Auth,
     version: EdcConnectorApiVersion,
     participant_context: Option<String>,
+    retry: RetryPolicy,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    http_client: Option<Client>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    identity: Option<Identity>,
+    root_certificates: Vec<Certificate>,
+    data_plane_http_client: Option<Client>,
 }
 
 impl EdcClientConnectorBuilder {
@@ -360,18 +446,104 @@ impl EdcClientConnectorBuilder {
         self
     }
 
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn with_middleware(mut self, middleware: impl Middleware) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    /// Uses a preconfigured http client for the management api and for the OAuth2
+    /// token exchange. It cannot be combined with the other transport settings.
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Client certificate used for mutual TLS.
+    pub fn identity(mut self, identity: Identity) -> Self {
+        self.identity = Some(identity);
+        self
+    }
+
+    pub fn root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Uses a preconfigured http client for the provider data planes. By
+    /// default they get a client of their own, so that the identity and the
+    /// root certificates of the management api are not sent to third parties.
+    pub fn with_data_plane_http_client(mut self, client: Client) -> Self {
+        self.data_plane_http_client = Some(client);
+        self
+    }
+
     pub fn build(self) -> Result<EdcConnectorClient, BuilderError> {
         let url = self
             .management_url
             .ok_or_else(|| BuilderError::missing_property("management_url"))?;
-        let client = Client::new();
+        let client = match self.http_client {
+            Some(client) => {
+                if self.timeout.is_some() {
+                    return Err(BuilderError::conflicting_property("timeout"));
+                }
+                if self.connect_timeout.is_some() {
+                    return Err(BuilderError::conflicting_property("connect_timeout"));
+                }
+                if self.identity.is_some() {
+                    return Err(BuilderError::conflicting_property("identity"));
+                }
+                if !self.root_certificates.is_empty() {
+                    return Err(BuilderError::conflicting_property("root_certificate"));
+                }
+                client
+            }
+            None => {
+                let mut builder = Client::builder();
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(identity) = self.identity {
+                    builder = builder.identity(identity);
+                }
+                for certificate in self.root_certificates {
+                    builder = builder.add_root_certificate(certificate);
+                }
+                builder.build()?
+            }
+        };
+
+        let auth = self.auth.with_http_client(&client);
+
+        let middlewares = std::iter::once(Arc::new(auth) as Arc<dyn Middleware>)
+            .chain(self.middlewares)
+            .collect();
 
         Ok(EdcConnectorClient::new(
             client,
             url,
-            self.auth,
+            middlewares,
             self.version,
             self.participant_context,
+            self.retry,
+            self.data_plane_http_client.unwrap_or_default(),
         ))
     }
 }
@@ -383,22 +555,14 @@ impl Default for EdcClientConnectorBuilder {
             auth: Auth::NoAuth,
             version: EdcConnectorApiVersion::V3,
             participant_context: None,
-        }
-    }
-}
-
-trait BuilderExt: Sized {
-    fn authenticated(self, auth: &Auth) -> impl Future<Output = EdcResult<Self>>;
-}
-
-impl BuilderExt for RequestBuilder {
-    async fn authenticated(self, auth: &Auth) -> EdcResult<Self> {
-        match auth {
-            Auth::NoAuth => Ok(self),
-            Auth::ApiToken(token) => Ok(self.header("X-Api-Key", token)),
-            Auth::OAuth2(client) => {
-                Ok(self.header("Authorization", format!("Bearer {}", client.token().await?)))
-            }
+            retry: RetryPolicy::none(),
+            middlewares: Vec::new(),
+            http_client: None,
+            timeout: None,
+            connect_timeout: None,
+            identity: None,
+            root_certificates: Vec::new(),
+            data_plane_http_client: None,
         }
     }
 }
@@ -0,0 +1,319 @@
+//! Client for pulling data from a provider data plane with the
+//! [`DataAddress`] of an EDR.
+//!
+//! ```rust,no_run
+//! use edc_connector_client::EdcConnectorClient;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = EdcConnectorClient::builder()
+//!         .management_url("http://myedc")
+//!         .build()?;
+//!
+//!     let data_plane = client.data_plane_for("transfer-process-id").await?;
+//!
+//!     let body = data_plane
+//!         .get("items")
+//!         .query(&[("page", "1")])
+//!         .send()
+//!         .await?
+//!         .text()
+//!         .await?;
+//!
+//!     println!("{}", body);
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use futures::{Stream, TryStreamExt};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION},
+    Client, Method, Request, RequestBuilder, Response, StatusCode, Url,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::{sync::Mutex, time::Instant};
+
+use crate::{
+    error::DataPlaneError, types::data_address::DataAddress, EdcConnectorClient, EdcResult,
+};
+
+/// Tokens are considered expired slightly before their actual expiry.
+const EXPIRY_MARGIN: Duration = Duration::from_secs(5);
+
+#[derive(Clone)]
+pub struct DataPlaneClient(Arc<DataPlaneClientInternal>);
+
+struct DataPlaneClientInternal {
+    http: Client,
+    edr: RwLock<Edr>,
+    refresh_lock: Mutex<()>,
+    source: Option<(EdcConnectorClient, String)>,
+}
+
+#[derive(Clone)]
+struct Edr {
+    endpoint: Url,
+    authorization: Option<String>,
+    refreshable: bool,
+    expires_at: Option<Instant>,
+}
+
+impl Edr {
+    fn from_data_address(data_address: &DataAddress) -> Result<Edr, DataPlaneError> {
+        let endpoint = data_address
+            .endpoint()
+            .and_then(|endpoint| Url::parse(endpoint).ok())
+            .ok_or_else(|| DataPlaneError::InvalidDataAddress("endpoint".to_string()))?;
+
+        // An expiry too far away to be represented is taken as no expiry.
+        let expires_at = data_address
+            .expires_in()
+            .and_then(|ttl| Instant::now().checked_add(ttl.saturating_sub(EXPIRY_MARGIN)));
+
+        Ok(Edr {
+            endpoint,
+            authorization: data_address.authorization().map(str::to_string),
+            refreshable: data_address.is_refreshable(),
+            expires_at,
+        })
+    }
+
+    fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| Instant::now() >= expires_at)
+    }
+
+    fn url_for(&self, path: &str) -> Url {
+        let mut url = self.endpoint.clone();
+        let path = path.trim_start_matches('/');
+        if !path.is_empty() {
+            let base = url.path().trim_end_matches('/').to_string();
+            url.set_path(&format!("{}/{}", base, path));
+        }
+        url
+    }
+}
+
+impl DataPlaneClient {
+    /// Creates a client for the data plane referenced by `data_address`.
+    ///
+    /// Clients created this way cannot refresh their token, see
+    /// [`EdcConnectorClient::data_plane_for`].
+    pub fn new(data_address: &DataAddress) -> EdcResult<DataPlaneClient> {
+        DataPlaneClient::with_http_client(Client::new(), data_address, None)
+    }
+
+    pub(crate) fn with_http_client(
+        http: Client,
+        data_address: &DataAddress,
+        source: Option<(EdcConnectorClient, String)>,
+    ) -> EdcResult<DataPlaneClient> {
+        let edr = Edr::from_data_address(data_address)?;
+        Ok(DataPlaneClient(Arc::new(DataPlaneClientInternal {
+            http,
+            edr: RwLock::new(edr),
+            refresh_lock: Mutex::new(()),
+            source,
+        })))
+    }
+
+    pub fn endpoint(&self) -> Url {
+        self.edr().endpoint
+    }
+
+    pub fn get(&self, path: &str) -> DataPlaneRequest {
+        self.request(Method::GET, path)
+    }
+
+    pub fn post(&self, path: &str) -> DataPlaneRequest {
+        self.request(Method::POST, path)
+    }
+
+    /// Starts a request for `path`, relative to the endpoint of the EDR.
+    pub fn request(&self, method: Method, path: &str) -> DataPlaneRequest {
+        DataPlaneRequest {
+            client: self.clone(),
+            path: path.to_string(),
+            builder: self.0.http.request(method, self.edr().url_for(path)),
+        }
+    }
+
+    /// Whether the token can be refreshed, which requires the client to be
+    /// bound to a transfer process and the EDR to advertise a refresh endpoint.
+    pub fn is_refreshable(&self) -> bool {
+        self.0.source.is_some() && self.edr().refreshable
+    }
+
+    /// Asks the connector to refresh the EDR of the bound transfer process.
+    pub async fn refresh(&self) -> EdcResult<()> {
+        let current = self.edr().authorization;
+        self.refresh_from(current.as_deref()).await
+    }
+
+    async fn refresh_from(&self, stale: Option<&str>) -> EdcResult<()> {
+        let Some((client, transfer_process_id)) = &self.0.source else {
+            return Err(DataPlaneError::RefreshNotSupported.into());
+        };
+
+        let _guard = self.0.refresh_lock.lock().await;
+
+        // Another request already refreshed the token while we were waiting.
+        if self.edr().authorization.as_deref() != stale {
+            return Ok(());
+        }
+
+        let data_address = client.edrs().refresh(transfer_process_id).await?;
+        let edr = Edr::from_data_address(&data_address)?;
+
+        if let Ok(mut current) = self.0.edr.write() {
+            *current = edr;
+        }
+        Ok(())
+    }
+
+    fn edr(&self) -> Edr {
+        match self.0.edr.read() {
+            Ok(edr) => edr.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    async fn execute(&self, path: &str, request: Request) -> EdcResult<DataPlaneResponse> {
+        let mut edr = self.edr();
+
+        if edr.is_expired() && self.is_refreshable() {
+            self.refresh_from(edr.authorization.as_deref()).await?;
+            edr = self.edr();
+        }
+
+        let retry = if self.is_refreshable() {
+            request.try_clone()
+        } else {
+            None
+        };
+
+        let response = self.0.http.execute(authorize(request, path, &edr)?).await?;
+
+        match retry {
+            Some(request)
+                if matches!(
+                    response.status(),
+                    StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN
+                ) =>
+            {
+                self.refresh_from(edr.authorization.as_deref()).await?;
+                let response = self
+                    .0
+                    .http
+                    .execute(authorize(request, path, &self.edr())?)
+                    .await?;
+                DataPlaneResponse::from_response(response).await
+            }
+            _ => DataPlaneResponse::from_response(response).await,
+        }
+    }
+}
+
+fn authorize(mut request: Request, path: &str, edr: &Edr) -> EdcResult<Request> {
+    let mut url = edr.url_for(path);
+    url.set_query(request.url().query());
+    *request.url_mut() = url;
+
+    if let Some(authorization) = &edr.authorization {
+        let value = HeaderValue::from_str(authorization)
+            .map_err(|_| DataPlaneError::InvalidDataAddress("authorization".to_string()))?;
+        request.headers_mut().insert(AUTHORIZATION, value);
+    }
+    Ok(request)
+}
+
+/// A request to the data plane, authorized with the EDR token when sent.
+pub struct DataPlaneRequest {
+    client: DataPlaneClient,
+    path: String,
+    builder: RequestBuilder,
+}
+
+impl DataPlaneRequest {
+    pub fn query<T: Serialize + ?Sized>(mut self, query: &T) -> Self {
+        self.builder = self.builder.query(query);
+        self
+    }
+
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.builder = self.builder.header(name, value);
+        self
+    }
+
+    pub fn headers(mut self, headers: HeaderMap) -> Self {
+        self.builder = self.builder.headers(headers);
+        self
+    }
+
+    pub fn json<T: Serialize + ?Sized>(mut self, body: &T) -> Self {
+        self.builder = self.builder.json(body);
+        self
+    }
+
+    /// Sets the request body, streaming bodies are not retried after a refresh.
+    pub fn body(mut self, body: impl Into<reqwest::Body>) -> Self {
+        self.builder = self.builder.body(body);
+        self
+    }
+
+    pub async fn send(self) -> EdcResult<DataPlaneResponse> {
+        let request = self.builder.build()?;
+        self.client.execute(&self.path, request).await
+    }
+}
+
+/// A successful response of the data plane.
+pub struct DataPlaneResponse(Response);
+
+impl DataPlaneResponse {
+    async fn from_response(response: Response) -> EdcResult<DataPlaneResponse> {
+        if response.status().is_success() {
+            Ok(DataPlaneResponse(response))
+        } else {
+            let status_code = response.status();
+            let body = response.text().await?;
+            Err(DataPlaneError::Status { status_code, body }.into())
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.0.status()
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        self.0.headers()
+    }
+
+    pub async fn bytes(self) -> EdcResult<Bytes> {
+        Ok(self.0.bytes().await?)
+    }
+
+    pub async fn text(self) -> EdcResult<String> {
+        Ok(self.0.text().await?)
+    }
+
+    pub async fn json<T: DeserializeOwned>(self) -> EdcResult<T> {
+        Ok(self.0.json().await?)
+    }
+
+    /// Streams the body without buffering it in memory.
+    pub fn bytes_stream(self) -> impl Stream<Item = EdcResult<Bytes>> {
+        self.0.bytes_stream().map_err(Into::into)
+    }
+
+    pub fn into_inner(self) -> Response {
+        self.0
+    }
+}
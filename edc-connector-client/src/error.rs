@@ -1,16 +1,146 @@
+use std::{fmt, time::Duration};
+
 use reqwest::StatusCode;
 use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{api::Operation, types::manifest::ResourceKind};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error(transparent)]
-    Reqwest(#[from] reqwest::Error),
+    Reqwest(reqwest::Error),
+
+    /// The request did not complete within the timeout of the client.
+    #[error("Request timed out: {0}")]
+    Timeout(#[source] reqwest::Error),
+
+    /// The response body does not match the expected type.
+    #[error("Failed to deserialize the response: {source}")]
+    Deserialization {
+        #[source]
+        source: serde_json::Error,
+        body: String,
+    },
+
+    /// 404, or an entity missing from a successful response.
+    #[error(transparent)]
+    NotFound(ManagementApiError),
+
+    /// 409, usually an entity with the same id already exists.
+    #[error(transparent)]
+    Conflict(ManagementApiError),
+
+    /// 400, with the validation violations in the error detail.
+    #[error(transparent)]
+    BadRequest(ManagementApiError),
+
+    #[error(transparent)]
+    Unauthorized(ManagementApiError),
+
+    #[error(transparent)]
+    Forbidden(ManagementApiError),
 
+    /// Any 5xx.
+    #[error(transparent)]
+    ServerError(ManagementApiError),
+
+    /// Any other error status.
     #[error(transparent)]
     ManagementApi(ManagementApiError),
 
     #[error(transparent)]
     Auth(#[from] Box<dyn std::error::Error + Send + Sync>),
+
+    #[error(transparent)]
+    Wait(#[from] WaitError),
+
+    #[error(transparent)]
+    Workflow(#[from] Box<WorkflowError>),
+
+    #[error(transparent)]
+    DataPlane(#[from] DataPlaneError),
+
+    #[error(transparent)]
+    Manifest(#[from] ManifestError),
+
+    #[error(transparent)]
+    Apply(#[from] Box<ApplyError>),
+
+    #[error(transparent)]
+    Archive(#[from] ArchiveError),
+}
+
+impl Error {
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Error::NotFound(_))
+    }
+
+    pub fn is_conflict(&self) -> bool {
+        matches!(self, Error::Conflict(_))
+    }
+
+    pub fn is_bad_request(&self) -> bool {
+        matches!(self, Error::BadRequest(_))
+    }
+
+    pub fn is_unauthorized(&self) -> bool {
+        matches!(self, Error::Unauthorized(_))
+    }
+
+    pub fn is_forbidden(&self) -> bool {
+        matches!(self, Error::Forbidden(_))
+    }
+
+    pub fn is_server_error(&self) -> bool {
+        matches!(self, Error::ServerError(_))
+    }
+
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, Error::Timeout(_))
+    }
+
+    /// The error returned by the management api, whatever its status.
+    pub fn management_api_error(&self) -> Option<&ManagementApiError> {
+        match self {
+            Error::NotFound(err)
+            | Error::Conflict(err)
+            | Error::BadRequest(err)
+            | Error::Unauthorized(err)
+            | Error::Forbidden(err)
+            | Error::ServerError(err)
+            | Error::ManagementApi(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    pub fn status_code(&self) -> Option<StatusCode> {
+        self.management_api_error().map(|err| err.status_code)
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            Error::Timeout(err)
+        } else {
+            Error::Reqwest(err)
+        }
+    }
+}
+
+impl From<ManagementApiError> for Error {
+    fn from(err: ManagementApiError) -> Self {
+        match err.status_code {
+            StatusCode::BAD_REQUEST => Error::BadRequest(err),
+            StatusCode::UNAUTHORIZED => Error::Unauthorized(err),
+            StatusCode::FORBIDDEN => Error::Forbidden(err),
+            StatusCode::NOT_FOUND => Error::NotFound(err),
+            StatusCode::CONFLICT => Error::Conflict(err),
+            status if status.is_server_error() => Error::ServerError(err),
+            _ => Error::ManagementApi(err),
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -20,11 +150,34 @@ pub struct ManagementApiError {
     pub error_detail: ManagementApiErrorDetailKind,
 }
 
+impl ManagementApiError {
+    /// The details of the error, empty when the body was not parsed.
+    pub fn details(&self) -> &[ManagementApiErrorDetail] {
+        match &self.error_detail {
+            ManagementApiErrorDetailKind::Parsed(details) => details,
+            ManagementApiErrorDetailKind::Raw(_) => &[],
+        }
+    }
+
+    /// The details reporting an invalid value, as returned on validation
+    /// failures.
+    pub fn violations(&self) -> impl Iterator<Item = &ManagementApiErrorDetail> {
+        self.details()
+            .iter()
+            .filter(|detail| detail.path.is_some() || detail.invalid_value.is_some())
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ManagementApiErrorDetail {
     pub message: String,
     #[serde(rename = "type")]
     pub kind: String,
+    /// Path of the invalid property in the request body.
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(rename = "invalidValue", default)]
+    pub invalid_value: Option<Value>,
 }
 
 #[derive(Debug)]
@@ -33,16 +186,128 @@ pub enum ManagementApiErrorDetailKind {
     Parsed(Vec<ManagementApiErrorDetail>),
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum WaitError {
+    #[error("Timed out after {elapsed:?} waiting for state {expected}, last state was {last}")]
+    Timeout {
+        expected: String,
+        last: String,
+        elapsed: Duration,
+    },
+    #[error("Reached state {actual} while waiting for state {expected}")]
+    UnexpectedState { expected: String, actual: String },
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Consumer workflow failed at step {step}")]
+pub struct WorkflowError {
+    pub step: WorkflowStep,
+    pub negotiation_id: Option<String>,
+    pub agreement_id: Option<String>,
+    pub transfer_process_id: Option<String>,
+    #[source]
+    pub source: Option<Error>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkflowStep {
+    Catalog,
+    OfferSelection,
+    Negotiation,
+    Agreement,
+    Transfer,
+    Edr,
+}
+
+impl fmt::Display for WorkflowStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let step = match self {
+            WorkflowStep::Catalog => "catalog",
+            WorkflowStep::OfferSelection => "offer selection",
+            WorkflowStep::Negotiation => "negotiation",
+            WorkflowStep::Agreement => "agreement",
+            WorkflowStep::Transfer => "transfer",
+            WorkflowStep::Edr => "edr",
+        };
+        f.write_str(step)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DataPlaneError {
+    #[error("Data address has a missing or invalid property {0}")]
+    InvalidDataAddress(String),
+    #[error("Data plane returned {status_code}")]
+    Status {
+        status_code: StatusCode,
+        body: String,
+    },
+    #[error("Token refresh requires a client bound to a transfer process")]
+    RefreshNotSupported,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ManifestError {
+    #[error("Invalid manifest: {0}")]
+    Json(#[from] serde_json::Error),
+    #[cfg(feature = "yaml")]
+    #[error("Invalid manifest: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+    #[error("Every {0} of the manifest requires an @id")]
+    MissingId(ResourceKind),
+    #[error("Duplicated {kind} {id} in the manifest")]
+    DuplicateId { kind: ResourceKind, id: String },
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to {operation} {kind} {id} after {applied} applied changes")]
+pub struct ApplyError {
+    pub operation: Operation,
+    pub kind: ResourceKind,
+    pub id: String,
+    pub applied: usize,
+    #[source]
+    pub source: Error,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ArchiveError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Failed to serialize the archive: {0}")]
+    Serialize(#[source] serde_json::Error),
+    #[error("Invalid archive entry at line {line}: {source}")]
+    InvalidLine {
+        line: usize,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("The archive has no header")]
+    MissingHeader,
+    #[error("Unsupported archive version {0}")]
+    UnsupportedVersion(u32),
+    #[error("The {kind} {id} already exists")]
+    Conflict { kind: ResourceKind, id: String },
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum BuilderError {
     #[error("Missing mandatory property {0}")]
     MissingProperty(String),
+    #[error("Property {0} cannot be combined with a custom http client")]
+    ConflictingProperty(String),
+    #[error(transparent)]
+    HttpClient(#[from] reqwest::Error),
 }
 
 impl BuilderError {
     pub fn missing_property(property: &str) -> BuilderError {
         BuilderError::MissingProperty(property.to_string())
     }
+
+    pub fn conflicting_property(property: &str) -> BuilderError {
+        BuilderError::ConflictingProperty(property.to_string())
+    }
 }
 
 #[derive(Debug, thiserror::Error, PartialEq)]
@@ -32,18 +32,32 @@
 
 pub mod api;
 mod auth;
+#[cfg(feature = "callbacks")]
+pub mod callback;
 mod client;
+pub mod data_plane;
 mod error;
+mod middleware;
+#[cfg(feature = "mock")]
+pub mod mock;
+mod retry;
 
 pub mod types;
+pub use async_trait::async_trait;
 pub use auth::{Auth, OAuth2Config};
 pub use client::{EdcConnectorApiVersion, EdcConnectorClient};
 pub use error::{
-    BuilderError, ConversionError, Error, ManagementApiError, ManagementApiErrorDetail,
-    ManagementApiErrorDetailKind,
+    ApplyError, ArchiveError, BuilderError, ConversionError, DataPlaneError, Error,
+    ManagementApiError, ManagementApiErrorDetail, ManagementApiErrorDetailKind, ManifestError,
+    WaitError, WorkflowError, WorkflowStep,
 };
+pub use middleware::{Middleware, Next};
+pub use retry::{InvalidMultiplier, RetryPolicy, RetryableError};
+#[doc(hidden)]
+pub use serde_json;
 
 pub const EDC_NAMESPACE: &str = "https://w3id.org/edc/v0.0.1/ns/";
+pub const ODRL_NAMESPACE: &str = "http://www.w3.org/ns/odrl/2/";
 pub const DATASPACE_PROTOCOL: &str = "dataspace-protocol-http:2025-1";
 
 pub type EdcResult<T> = Result<T, Error>;
@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use reqwest::{Client, Request, Response};
+
+use crate::{EdcResult, Error};
+
+/// Hook invoked for every request sent to the management api.
+///
+/// Middlewares are chained in registration order, right after the built-in
+/// authentication one. Each middleware can inspect or mutate the [`Request`],
+/// forward it to the rest of the chain with [`Next::run`] and inspect or mutate
+/// the resulting [`Response`].
+///
+/// ```rust
+/// use edc_connector_client::{async_trait, EdcResult, Middleware, Next};
+/// use reqwest::{header::HeaderValue, Request, Response};
+///
+/// struct CorrelationId;
+///
+/// #[async_trait]
+/// impl Middleware for CorrelationId {
+///     async fn handle(&self, mut request: Request, next: Next<'_>) -> EdcResult<Response> {
+///         request
+///             .headers_mut()
+///             .insert("X-Correlation-Id", HeaderValue::from_static("my-id"));
+///         next.run(request).await
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait Middleware: Send + Sync + 'static {
+    async fn handle(&self, request: Request, next: Next<'_>) -> EdcResult<Response>;
+}
+
+/// The remaining part of the middleware chain.
+pub struct Next<'a> {
+    client: &'a Client,
+    middlewares: &'a [Arc<dyn Middleware>],
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(client: &'a Client, middlewares: &'a [Arc<dyn Middleware>]) -> Next<'a> {
+        Next {
+            client,
+            middlewares,
+        }
+    }
+
+    pub async fn run(mut self, request: Request) -> EdcResult<Response> {
+        if let Some((current, rest)) = self.middlewares.split_first() {
+            self.middlewares = rest;
+            current.handle(request, self).await
+        } else {
+            self.client.execute(request).await.map_err(Error::from)
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! In-process fake of the management API for tests, without a running connector.
+//!
+//! Entities are kept in memory, negotiations and transfers move through their
+//! states on their own and the catalog is derived from the stored assets,
+//! policies and contract definitions.
+//!
+//! ```rust,no_run
+//! use edc_connector_client::{
+//!     mock::MockConnector,
+//!     types::{asset::NewAsset, data_address::DataAddress},
+//! };
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let connector = MockConnector::start().await?;
+//!     let client = connector.client()?;
+//!
+//!     let asset = NewAsset::builder()
+//!         .id("asset")
+//!         .data_address(DataAddress::builder().kind("HttpData").build()?)
+//!         .build();
+//!     client.assets().create(&asset).await?;
+//!
+//!     Ok(())
+//! }
+//! ```
+
+mod query;
+mod routes;
+mod store;
+
+use std::sync::{Arc, Mutex, PoisonError};
+
+use bon::Builder;
+use tokio::{net::TcpListener, sync::oneshot};
+
+use crate::{
+    types::{
+        contract_negotiation::ContractNegotiationState, transfer_process::TransferProcessState,
+    },
+    BuilderError, EdcConnectorClient,
+};
+
+use store::Store;
+
+/// How negotiations and transfers advance after being initiated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Progression {
+    /// Negotiations are finalized and transfers started right away.
+    Immediate,
+    /// Every read moves the process one state further, so that waiting on a
+    /// state goes through the intermediate ones.
+    #[default]
+    Stepwise,
+}
+
+#[derive(Builder, Clone, Debug)]
+pub struct MockOptions {
+    #[builder(into, default = "consumer")]
+    participant_id: String,
+    #[builder(default)]
+    progression: Progression,
+}
+
+impl Default for MockOptions {
+    fn default() -> Self {
+        MockOptions::builder().build()
+    }
+}
+
+/// A running mock connector, shut down on drop.
+pub struct MockConnector {
+    url: String,
+    store: Arc<Mutex<Store>>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockConnector {
+    pub async fn start() -> std::io::Result<MockConnector> {
+        Self::start_with_opts(MockOptions::default()).await
+    }
+
+    pub async fn start_with_opts(options: MockOptions) -> std::io::Result<MockConnector> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://{}", listener.local_addr()?);
+
+        let mut store = Store::new(options.participant_id, options.progression);
+        store.set_public_url(format!("{url}/public"));
+        let store = Arc::new(Mutex::new(store));
+
+        let (shutdown, signal) = oneshot::channel::<()>();
+        let router = routes::router(store.clone());
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, router)
+                .with_graceful_shutdown(async {
+                    let _ = signal.await;
+                })
+                .await;
+        });
+
+        Ok(MockConnector {
+            url,
+            store,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    /// The management url to configure clients with.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn client(&self) -> Result<EdcConnectorClient, BuilderError> {
+        EdcConnectorClient::builder()
+            .management_url(self.url.clone())
+            .build()
+    }
+
+    /// Forces the state of a negotiation, returning `false` when it does not exist.
+    ///
+    /// Setting `FINALIZED` creates the contract agreement.
+    pub fn set_negotiation_state(&self, id: &str, state: ContractNegotiationState) -> bool {
+        self.store
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .set_negotiation_state(id, state.as_str())
+            .is_ok()
+    }
+
+    /// Forces the state of a transfer process, returning `false` when it does not exist.
+    ///
+    /// Setting `STARTED` on a `*-PULL` transfer creates its EDR.
+    pub fn set_transfer_state(&self, id: &str, state: TransferProcessState) -> bool {
+        self.store
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .set_transfer_state(id, state.as_str())
+            .is_ok()
+    }
+}
+
+impl Drop for MockConnector {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
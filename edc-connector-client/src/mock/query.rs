@@ -0,0 +1,109 @@
+use std::cmp::Ordering;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::types::query::matching::{self, resolve};
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct QuerySpec {
+    offset: Option<usize>,
+    limit: Option<usize>,
+    filter_expression: Vec<Criterion>,
+    sort_field: Option<String>,
+    sort_order: Option<String>,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Criterion {
+    operand_left: String,
+    operator: String,
+    #[serde(default)]
+    operand_right: Value,
+}
+
+impl QuerySpec {
+    pub(crate) fn from_body(body: &Value) -> QuerySpec {
+        serde_json::from_value(body.clone()).unwrap_or_default()
+    }
+
+    pub(crate) fn apply<'a>(&self, entities: impl Iterator<Item = &'a Value>) -> Vec<Value> {
+        let mut matching = entities
+            .filter(|entity| matches_all(&self.filter_expression, entity))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if let Some(field) = &self.sort_field {
+            matching.sort_by(|a, b| sort_order(resolve(a, field), resolve(b, field)));
+            if self.sort_order.as_deref() == Some("DESC") {
+                matching.reverse();
+            }
+        }
+
+        matching
+            .into_iter()
+            .skip(self.offset.unwrap_or(0))
+            .take(self.limit.filter(|limit| *limit > 0).unwrap_or(50))
+            .collect()
+    }
+}
+
+pub(crate) fn criteria_from(value: Option<&Value>) -> Vec<Criterion> {
+    match value {
+        Some(Value::Array(criteria)) => criteria
+            .iter()
+            .filter_map(|c| serde_json::from_value(c.clone()).ok())
+            .collect(),
+        Some(criterion @ Value::Object(_)) => serde_json::from_value(criterion.clone())
+            .map(|c| vec![c])
+            .unwrap_or_default(),
+        _ => vec![],
+    }
+}
+
+pub(crate) fn matches_all(criteria: &[Criterion], entity: &Value) -> bool {
+    criteria.iter().all(|criterion| criterion.matches(entity))
+}
+
+impl Criterion {
+    fn matches(&self, entity: &Value) -> bool {
+        matching::matches(
+            entity,
+            &self.operand_left,
+            &self.operator,
+            &self.operand_right,
+        )
+    }
+}
+
+/// Total order used for sorting: missing values first, then values ranked by
+/// type, then by value within the same type.
+fn sort_order(left: Option<&Value>, right: Option<&Value>) -> Ordering {
+    match (left, right) {
+        (Some(l), Some(r)) => type_rank(l).cmp(&type_rank(r)).then_with(|| match (l, r) {
+            (Value::Bool(l), Value::Bool(r)) => l.cmp(r),
+            (Value::Number(l), Value::Number(r)) => l
+                .as_f64()
+                .unwrap_or_default()
+                .total_cmp(&r.as_f64().unwrap_or_default()),
+            (Value::String(l), Value::String(r)) => l.cmp(r),
+            _ => Ordering::Equal,
+        }),
+        (Some(_), None) => Ordering::Greater,
+        (None, Some(_)) => Ordering::Less,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+fn type_rank(value: &Value) -> u8 {
+    match value {
+        Value::Null => 0,
+        Value::Bool(_) => 1,
+        Value::Number(_) => 2,
+        Value::String(_) => 3,
+        Value::Array(_) => 4,
+        Value::Object(_) => 5,
+    }
+}
@@ -0,0 +1,362 @@
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::EDC_NAMESPACE;
+
+use super::store::{Collection, Store, StoreError, StoreResult};
+
+type SharedStore = Arc<Mutex<Store>>;
+
+#[derive(Deserialize)]
+struct IdPath {
+    id: String,
+}
+
+pub(crate) fn router(store: SharedStore) -> Router {
+    let api = Router::new()
+        .route("/assets", post(create_asset).put(update_asset))
+        .route("/assets/request", post(query_assets))
+        .route("/assets/{id}", get(get_asset).delete(delete_asset))
+        .route("/policydefinitions", post(create_policy))
+        .route("/policydefinitions/request", post(query_policies))
+        .route(
+            "/policydefinitions/{id}",
+            get(get_policy).put(update_policy).delete(delete_policy),
+        )
+        .route(
+            "/contractdefinitions",
+            post(create_contract_definition).put(update_contract_definition),
+        )
+        .route(
+            "/contractdefinitions/request",
+            post(query_contract_definitions),
+        )
+        .route(
+            "/contractdefinitions/{id}",
+            get(get_contract_definition).delete(delete_contract_definition),
+        )
+        .route("/secrets", post(create_secret).put(update_secret))
+        .route("/secrets/request", post(query_secrets))
+        .route("/secrets/{id}", get(get_secret).delete(delete_secret))
+        .route("/contractnegotiations", post(initiate_negotiation))
+        .route("/contractnegotiations/request", post(query_negotiations))
+        .route("/contractnegotiations/{id}", get(get_negotiation))
+        .route(
+            "/contractnegotiations/{id}/state",
+            get(get_negotiation_state),
+        )
+        .route(
+            "/contractnegotiations/{id}/agreement",
+            get(get_negotiation_agreement),
+        )
+        .route(
+            "/contractnegotiations/{id}/terminate",
+            post(terminate_negotiation),
+        )
+        .route("/contractagreements/request", post(query_agreements))
+        .route("/contractagreements/{id}", get(get_agreement))
+        .route(
+            "/contractagreements/{id}/negotiation",
+            get(get_agreement_negotiation),
+        )
+        .route("/transferprocesses", post(initiate_transfer))
+        .route("/transferprocesses/request", post(query_transfers))
+        .route("/transferprocesses/{id}", get(get_transfer))
+        .route("/transferprocesses/{id}/state", get(get_transfer_state))
+        .route(
+            "/transferprocesses/{id}/terminate",
+            post(terminate_transfer),
+        )
+        .route("/transferprocesses/{id}/suspend", post(suspend_transfer))
+        .route("/transferprocesses/{id}/resume", post(resume_transfer))
+        .route("/edrs/request", post(query_edrs))
+        .route("/edrs/{id}", axum::routing::delete(delete_edr))
+        .route("/edrs/{id}/dataaddress", get(get_data_address))
+        .route("/edrs/{id}/refresh", post(refresh_data_address))
+        .route("/catalog/request", post(request_catalog))
+        .route("/catalog/dataset/request", post(request_dataset));
+
+    Router::new()
+        .nest("/v4alpha/participants/{participant}", api.clone())
+        .nest("/{version}", api)
+        .with_state(store)
+}
+
+fn lock(store: &SharedStore) -> MutexGuard<'_, Store> {
+    store.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn body(bytes: &Bytes) -> Value {
+    serde_json::from_slice(bytes).unwrap_or(Value::Null)
+}
+
+fn context() -> Value {
+    json!({
+        "@vocab": EDC_NAMESPACE,
+        "edc": EDC_NAMESPACE,
+        "odrl": "http://www.w3.org/ns/odrl/2/",
+        "dcat": "http://www.w3.org/ns/dcat#",
+        "dct": "http://purl.org/dc/terms/",
+        "dspace": "https://w3id.org/dspace/v0.8/"
+    })
+}
+
+fn with_context(mut value: Value) -> Value {
+    match &mut value {
+        Value::Object(entity) => {
+            entity.insert("@context".to_string(), context());
+        }
+        Value::Array(entities) => {
+            for entity in entities.iter_mut() {
+                if let Value::Object(entity) = entity {
+                    entity.insert("@context".to_string(), context());
+                }
+            }
+        }
+        _ => {}
+    }
+    value
+}
+
+fn error(status: StatusCode, kind: &str, message: String) -> Response {
+    let detail = json!([{ "message": message, "type": kind, "path": null, "invalidValue": null }]);
+    (status, Json(detail)).into_response()
+}
+
+fn respond(result: StoreResult<Value>) -> Response {
+    match result {
+        Ok(value) => Json(with_context(value)).into_response(),
+        Err(err) => error_response(err),
+    }
+}
+
+fn respond_empty(result: StoreResult<()>) -> Response {
+    match result {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(err) => error_response(err),
+    }
+}
+
+fn error_response(err: StoreError) -> Response {
+    match err {
+        StoreError::NotFound(kind, id) => error(
+            StatusCode::NOT_FOUND,
+            "ObjectNotFound",
+            format!("Object of type {kind} with ID={id} was not found"),
+        ),
+        StoreError::Conflict(kind, id) => error(
+            StatusCode::CONFLICT,
+            "ObjectConflict",
+            format!("Object of type {kind} with ID={id} already exists"),
+        ),
+        StoreError::BadRequest(message) => {
+            error(StatusCode::BAD_REQUEST, "InvalidRequest", message)
+        }
+    }
+}
+
+macro_rules! crud {
+    ($collection:expr, $create:ident, $get:ident, $delete:ident, $query:ident) => {
+        async fn $create(State(store): State<SharedStore>, bytes: Bytes) -> Response {
+            respond(lock(&store).create($collection, body(&bytes)))
+        }
+
+        async fn $get(State(store): State<SharedStore>, Path(path): Path<IdPath>) -> Response {
+            respond(lock(&store).get($collection, &path.id))
+        }
+
+        async fn $delete(State(store): State<SharedStore>, Path(path): Path<IdPath>) -> Response {
+            respond_empty(lock(&store).delete($collection, &path.id))
+        }
+
+        async fn $query(State(store): State<SharedStore>, bytes: Bytes) -> Response {
+            respond(Ok(Value::Array(
+                lock(&store).query($collection, &body(&bytes)),
+            )))
+        }
+    };
+}
+
+crud!(
+    Collection::Assets,
+    create_asset,
+    get_asset,
+    delete_asset,
+    query_assets
+);
+crud!(
+    Collection::Policies,
+    create_policy,
+    get_policy,
+    delete_policy,
+    query_policies
+);
+crud!(
+    Collection::ContractDefinitions,
+    create_contract_definition,
+    get_contract_definition,
+    delete_contract_definition,
+    query_contract_definitions
+);
+crud!(
+    Collection::Secrets,
+    create_secret,
+    get_secret,
+    delete_secret,
+    query_secrets
+);
+
+async fn update_asset(State(store): State<SharedStore>, bytes: Bytes) -> Response {
+    respond_empty(lock(&store).update(Collection::Assets, None, body(&bytes)))
+}
+
+async fn update_policy(
+    State(store): State<SharedStore>,
+    Path(path): Path<IdPath>,
+    bytes: Bytes,
+) -> Response {
+    respond_empty(lock(&store).update(Collection::Policies, Some(&path.id), body(&bytes)))
+}
+
+async fn update_contract_definition(State(store): State<SharedStore>, bytes: Bytes) -> Response {
+    respond_empty(lock(&store).update(Collection::ContractDefinitions, None, body(&bytes)))
+}
+
+async fn update_secret(State(store): State<SharedStore>, bytes: Bytes) -> Response {
+    respond_empty(lock(&store).update(Collection::Secrets, None, body(&bytes)))
+}
+
+async fn initiate_negotiation(State(store): State<SharedStore>, bytes: Bytes) -> Response {
+    respond(lock(&store).initiate_negotiation(body(&bytes)))
+}
+
+async fn query_negotiations(State(store): State<SharedStore>, bytes: Bytes) -> Response {
+    respond(Ok(Value::Array(
+        lock(&store).query(Collection::Negotiations, &body(&bytes)),
+    )))
+}
+
+async fn get_negotiation(State(store): State<SharedStore>, Path(path): Path<IdPath>) -> Response {
+    respond(lock(&store).get(Collection::Negotiations, &path.id))
+}
+
+async fn get_negotiation_state(
+    State(store): State<SharedStore>,
+    Path(path): Path<IdPath>,
+) -> Response {
+    respond(
+        lock(&store).get(Collection::Negotiations, &path.id).map(
+            |negotiation| json!({ "@type": "NegotiationState", "state": negotiation["state"] }),
+        ),
+    )
+}
+
+async fn get_negotiation_agreement(
+    State(store): State<SharedStore>,
+    Path(path): Path<IdPath>,
+) -> Response {
+    respond(lock(&store).negotiation_agreement(&path.id))
+}
+
+async fn terminate_negotiation(
+    State(store): State<SharedStore>,
+    Path(path): Path<IdPath>,
+) -> Response {
+    respond_empty(lock(&store).terminate_negotiation(&path.id))
+}
+
+async fn query_agreements(State(store): State<SharedStore>, bytes: Bytes) -> Response {
+    respond(Ok(Value::Array(
+        lock(&store).query(Collection::Agreements, &body(&bytes)),
+    )))
+}
+
+async fn get_agreement(State(store): State<SharedStore>, Path(path): Path<IdPath>) -> Response {
+    respond(lock(&store).get(Collection::Agreements, &path.id))
+}
+
+async fn get_agreement_negotiation(
+    State(store): State<SharedStore>,
+    Path(path): Path<IdPath>,
+) -> Response {
+    respond(lock(&store).agreement_negotiation(&path.id))
+}
+
+async fn initiate_transfer(State(store): State<SharedStore>, bytes: Bytes) -> Response {
+    respond(lock(&store).initiate_transfer(body(&bytes)))
+}
+
+async fn query_transfers(State(store): State<SharedStore>, bytes: Bytes) -> Response {
+    respond(Ok(Value::Array(
+        lock(&store).query(Collection::Transfers, &body(&bytes)),
+    )))
+}
+
+async fn get_transfer(State(store): State<SharedStore>, Path(path): Path<IdPath>) -> Response {
+    respond(lock(&store).get(Collection::Transfers, &path.id))
+}
+
+async fn get_transfer_state(
+    State(store): State<SharedStore>,
+    Path(path): Path<IdPath>,
+) -> Response {
+    respond(
+        lock(&store)
+            .get(Collection::Transfers, &path.id)
+            .map(|transfer| json!({ "@type": "TransferState", "state": transfer["state"] })),
+    )
+}
+
+async fn terminate_transfer(
+    State(store): State<SharedStore>,
+    Path(path): Path<IdPath>,
+) -> Response {
+    respond_empty(lock(&store).set_transfer_state(&path.id, "TERMINATED"))
+}
+
+async fn suspend_transfer(State(store): State<SharedStore>, Path(path): Path<IdPath>) -> Response {
+    respond_empty(lock(&store).set_transfer_state(&path.id, "SUSPENDED"))
+}
+
+async fn resume_transfer(State(store): State<SharedStore>, Path(path): Path<IdPath>) -> Response {
+    respond_empty(lock(&store).set_transfer_state(&path.id, "STARTED"))
+}
+
+async fn query_edrs(State(store): State<SharedStore>, bytes: Bytes) -> Response {
+    respond(Ok(Value::Array(
+        lock(&store).query(Collection::Edrs, &body(&bytes)),
+    )))
+}
+
+async fn delete_edr(State(store): State<SharedStore>, Path(path): Path<IdPath>) -> Response {
+    respond_empty(lock(&store).delete(Collection::Edrs, &path.id))
+}
+
+async fn get_data_address(State(store): State<SharedStore>, Path(path): Path<IdPath>) -> Response {
+    respond(lock(&store).get_data_address(&path.id))
+}
+
+async fn refresh_data_address(
+    State(store): State<SharedStore>,
+    Path(path): Path<IdPath>,
+) -> Response {
+    respond(lock(&store).refresh_data_address(&path.id))
+}
+
+async fn request_catalog(State(store): State<SharedStore>, bytes: Bytes) -> Response {
+    respond(lock(&store).catalog(&body(&bytes)))
+}
+
+async fn request_dataset(State(store): State<SharedStore>, bytes: Bytes) -> Response {
+    respond(lock(&store).dataset(&body(&bytes)))
+}
@@ -0,0 +1,526 @@
+use std::{
+    collections::BTreeMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde_json::{json, Map, Value};
+
+use super::{
+    query::{criteria_from, matches_all, QuerySpec},
+    Progression,
+};
+
+const NEGOTIATION_STATES: &[&str] = &["REQUESTING", "REQUESTED", "AGREED", "VERIFIED", "FINALIZED"];
+const TRANSFER_STATES: &[&str] = &["REQUESTING", "REQUESTED", "STARTED"];
+const TRANSFER_TYPES: &[&str] = &["HttpData-PULL", "HttpData-PUSH"];
+const DATA_SERVICE_ID: &str = "data-service";
+
+#[derive(Debug)]
+pub(crate) enum StoreError {
+    NotFound(&'static str, String),
+    Conflict(&'static str, String),
+    BadRequest(String),
+}
+
+pub(crate) type StoreResult<T> = Result<T, StoreError>;
+
+#[derive(Clone, Copy)]
+pub(crate) enum Collection {
+    Assets,
+    Policies,
+    ContractDefinitions,
+    Secrets,
+    Negotiations,
+    Agreements,
+    Transfers,
+    Edrs,
+}
+
+impl Collection {
+    fn kind(&self) -> &'static str {
+        match self {
+            Collection::Assets => "Asset",
+            Collection::Policies => "PolicyDefinition",
+            Collection::ContractDefinitions => "ContractDefinition",
+            Collection::Secrets => "Secret",
+            Collection::Negotiations => "ContractNegotiation",
+            Collection::Agreements => "ContractAgreement",
+            Collection::Transfers => "TransferProcess",
+            Collection::Edrs => "EndpointDataReferenceEntry",
+        }
+    }
+}
+
+pub(crate) struct Store {
+    participant_id: String,
+    progression: Progression,
+    public_url: String,
+    assets: BTreeMap<String, Value>,
+    policies: BTreeMap<String, Value>,
+    contract_definitions: BTreeMap<String, Value>,
+    secrets: BTreeMap<String, Value>,
+    negotiations: BTreeMap<String, Value>,
+    agreements: BTreeMap<String, Value>,
+    transfers: BTreeMap<String, Value>,
+    edrs: BTreeMap<String, Value>,
+    data_addresses: BTreeMap<String, Value>,
+}
+
+impl Store {
+    pub(crate) fn new(participant_id: String, progression: Progression) -> Store {
+        Store {
+            participant_id,
+            progression,
+            public_url: String::new(),
+            assets: BTreeMap::new(),
+            policies: BTreeMap::new(),
+            contract_definitions: BTreeMap::new(),
+            secrets: BTreeMap::new(),
+            negotiations: BTreeMap::new(),
+            agreements: BTreeMap::new(),
+            transfers: BTreeMap::new(),
+            edrs: BTreeMap::new(),
+            data_addresses: BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn set_public_url(&mut self, public_url: String) {
+        self.public_url = public_url;
+    }
+
+    fn collection(&mut self, collection: Collection) -> &mut BTreeMap<String, Value> {
+        match collection {
+            Collection::Assets => &mut self.assets,
+            Collection::Policies => &mut self.policies,
+            Collection::ContractDefinitions => &mut self.contract_definitions,
+            Collection::Secrets => &mut self.secrets,
+            Collection::Negotiations => &mut self.negotiations,
+            Collection::Agreements => &mut self.agreements,
+            Collection::Transfers => &mut self.transfers,
+            Collection::Edrs => &mut self.edrs,
+        }
+    }
+
+    pub(crate) fn create(&mut self, collection: Collection, body: Value) -> StoreResult<Value> {
+        let mut entity = entity_from(body)?;
+        let id = match entity.get("@id").and_then(Value::as_str) {
+            Some(id) => id.to_string(),
+            None => new_id(),
+        };
+        let created_at = now_millis();
+
+        entity.insert("@id".to_string(), json!(id));
+        entity.insert("createdAt".to_string(), json!(created_at));
+
+        let entities = self.collection(collection);
+        if entities.contains_key(&id) {
+            return Err(StoreError::Conflict(collection.kind(), id));
+        }
+        entities.insert(id.clone(), Value::Object(entity));
+
+        Ok(id_response(&id, created_at))
+    }
+
+    pub(crate) fn get(&mut self, collection: Collection, id: &str) -> StoreResult<Value> {
+        match collection {
+            Collection::Negotiations => self.advance_negotiation(id),
+            Collection::Transfers => self.advance_transfer(id),
+            _ => {}
+        }
+
+        self.collection(collection)
+            .get(id)
+            .cloned()
+            .ok_or_else(|| StoreError::NotFound(collection.kind(), id.to_string()))
+    }
+
+    /// Replaces an entity, taking the id from the body unless given.
+    pub(crate) fn update(
+        &mut self,
+        collection: Collection,
+        id: Option<&str>,
+        body: Value,
+    ) -> StoreResult<()> {
+        let mut entity = entity_from(body)?;
+        let id = match id {
+            Some(id) => id.to_string(),
+            None => entity
+                .get("@id")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .ok_or_else(|| StoreError::BadRequest("Missing @id".to_string()))?,
+        };
+
+        let entities = self.collection(collection);
+        let Some(existing) = entities.get(&id) else {
+            return Err(StoreError::NotFound(collection.kind(), id));
+        };
+
+        entity.insert("@id".to_string(), json!(id));
+        if let Some(created_at) = existing.get("createdAt") {
+            entity.insert("createdAt".to_string(), created_at.clone());
+        }
+        entities.insert(id, Value::Object(entity));
+        Ok(())
+    }
+
+    pub(crate) fn delete(&mut self, collection: Collection, id: &str) -> StoreResult<()> {
+        if let Collection::Edrs = collection {
+            self.data_addresses.remove(id);
+        }
+        self.collection(collection)
+            .remove(id)
+            .map(|_| ())
+            .ok_or_else(|| StoreError::NotFound(collection.kind(), id.to_string()))
+    }
+
+    pub(crate) fn query(&mut self, collection: Collection, body: &Value) -> Vec<Value> {
+        QuerySpec::from_body(body).apply(self.collection(collection).values())
+    }
+
+    pub(crate) fn initiate_negotiation(&mut self, body: Value) -> StoreResult<Value> {
+        let request = entity_from(body)?;
+        let policy = request
+            .get("policy")
+            .cloned()
+            .ok_or_else(|| StoreError::BadRequest("Missing policy".to_string()))?;
+
+        let id = new_id();
+        let created_at = now_millis();
+        let negotiation = json!({
+            "@id": id,
+            "@type": "ContractNegotiation",
+            "type": "CONSUMER",
+            "protocol": request.get("protocol").cloned().unwrap_or(json!("dataspace-protocol-http")),
+            "state": NEGOTIATION_STATES[0],
+            "counterPartyId": request.get("counterPartyId").cloned().unwrap_or(json!("")),
+            "counterPartyAddress": request.get("counterPartyAddress").cloned().unwrap_or(json!("")),
+            "callbackAddresses": request.get("callbackAddresses").cloned().unwrap_or(json!([])),
+            "createdAt": created_at,
+            "policy": policy,
+        });
+
+        self.negotiations.insert(id.clone(), negotiation);
+        if let Progression::Immediate = self.progression {
+            self.set_negotiation_state(&id, "FINALIZED")?;
+        }
+
+        Ok(id_response(&id, created_at))
+    }
+
+    pub(crate) fn set_negotiation_state(&mut self, id: &str, state: &str) -> StoreResult<()> {
+        let negotiation = self
+            .negotiations
+            .get_mut(id)
+            .ok_or_else(|| StoreError::NotFound(Collection::Negotiations.kind(), id.to_string()))?;
+
+        negotiation["state"] = json!(state);
+
+        if state == "FINALIZED" && negotiation.get("contractAgreementId").is_none() {
+            let agreement_id = new_id();
+            negotiation["contractAgreementId"] = json!(agreement_id);
+
+            let mut policy = negotiation["policy"].clone();
+            policy["@type"] = json!("Agreement");
+            policy["assignee"] = json!(self.participant_id);
+
+            let agreement = json!({
+                "@id": agreement_id,
+                "@type": "ContractAgreement",
+                "assetId": target_of(&policy),
+                "consumerId": self.participant_id,
+                "providerId": negotiation["counterPartyId"],
+                "contractSigningDate": now_millis() / 1000,
+                "policy": policy,
+                "negotiationId": id,
+            });
+            self.agreements.insert(agreement_id, agreement);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn terminate_negotiation(&mut self, id: &str) -> StoreResult<()> {
+        self.set_negotiation_state(id, "TERMINATED")
+    }
+
+    pub(crate) fn negotiation_agreement(&mut self, id: &str) -> StoreResult<Value> {
+        let agreement_id = self
+            .get(Collection::Negotiations, id)?
+            .get("contractAgreementId")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| StoreError::NotFound(Collection::Agreements.kind(), id.to_string()))?;
+        self.get(Collection::Agreements, &agreement_id)
+    }
+
+    pub(crate) fn agreement_negotiation(&mut self, id: &str) -> StoreResult<Value> {
+        let negotiation_id = self
+            .get(Collection::Agreements, id)?
+            .get("negotiationId")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| StoreError::NotFound(Collection::Negotiations.kind(), id.to_string()))?;
+        self.get(Collection::Negotiations, &negotiation_id)
+    }
+
+    fn advance_negotiation(&mut self, id: &str) {
+        let Progression::Stepwise = self.progression else {
+            return;
+        };
+        let next = self
+            .negotiations
+            .get(id)
+            .and_then(|negotiation| next_state(NEGOTIATION_STATES, &negotiation["state"]));
+        if let Some(next) = next {
+            let _ = self.set_negotiation_state(id, next);
+        }
+    }
+
+    pub(crate) fn initiate_transfer(&mut self, body: Value) -> StoreResult<Value> {
+        let request = entity_from(body)?;
+        let contract_id = request
+            .get("contractId")
+            .and_then(Value::as_str)
+            .ok_or_else(|| StoreError::BadRequest("Missing contractId".to_string()))?;
+
+        let agreement = self.agreements.get(contract_id).ok_or_else(|| {
+            StoreError::BadRequest(format!("Contract agreement {contract_id} not found"))
+        })?;
+
+        let id = new_id();
+        let created_at = now_millis();
+        let mut transfer = json!({
+            "@id": id,
+            "@type": "TransferProcess",
+            "type": "CONSUMER",
+            "state": TRANSFER_STATES[0],
+            "stateTimestamp": created_at,
+            "assetId": agreement["assetId"],
+            "contractId": contract_id,
+            "transferType": request.get("transferType").cloned().unwrap_or(json!("")),
+            "callbackAddresses": request.get("callbackAddresses").cloned().unwrap_or(json!([])),
+            "correlationId": new_id(),
+            "providerId": agreement["providerId"],
+            "negotiationId": agreement["negotiationId"],
+            "createdAt": created_at,
+        });
+        if let Some(destination) = request.get("dataDestination") {
+            transfer["dataDestination"] = destination.clone();
+        }
+
+        self.transfers.insert(id.clone(), transfer);
+        if let Progression::Immediate = self.progression {
+            self.set_transfer_state(&id, "STARTED")?;
+        }
+
+        Ok(id_response(&id, created_at))
+    }
+
+    pub(crate) fn set_transfer_state(&mut self, id: &str, state: &str) -> StoreResult<()> {
+        let transfer = self
+            .transfers
+            .get_mut(id)
+            .ok_or_else(|| StoreError::NotFound(Collection::Transfers.kind(), id.to_string()))?;
+
+        transfer["state"] = json!(state);
+        transfer["stateTimestamp"] = json!(now_millis());
+
+        let pull = transfer["transferType"]
+            .as_str()
+            .is_some_and(|ty| ty.ends_with("-PULL"));
+
+        if state == "STARTED" && pull && !self.edrs.contains_key(id) {
+            let entry = json!({
+                "@id": id,
+                "@type": "EndpointDataReferenceEntry",
+                "transferProcessId": id,
+                "assetId": transfer["assetId"],
+                "agreementId": transfer["contractId"],
+                "providerId": transfer["providerId"],
+                "contractNegotiationId": transfer["negotiationId"],
+                "createdAt": now_millis(),
+            });
+            self.edrs.insert(id.to_string(), entry);
+            self.data_addresses
+                .insert(id.to_string(), self.data_address());
+        }
+        Ok(())
+    }
+
+    fn advance_transfer(&mut self, id: &str) {
+        let Progression::Stepwise = self.progression else {
+            return;
+        };
+        let next = self
+            .transfers
+            .get(id)
+            .and_then(|transfer| next_state(TRANSFER_STATES, &transfer["state"]));
+        if let Some(next) = next {
+            let _ = self.set_transfer_state(id, next);
+        }
+    }
+
+    pub(crate) fn data_address(&self) -> Value {
+        json!({
+            "@type": "DataAddress",
+            "type": "https://w3id.org/idsa/v4.1/HTTP",
+            "endpointType": "https://w3id.org/idsa/v4.1/HTTP",
+            "endpoint": self.public_url,
+            "authType": "bearer",
+            "authorization": new_id(),
+            "refreshEndpoint": format!("{}/refresh", self.public_url),
+            "refreshToken": new_id(),
+            "expiresIn": "300",
+        })
+    }
+
+    pub(crate) fn get_data_address(&mut self, id: &str) -> StoreResult<Value> {
+        self.data_addresses
+            .get(id)
+            .cloned()
+            .ok_or_else(|| StoreError::NotFound(Collection::Edrs.kind(), id.to_string()))
+    }
+
+    pub(crate) fn refresh_data_address(&mut self, id: &str) -> StoreResult<Value> {
+        if !self.data_addresses.contains_key(id) {
+            return Err(StoreError::NotFound(
+                Collection::Edrs.kind(),
+                id.to_string(),
+            ));
+        }
+        let data_address = self.data_address();
+        self.data_addresses
+            .insert(id.to_string(), data_address.clone());
+        Ok(data_address)
+    }
+
+    /// Datasets of the assets selected by the contract definitions, with the
+    /// contract policies as offers.
+    pub(crate) fn datasets(&self) -> Vec<Value> {
+        let mut datasets = BTreeMap::<String, Value>::new();
+
+        for definition in self.contract_definitions.values() {
+            let Some(policy) = definition["contractPolicyId"]
+                .as_str()
+                .and_then(|id| self.policies.get(id))
+            else {
+                continue;
+            };
+            let criteria = criteria_from(definition.get("assetsSelector"));
+
+            for (asset_id, asset) in &self.assets {
+                if !matches_all(&criteria, asset) {
+                    continue;
+                }
+
+                let mut offer = policy["policy"].clone();
+                offer["@id"] = json!(format!(
+                    "{}:{}:{}",
+                    definition["@id"].as_str().unwrap_or_default(),
+                    asset_id,
+                    new_id()
+                ));
+                offer["@type"] = json!("odrl:Offer");
+
+                let dataset = datasets.entry(asset_id.clone()).or_insert_with(|| {
+                    let mut dataset = asset
+                        .get("properties")
+                        .cloned()
+                        .unwrap_or_else(|| json!({}));
+                    dataset["@id"] = json!(asset_id);
+                    dataset["@type"] = json!("dcat:Dataset");
+                    dataset["odrl:hasPolicy"] = json!([]);
+                    dataset["dcat:distribution"] = TRANSFER_TYPES
+                        .iter()
+                        .map(|format| {
+                            json!({
+                                "@type": "dcat:Distribution",
+                                "dct:format": { "@id": format },
+                                "dcat:accessService": { "@id": DATA_SERVICE_ID }
+                            })
+                        })
+                        .collect();
+                    dataset
+                });
+
+                if let Some(offers) = dataset["odrl:hasPolicy"].as_array_mut() {
+                    offers.push(offer);
+                }
+            }
+        }
+
+        datasets.into_values().collect()
+    }
+
+    /// The catalog of the datasets, served at the requested address.
+    pub(crate) fn catalog(&self, body: &Value) -> StoreResult<Value> {
+        let address = body
+            .get("counterPartyAddress")
+            .and_then(Value::as_str)
+            .filter(|address| address.starts_with("http://") || address.starts_with("https://"))
+            .ok_or_else(|| StoreError::BadRequest("Invalid counterPartyAddress".to_string()))?;
+
+        Ok(json!({
+            "@id": new_id(),
+            "@type": "dcat:Catalog",
+            "participantId": self.participant_id,
+            "dcat:dataService": {
+                "@id": DATA_SERVICE_ID,
+                "@type": "dcat:DataService",
+                "dcat:endpointDescription": "dspace:connector",
+                "dcat:endpointURL": address,
+            },
+            "dcat:dataset": QuerySpec::from_body(&body["querySpec"]).apply(self.datasets().iter()),
+        }))
+    }
+
+    pub(crate) fn dataset(&self, body: &Value) -> StoreResult<Value> {
+        let id = body
+            .get("@id")
+            .and_then(Value::as_str)
+            .ok_or_else(|| StoreError::BadRequest("Missing @id".to_string()))?;
+
+        self.datasets()
+            .into_iter()
+            .find(|dataset| dataset["@id"] == id)
+            .ok_or_else(|| StoreError::NotFound("Dataset", id.to_string()))
+    }
+}
+
+fn entity_from(body: Value) -> StoreResult<Map<String, Value>> {
+    match body {
+        Value::Object(mut entity) => {
+            entity.remove("@context");
+            Ok(entity)
+        }
+        _ => Err(StoreError::BadRequest("Expected a json object".to_string())),
+    }
+}
+
+fn next_state(states: &[&'static str], current: &Value) -> Option<&'static str> {
+    let position = states.iter().position(|state| current == *state)?;
+    states.get(position + 1).copied()
+}
+
+fn target_of(policy: &Value) -> Value {
+    match &policy["target"] {
+        Value::Object(target) => target.get("@id").cloned().unwrap_or(json!("")),
+        Value::Null => json!(""),
+        target => target.clone(),
+    }
+}
+
+fn id_response(id: &str, created_at: u64) -> Value {
+    json!({ "@type": "IdResponse", "@id": id, "createdAt": created_at })
+}
+
+fn new_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,196 @@
+use std::time::Duration;
+
+use bon::Builder;
+use reqwest::StatusCode;
+
+/// Retry policy applied to every request sent to the management api.
+///
+/// Non idempotent requests (e.g. `initiate` or `create`) are only retried when the
+/// connection could not be established, unless `retry_non_idempotent` is enabled.
+#[derive(Debug, Clone, Builder)]
+pub struct RetryPolicy {
+    #[builder(default = 3)]
+    max_attempts: u32,
+    #[builder(default = Duration::from_millis(200))]
+    initial_backoff: Duration,
+    #[builder(default = Duration::from_secs(10))]
+    max_backoff: Duration,
+    /// Factor applied to the backoff after each attempt, rejected when not a
+    /// finite number of at least 1.
+    #[builder(
+        default = 2.0,
+        with = |multiplier: f64| -> Result<_, InvalidMultiplier> {
+            if multiplier.is_finite() && multiplier >= 1.0 {
+                Ok(multiplier)
+            } else {
+                Err(InvalidMultiplier(multiplier))
+            }
+        }
+    )]
+    multiplier: f64,
+    #[builder(default = true)]
+    jitter: bool,
+    #[builder(default = vec![
+        StatusCode::TOO_MANY_REQUESTS,
+        StatusCode::BAD_GATEWAY,
+        StatusCode::SERVICE_UNAVAILABLE,
+        StatusCode::GATEWAY_TIMEOUT,
+    ])]
+    retryable_statuses: Vec<StatusCode>,
+    #[builder(default = vec![
+        RetryableError::Connect,
+        RetryableError::Timeout,
+        RetryableError::Request,
+    ])]
+    retryable_errors: Vec<RetryableError>,
+    #[builder(default = false)]
+    retry_non_idempotent: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, thiserror::Error)]
+#[error("Invalid backoff multiplier {0}, expected a finite number of at least 1")]
+pub struct InvalidMultiplier(pub f64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryableError {
+    Connect,
+    Timeout,
+    Request,
+}
+
+impl RetryableError {
+    fn matches(&self, err: &reqwest::Error) -> bool {
+        match self {
+            RetryableError::Connect => err.is_connect(),
+            RetryableError::Timeout => err.is_timeout(),
+            RetryableError::Request => err.is_request(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that sends every request exactly once.
+    pub fn none() -> RetryPolicy {
+        RetryPolicy::builder().max_attempts(1).build()
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    pub fn retryable_statuses(&self) -> &[StatusCode] {
+        &self.retryable_statuses
+    }
+
+    pub fn retryable_errors(&self) -> &[RetryableError] {
+        &self.retryable_errors
+    }
+
+    pub(crate) fn should_retry_status(&self, status: StatusCode, idempotent: bool) -> bool {
+        (idempotent || self.retry_non_idempotent) && self.retryable_statuses.contains(&status)
+    }
+
+    pub(crate) fn should_retry_error(&self, err: &reqwest::Error, idempotent: bool) -> bool {
+        let retryable = self.retryable_errors.iter().any(|kind| kind.matches(err));
+
+        if err.is_connect() {
+            // The request never reached the connector, so it's safe to retry it.
+            retryable
+        } else {
+            retryable && (idempotent || self.retry_non_idempotent)
+        }
+    }
+
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let exp = self
+            .multiplier
+            .powi(i32::try_from(attempt.saturating_sub(1)).unwrap_or(i32::MAX));
+        let secs = (self.initial_backoff.as_secs_f64() * exp).min(self.max_backoff.as_secs_f64());
+        let backoff = Duration::try_from_secs_f64(secs)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff);
+
+        if self.jitter {
+            let half = backoff / 2;
+            half + half.mul_f64(fastrand::f64())
+        } else {
+            backoff
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::builder().build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::StatusCode;
+
+    use super::RetryPolicy;
+
+    #[test]
+    fn should_compute_exponential_backoff() {
+        let policy = RetryPolicy::builder()
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(500))
+            .jitter(false)
+            .build();
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff(4), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn should_cap_backoff_without_overflowing() {
+        let policy = RetryPolicy::builder()
+            .initial_backoff(Duration::from_secs(1))
+            .max_backoff(Duration::from_secs(30))
+            .multiplier(1e10)
+            .unwrap()
+            .jitter(false)
+            .build();
+
+        assert_eq!(policy.backoff(3), Duration::from_secs(30));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn should_reject_invalid_multipliers() {
+        for multiplier in [0.5, -2.0, f64::NAN, f64::INFINITY] {
+            assert!(RetryPolicy::builder().multiplier(multiplier).is_err());
+        }
+    }
+
+    #[test]
+    fn should_apply_jitter_within_bounds() {
+        let policy = RetryPolicy::builder()
+            .initial_backoff(Duration::from_millis(100))
+            .build();
+
+        for _ in 0..100 {
+            let backoff = policy.backoff(1);
+            assert!(backoff >= Duration::from_millis(50));
+            assert!(backoff <= Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn should_not_retry_non_idempotent_on_status() {
+        let policy = RetryPolicy::default();
+
+        assert!(policy.should_retry_status(StatusCode::SERVICE_UNAVAILABLE, true));
+        assert!(!policy.should_retry_status(StatusCode::SERVICE_UNAVAILABLE, false));
+        assert!(!policy.should_retry_status(StatusCode::NOT_FOUND, true));
+
+        let policy = RetryPolicy::builder().retry_non_idempotent(true).build();
+
+        assert!(policy.should_retry_status(StatusCode::SERVICE_UNAVAILABLE, false));
+    }
+}
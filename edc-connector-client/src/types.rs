@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::DATASPACE_PROTOCOL;
 
+pub mod archive;
 pub mod asset;
 pub mod callback_address;
 pub mod catalog;
@@ -12,6 +13,8 @@ pub mod contract_negotiation;
 pub mod data_address;
 pub mod dataplane;
 pub mod edr;
+pub mod event;
+pub mod manifest;
 pub mod participants;
 pub mod policy;
 pub mod properties;
@@ -0,0 +1,207 @@
+use std::io::{BufRead, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ArchiveError;
+
+use super::{
+    asset::Asset, contract_definition::ContractDefinition, manifest::ResourceKind,
+    policy::PolicyDefinition, secret::Secret,
+};
+
+/// Version of the archive format written by [`Archive::write_to`].
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// A snapshot of the management state of a connector.
+///
+/// It's stored as JSON lines, a header followed by one line per entity:
+///
+/// ```text
+/// {"version":1,"apiVersion":"v3","exportedAt":1718000000000}
+/// {"kind":"asset","entity":{"@id":"asset-1","@type":"Asset",...}}
+/// {"kind":"policyDefinition","entity":{"@id":"policy-1",...}}
+/// ```
+///
+/// The entities are stored without `@context`, which is added on import
+/// according to the api version of the target connector.
+#[derive(Debug, Clone)]
+pub struct Archive {
+    header: ArchiveHeader,
+    entries: Vec<ArchiveEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveHeader {
+    version: u32,
+    api_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    participant_context: Option<String>,
+    exported_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "entity", rename_all = "camelCase")]
+pub enum ArchiveEntry {
+    Secret(Secret),
+    Asset(Asset),
+    PolicyDefinition(PolicyDefinition),
+    ContractDefinition(ContractDefinition),
+}
+
+impl ArchiveHeader {
+    pub(crate) fn new(
+        api_version: &str,
+        participant_context: Option<String>,
+        exported_at: i64,
+    ) -> ArchiveHeader {
+        ArchiveHeader {
+            version: ARCHIVE_VERSION,
+            api_version: api_version.to_string(),
+            participant_context,
+            exported_at,
+        }
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn api_version(&self) -> &str {
+        &self.api_version
+    }
+
+    pub fn participant_context(&self) -> Option<&String> {
+        self.participant_context.as_ref()
+    }
+
+    /// Export time in milliseconds since the epoch.
+    pub fn exported_at(&self) -> i64 {
+        self.exported_at
+    }
+}
+
+impl ArchiveEntry {
+    pub fn kind(&self) -> ResourceKind {
+        match self {
+            ArchiveEntry::Secret(_) => ResourceKind::Secret,
+            ArchiveEntry::Asset(_) => ResourceKind::Asset,
+            ArchiveEntry::PolicyDefinition(_) => ResourceKind::PolicyDefinition,
+            ArchiveEntry::ContractDefinition(_) => ResourceKind::ContractDefinition,
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        match self {
+            ArchiveEntry::Secret(secret) => secret.id(),
+            ArchiveEntry::Asset(asset) => asset.id(),
+            ArchiveEntry::PolicyDefinition(policy_definition) => policy_definition.id(),
+            ArchiveEntry::ContractDefinition(contract_definition) => contract_definition.id(),
+        }
+    }
+}
+
+impl Archive {
+    pub(crate) fn new(header: ArchiveHeader, entries: Vec<ArchiveEntry>) -> Archive {
+        Archive { header, entries }
+    }
+
+    pub fn header(&self) -> &ArchiveHeader {
+        &self.header
+    }
+
+    pub fn entries(&self) -> &[ArchiveEntry] {
+        &self.entries
+    }
+
+    pub fn write_to<W: Write>(&self, mut writer: W) -> Result<(), ArchiveError> {
+        serde_json::to_writer(&mut writer, &self.header).map_err(ArchiveError::Serialize)?;
+        writeln!(writer)?;
+        for entry in &self.entries {
+            serde_json::to_writer(&mut writer, entry).map_err(ArchiveError::Serialize)?;
+            writeln!(writer)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Reads an archive, rejecting the ones written by a newer version of the format.
+    pub fn read_from<R: BufRead>(reader: R) -> Result<Archive, ArchiveError> {
+        let mut lines = reader
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line))
+            .filter(|(_, line)| !matches!(line, Ok(line) if line.trim().is_empty()));
+
+        let (line, header) = lines.next().ok_or(ArchiveError::MissingHeader)?;
+        let header: ArchiveHeader = parse(line, &header?)?;
+        if header.version > ARCHIVE_VERSION {
+            return Err(ArchiveError::UnsupportedVersion(header.version));
+        }
+
+        let entries = lines
+            .map(|(line, content)| parse(line, &content?))
+            .collect::<Result<Vec<ArchiveEntry>, _>>()?;
+
+        Ok(Archive { header, entries })
+    }
+}
+
+fn parse<T: for<'de> Deserialize<'de>>(line: usize, content: &str) -> Result<T, ArchiveError> {
+    serde_json::from_str(content).map_err(|source| ArchiveError::InvalidLine { line, source })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{data_address::DataAddress, secret::Secret};
+
+    use super::*;
+
+    #[test]
+    fn should_round_trip_json_lines() {
+        let archive = Archive::new(
+            ArchiveHeader::new("v3", None, 1),
+            vec![
+                ArchiveEntry::Secret(Secret::builder().id("secret").value("value").build()),
+                ArchiveEntry::Asset(
+                    Asset::builder()
+                        .id("asset")
+                        .property("name", "Asset")
+                        .data_address(DataAddress::builder().kind("HttpData").build().unwrap())
+                        .build(),
+                ),
+            ],
+        );
+
+        let mut content = Vec::new();
+        archive.write_to(&mut content).unwrap();
+        assert_eq!(content.iter().filter(|c| **c == b'\n').count(), 3);
+
+        let read = Archive::read_from(content.as_slice()).unwrap();
+        assert_eq!(read.header().api_version(), "v3");
+        assert_eq!(read.entries().len(), 2);
+        assert_eq!(read.entries()[1].kind(), ResourceKind::Asset);
+        assert_eq!(read.entries()[1].id(), "asset");
+    }
+
+    #[test]
+    fn should_reject_unsupported_archives() {
+        let newer = r#"{"version":99,"apiVersion":"v3","exportedAt":1}"#;
+        assert!(matches!(
+            Archive::read_from(newer.as_bytes()),
+            Err(ArchiveError::UnsupportedVersion(99))
+        ));
+
+        let invalid =
+            "{\"version\":1,\"apiVersion\":\"v3\",\"exportedAt\":1}\n{\"kind\":\"unknown\"}";
+        assert!(matches!(
+            Archive::read_from(invalid.as_bytes()),
+            Err(ArchiveError::InvalidLine { line: 2, .. })
+        ));
+
+        assert!(matches!(
+            Archive::read_from("".as_bytes()),
+            Err(ArchiveError::MissingHeader)
+        ));
+    }
+}
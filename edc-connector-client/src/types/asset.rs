@@ -25,7 +25,7 @@ pub struct Asset {
     data_address: DataAddress,
 }
 
-#[derive(Debug, Serialize, Deserialize, Builder)]
+#[derive(Debug, Serialize, Deserialize, Clone, Builder)]
 #[serde(rename_all = "camelCase")]
 pub struct NewAsset {
     #[builder(field)]
@@ -43,7 +43,23 @@ pub struct NewAsset {
     data_address: DataAddress,
 }
 
-impl NewAsset {}
+impl NewAsset {
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+}
+
+impl From<Asset> for NewAsset {
+    fn from(asset: Asset) -> Self {
+        NewAsset {
+            properties: asset.properties,
+            private_properties: asset.private_properties,
+            id: Some(asset.id),
+            ty: asset.ty,
+            data_address: asset.data_address,
+        }
+    }
+}
 
 impl Asset {
     pub fn property<T>(&self, property: &str) -> Result<Option<T>, ConversionError>
@@ -16,10 +16,14 @@ pub struct CallbackAddress {
     #[builder(into)]
     events: Vec<String>,
     #[builder(default = "CallbackAddress".to_string())]
-    #[serde(rename = "@type")]
+    #[serde(rename = "@type", default = "default_type")]
     ty: String,
 }
 
+fn default_type() -> String {
+    "CallbackAddress".to_string()
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CallbackAddressAuth {
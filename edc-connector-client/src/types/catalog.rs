@@ -1,31 +1,163 @@
 use bon::Builder;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
 use serde_with::{formats::PreferMany, serde_as, OneOrMany};
 
-use super::{policy::Policy, query::Query, Protocol};
+use crate::{ConversionError, EDC_NAMESPACE};
 
-#[serde_as]
-#[derive(Deserialize, Debug)]
+use super::{
+    policy::Policy,
+    properties::{FromValue, Properties, PropertyValue},
+    query::Query,
+    Protocol,
+};
+
+pub mod index;
+
+/// A DCAT catalog, with its datasets and nested catalogs.
+///
+/// Datasets listed with the `Catalog` type, as in catalogs of catalogs, are
+/// returned as nested catalogs. Unknown keys are kept as properties.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(try_from = "RawCatalog")]
 pub struct Catalog {
-    #[serde(rename = "dataset", alias = "dcat:dataset")]
-    #[serde_as(deserialize_as = "OneOrMany<_, PreferMany>")]
+    #[serde(rename = "@id", skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(rename = "@type", skip_serializing_if = "Option::is_none")]
+    ty: Option<String>,
+    #[serde(rename = "participantId", skip_serializing_if = "Option::is_none")]
+    participant_id: Option<String>,
+    #[serde(rename = "dataService", skip_serializing_if = "Vec::is_empty")]
+    data_services: Vec<DataService>,
+    #[serde(rename = "dataset")]
     datasets: Vec<Dataset>,
+    #[serde(rename = "catalog", skip_serializing_if = "Vec::is_empty")]
+    catalogs: Vec<Catalog>,
+    #[serde(flatten)]
+    properties: Properties,
 }
 
 impl Catalog {
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// The id of the participant publishing the catalog.
+    pub fn participant_id(&self) -> Option<&str> {
+        self.participant_id.as_deref()
+    }
+
+    pub fn data_services(&self) -> &[DataService] {
+        &self.data_services
+    }
+
     pub fn datasets(&self) -> &[Dataset] {
         &self.datasets
     }
+
+    pub fn catalogs(&self) -> &[Catalog] {
+        &self.catalogs
+    }
+
+    pub fn properties(&self) -> &Properties {
+        &self.properties
+    }
+
+    pub fn property<T>(&self, property: &str) -> Result<Option<T>, ConversionError>
+    where
+        T: FromValue,
+    {
+        property_of(&self.properties, property)
+    }
+
+    /// The datasets of the catalog followed by those of its nested catalogs,
+    /// depth first.
+    pub fn into_datasets(self) -> Vec<Dataset> {
+        let mut datasets = self.datasets;
+        for catalog in self.catalogs {
+            datasets.extend(catalog.into_datasets());
+        }
+        datasets
+    }
+
+    pub(crate) fn into_parts(self) -> (Vec<Dataset>, Vec<Catalog>) {
+        (self.datasets, self.catalogs)
+    }
+}
+
+#[serde_as]
+#[derive(Deserialize)]
+struct RawCatalog {
+    #[serde(rename = "@id")]
+    id: Option<String>,
+    #[serde(rename = "@type")]
+    ty: Option<String>,
+    #[serde(
+        rename = "participantId",
+        alias = "dspace:participantId",
+        alias = "edc:participantId",
+        alias = "https://w3id.org/dspace/v0.8/participantId"
+    )]
+    participant_id: Option<String>,
+    #[serde(rename = "dataService", alias = "dcat:dataService", default)]
+    #[serde_as(deserialize_as = "OneOrMany<_, PreferMany>")]
+    data_services: Vec<DataService>,
+    #[serde(rename = "dataset", alias = "dcat:dataset", default)]
+    #[serde(deserialize_with = "one_or_many_values")]
+    datasets: Vec<Value>,
+    #[serde(rename = "catalog", alias = "dcat:catalog", default)]
+    #[serde_as(deserialize_as = "OneOrMany<_, PreferMany>")]
+    catalogs: Vec<Catalog>,
+    #[serde(flatten)]
+    properties: Properties,
+}
+
+impl TryFrom<RawCatalog> for Catalog {
+    type Error = serde_json::Error;
+
+    fn try_from(raw: RawCatalog) -> Result<Self, Self::Error> {
+        let mut datasets = Vec::new();
+        let mut catalogs = raw.catalogs;
+        for dataset in raw.datasets {
+            let is_catalog = dataset
+                .get("@type")
+                .and_then(Value::as_str)
+                .is_some_and(|ty| ty.ends_with("Catalog"));
+            if is_catalog {
+                catalogs.push(serde_json::from_value(dataset)?);
+            } else {
+                datasets.push(serde_json::from_value(dataset)?);
+            }
+        }
+
+        Ok(Catalog {
+            id: raw.id,
+            ty: raw.ty,
+            participant_id: raw.participant_id,
+            data_services: raw.data_services,
+            datasets,
+            catalogs,
+            properties: raw.properties,
+        })
+    }
 }
 
 #[serde_as]
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Dataset {
     #[serde(rename = "@id")]
     id: String,
+    #[serde(rename = "@type", skip_serializing_if = "Option::is_none")]
+    ty: Option<String>,
     #[serde(rename = "hasPolicy", alias = "odrl:hasPolicy")]
     #[serde_as(deserialize_as = "OneOrMany<_, PreferMany>")]
     offers: Vec<Policy>,
+    #[serde(rename = "distribution", alias = "dcat:distribution", default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde_as(deserialize_as = "OneOrMany<_, PreferMany>")]
+    distributions: Vec<Distribution>,
+    #[serde(flatten)]
+    properties: Properties,
 }
 
 impl Dataset {
@@ -36,9 +168,179 @@ impl Dataset {
     pub fn offers(&self) -> &[Policy] {
         &self.offers
     }
+
+    pub fn distributions(&self) -> &[Distribution] {
+        &self.distributions
+    }
+
+    /// The formats of the distributions, which are the transfer types
+    /// supported by the provider, like `HttpData-PULL`.
+    pub fn formats(&self) -> Vec<&str> {
+        self.distributions
+            .iter()
+            .filter_map(Distribution::format)
+            .collect()
+    }
+
+    /// The first of the preferred transfer types offered by a distribution.
+    pub fn transfer_type<'a>(&self, preferred: &[&'a str]) -> Option<&'a str> {
+        let formats = self.formats();
+        preferred
+            .iter()
+            .find(|transfer_type| formats.contains(transfer_type))
+            .copied()
+    }
+
+    pub fn properties(&self) -> &Properties {
+        &self.properties
+    }
+
+    /// Reads a property by its name, also when expanded with the EDC
+    /// namespace or prefixed with `edc:`.
+    pub fn property<T>(&self, property: &str) -> Result<Option<T>, ConversionError>
+    where
+        T: FromValue,
+    {
+        property_of(&self.properties, property)
+    }
+
+    pub fn raw_property(&self, property: &str) -> Option<&PropertyValue> {
+        raw_property_of(&self.properties, property)
+    }
+
+    pub fn name(&self) -> Option<String> {
+        self.property("name").ok().flatten()
+    }
+
+    pub fn description(&self) -> Option<String> {
+        self.property("description").ok().flatten()
+    }
+
+    pub fn content_type(&self) -> Option<String> {
+        self.property("contenttype").ok().flatten()
+    }
 }
 
-#[derive(Serialize, Builder)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Distribution {
+    #[serde(rename = "format", alias = "dct:format", default)]
+    #[serde(deserialize_with = "id_or_string")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<String>,
+    #[serde(rename = "accessService", alias = "dcat:accessService", default)]
+    #[serde(deserialize_with = "data_service_or_id")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    access_service: Option<DataService>,
+}
+
+impl Distribution {
+    pub fn format(&self) -> Option<&str> {
+        self.format.as_deref()
+    }
+
+    /// The service of the distribution, possibly only with its id when
+    /// described in the catalog.
+    pub fn access_service(&self) -> Option<&DataService> {
+        self.access_service.as_ref()
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct DataService {
+    #[serde(rename = "@id", skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(
+        rename = "endpointURL",
+        alias = "dcat:endpointURL",
+        alias = "endpointUrl",
+        alias = "dcat:endpointUrl"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    endpoint_url: Option<String>,
+    #[serde(rename = "endpointDescription", alias = "dcat:endpointDescription")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    endpoint_description: Option<String>,
+}
+
+impl DataService {
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    pub fn endpoint_url(&self) -> Option<&str> {
+        self.endpoint_url.as_deref()
+    }
+
+    pub fn endpoint_description(&self) -> Option<&str> {
+        self.endpoint_description.as_deref()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum IdOrString {
+    String(String),
+    Id {
+        #[serde(rename = "@id")]
+        id: String,
+    },
+}
+
+fn id_or_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    Ok(
+        Option::<IdOrString>::deserialize(deserializer)?.map(|value| match value {
+            IdOrString::String(value) | IdOrString::Id { id: value } => value,
+        }),
+    )
+}
+
+fn data_service_or_id<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<DataService>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum DataServiceOrId {
+        Id(String),
+        DataService(DataService),
+    }
+
+    Ok(
+        Option::<DataServiceOrId>::deserialize(deserializer)?.map(|value| match value {
+            DataServiceOrId::Id(id) => DataService {
+                id: Some(id),
+                ..Default::default()
+            },
+            DataServiceOrId::DataService(data_service) => data_service,
+        }),
+    )
+}
+
+/// Datasets are kept as JSON until their type is known, and a JSON value
+/// would otherwise take the whole list as one.
+fn one_or_many_values<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Value>, D::Error> {
+    Ok(match Value::deserialize(deserializer)? {
+        Value::Array(values) => values,
+        value => vec![value],
+    })
+}
+
+fn raw_property_of<'a>(properties: &'a Properties, property: &str) -> Option<&'a PropertyValue> {
+    properties
+        .get_raw(property)
+        .or_else(|| properties.get_raw(&format!("{EDC_NAMESPACE}{property}")))
+        .or_else(|| properties.get_raw(&format!("edc:{property}")))
+}
+
+fn property_of<T: FromValue>(
+    properties: &Properties,
+    property: &str,
+) -> Result<Option<T>, ConversionError> {
+    raw_property_of(properties, property)
+        .map(PropertyValue::try_from)
+        .transpose()
+}
+
+#[derive(Serialize, Builder, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CatalogRequest {
     #[builder(default = "CatalogRequest".to_string())]
@@ -55,6 +357,27 @@ pub struct CatalogRequest {
     query_spec: Query,
 }
 
+impl CatalogRequest {
+    pub fn counter_party_address(&self) -> &str {
+        &self.counter_party_address
+    }
+
+    pub fn counter_party_id(&self) -> Option<&str> {
+        self.counter_party_id.as_deref()
+    }
+
+    pub fn query_spec(&self) -> &Query {
+        &self.query_spec
+    }
+
+    pub(crate) fn with_query_spec(&self, query_spec: Query) -> CatalogRequest {
+        CatalogRequest {
+            query_spec,
+            ..self.clone()
+        }
+    }
+}
+
 #[derive(Serialize, Builder)]
 #[serde(rename_all = "camelCase")]
 pub struct DatasetRequest {
@@ -73,3 +396,108 @@ pub struct DatasetRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     counter_party_id: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn should_deserialize_dcat_catalog() {
+        let catalog: Catalog = serde_json::from_value(json!({
+            "@id": "catalog",
+            "@type": "dcat:Catalog",
+            "dspace:participantId": "provider",
+            "dcat:dataService": {
+                "@id": "service",
+                "@type": "dcat:DataService",
+                "dcat:endpointDescription": "dspace:connector",
+                "dcat:endpointUrl": "http://provider/protocol"
+            },
+            "dcat:dataset": [
+                {
+                    "@id": "asset-1",
+                    "@type": "dcat:Dataset",
+                    "odrl:hasPolicy": { "@id": "offer-1", "@type": "odrl:Offer" },
+                    "dcat:distribution": [
+                        { "dct:format": { "@id": "HttpData-PULL" }, "dcat:accessService": "service" },
+                        {
+                            "dct:format": "AmazonS3-PUSH",
+                            "dcat:accessService": {
+                                "@id": "service",
+                                "dcat:endpointURL": "http://provider/protocol"
+                            }
+                        }
+                    ],
+                    "name": "Asset",
+                    "https://w3id.org/edc/v0.0.1/ns/contenttype": "application/json"
+                },
+                {
+                    "@id": "sub-catalog",
+                    "@type": "dcat:Catalog",
+                    "dcat:dataset": { "@id": "asset-2", "odrl:hasPolicy": [] }
+                }
+            ],
+            "version": "1.0"
+        }))
+        .unwrap();
+
+        assert_eq!(catalog.id(), Some("catalog"));
+        assert_eq!(catalog.participant_id(), Some("provider"));
+        assert_eq!(
+            catalog.data_services()[0].endpoint_url(),
+            Some("http://provider/protocol")
+        );
+        assert_eq!(
+            catalog.property::<String>("version").unwrap().unwrap(),
+            "1.0"
+        );
+
+        let dataset = &catalog.datasets()[0];
+        assert_eq!(dataset.offers().len(), 1);
+        assert_eq!(dataset.name().unwrap(), "Asset");
+        assert_eq!(dataset.content_type().unwrap(), "application/json");
+        assert_eq!(dataset.formats(), vec!["HttpData-PULL", "AmazonS3-PUSH"]);
+        assert_eq!(
+            dataset.transfer_type(&["HttpData-PUSH", "AmazonS3-PUSH"]),
+            Some("AmazonS3-PUSH")
+        );
+        assert_eq!(
+            dataset.distributions()[0].access_service().unwrap().id(),
+            Some("service")
+        );
+
+        assert_eq!(catalog.datasets().len(), 1);
+        assert_eq!(catalog.catalogs()[0].datasets()[0].id(), "asset-2");
+
+        let ids = catalog
+            .into_datasets()
+            .iter()
+            .map(|dataset| dataset.id().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["asset-1", "asset-2"]);
+    }
+
+    #[test]
+    fn should_round_trip_compact_catalog() {
+        let json = json!({
+            "@id": "catalog",
+            "@type": "Catalog",
+            "participantId": "provider",
+            "dataService": [{ "@id": "service", "endpointURL": "http://provider/protocol" }],
+            "dataset": [{
+                "@id": "asset-1",
+                "@type": "Dataset",
+                "hasPolicy": [{ "@id": "offer-1", "@type": "Offer" }],
+                "distribution": [{ "format": "HttpData-PULL", "accessService": { "@id": "service" } }],
+                "description": "An asset"
+            }]
+        });
+
+        let catalog: Catalog = serde_json::from_value(json.clone()).unwrap();
+
+        assert_eq!(catalog.datasets()[0].description().unwrap(), "An asset");
+        assert_eq!(serde_json::to_value(&catalog).unwrap(), json);
+    }
+}
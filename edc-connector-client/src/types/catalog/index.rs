@@ -0,0 +1,521 @@
+//! In-memory index over the datasets of fetched catalogs, for answering
+//! questions like "which providers offer datasets with property X whose
+//! policy allows purpose Y" offline.
+//!
+//! ```rust
+//! use edc_connector_client::types::{
+//!     catalog::index::{CatalogIndex, IndexQuery},
+//!     policy::evaluation::EvaluationContext,
+//! };
+//!
+//! let index = CatalogIndex::default();
+//!
+//! let query = IndexQuery::builder()
+//!     .text("traceability")
+//!     .filter("contenttype", "=", "application/json")
+//!     .permitted_by(
+//!         EvaluationContext::builder()
+//!             .value("purpose", "research")
+//!             .build(),
+//!     )
+//!     .build();
+//!
+//! assert!(index.providers(&query).is_empty());
+//! ```
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use bon::Builder;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::types::{
+    policy::{
+        evaluation::{EvaluationContext, PolicyEvaluator},
+        local_name, Constraint, MultiplicityConstraint, Policy,
+    },
+    properties::ToValue,
+    query::{Criterion, Field, Operator},
+};
+
+use super::{Catalog, Dataset};
+
+const INDEX_VERSION: u32 = 1;
+
+type Key = (String, String);
+
+/// Datasets keyed by provider id and dataset id, with an inverted index over
+/// the words of their id, name and description.
+#[derive(Debug, Clone, Default)]
+pub struct CatalogIndex {
+    entries: BTreeMap<Key, Entry>,
+    terms: BTreeMap<String, BTreeSet<Key>>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    dataset: Dataset,
+    /// The dataset as JSON, which the criteria are evaluated against.
+    value: Value,
+}
+
+/// Filters on the datasets of a [`CatalogIndex`], all of which have to match.
+#[derive(Debug, Clone, Default, Builder)]
+pub struct IndexQuery {
+    #[builder(field)]
+    criteria: Vec<Criterion>,
+    #[builder(field)]
+    policy_filters: Vec<PolicyFilter>,
+    /// Words each matching the beginning of a word of the id, name or
+    /// description, ignoring the case.
+    #[builder(into)]
+    text: Option<String>,
+    #[builder(into)]
+    provider_id: Option<String>,
+}
+
+impl<S: index_query_builder::State> IndexQueryBuilder<S> {
+    /// Filters on a dataset property, with the semantics of [`Criterion`].
+    pub fn filter<T: ToValue>(
+        self,
+        left: impl Into<Field>,
+        operator: impl Into<Operator>,
+        right: T,
+    ) -> Self {
+        self.criterion(Criterion::new(left, operator, right))
+    }
+
+    pub fn criterion(mut self, criterion: Criterion) -> Self {
+        self.criteria.push(criterion);
+        self
+    }
+
+    pub fn policy_filter(mut self, filter: PolicyFilter) -> Self {
+        self.policy_filters.push(filter);
+        self
+    }
+
+    /// Keeps the datasets with an offer permitting the use in the context.
+    pub fn permitted_by(self, context: EvaluationContext) -> Self {
+        self.policy_filter(PolicyFilter::PermittedBy(context))
+    }
+
+    /// Keeps the datasets with an offer constraining the left operand to the
+    /// value.
+    pub fn constraint(self, left_operand: &str, right_operand: impl Into<Value>) -> Self {
+        self.policy_filter(PolicyFilter::Constraint {
+            left_operand: left_operand.to_string(),
+            right_operand: right_operand.into(),
+        })
+    }
+}
+
+/// Filter on the offers of a dataset, matching when any offer matches.
+#[derive(Debug, Clone)]
+pub enum PolicyFilter {
+    /// The offer permits the use when evaluated in the context, an offer
+    /// without constraints permitting everything.
+    PermittedBy(EvaluationContext),
+    /// The offer has a constraint, possibly nested, on the left operand with
+    /// the value as right operand or among it. Left operands are compared
+    /// without namespace.
+    Constraint {
+        left_operand: String,
+        right_operand: Value,
+    },
+}
+
+impl PolicyFilter {
+    fn matches(&self, evaluator: &PolicyEvaluator, offer: &Policy) -> bool {
+        match self {
+            PolicyFilter::PermittedBy(context) => evaluator.evaluate(offer, context).is_permitted(),
+            PolicyFilter::Constraint {
+                left_operand,
+                right_operand,
+            } => offer.permissions().iter().any(|permission| {
+                permission.constraints().iter().any(|constraint| {
+                    constrains(constraint, local_name(left_operand), right_operand)
+                })
+            }),
+        }
+    }
+}
+
+fn constrains(constraint: &Constraint, left_operand: &str, value: &Value) -> bool {
+    match constraint {
+        Constraint::Atomic(atomic) => {
+            local_name(atomic.left_operand.get_id()) == left_operand
+                && match &atomic.right_operand.0 {
+                    Value::Array(values) => values.contains(value),
+                    right_operand => right_operand == value,
+                }
+        }
+        Constraint::MultiplicityConstraint(
+            MultiplicityConstraint::And(constraints)
+            | MultiplicityConstraint::Or(constraints)
+            | MultiplicityConstraint::Xone(constraints),
+        ) => constraints
+            .iter()
+            .any(|constraint| constrains(constraint, left_operand, value)),
+    }
+}
+
+impl CatalogIndex {
+    /// Adds the dataset of the provider, replacing the one with the same id.
+    ///
+    /// Fails, leaving the index untouched, when the dataset can't be
+    /// serialized for evaluating criteria against it.
+    pub fn insert(&mut self, provider_id: &str, dataset: Dataset) -> serde_json::Result<()> {
+        let value = serde_json::to_value(&dataset)?;
+        let key = (provider_id.to_string(), dataset.id().to_string());
+        self.remove(&key.0, &key.1);
+
+        for term in terms_of(&dataset) {
+            self.terms.entry(term).or_default().insert(key.clone());
+        }
+        self.entries.insert(key, Entry { dataset, value });
+        Ok(())
+    }
+
+    /// Adds the datasets of the catalog and of its nested catalogs.
+    pub fn insert_catalog(
+        &mut self,
+        provider_id: &str,
+        catalog: Catalog,
+    ) -> serde_json::Result<()> {
+        for dataset in catalog.into_datasets() {
+            self.insert(provider_id, dataset)?;
+        }
+        Ok(())
+    }
+
+    pub fn remove(&mut self, provider_id: &str, dataset_id: &str) -> Option<Dataset> {
+        let key = (provider_id.to_string(), dataset_id.to_string());
+        let entry = self.entries.remove(&key)?;
+
+        for term in terms_of(&entry.dataset) {
+            if let Some(keys) = self.terms.get_mut(&term) {
+                keys.remove(&key);
+                if keys.is_empty() {
+                    self.terms.remove(&term);
+                }
+            }
+        }
+        Some(entry.dataset)
+    }
+
+    pub fn remove_provider(&mut self, provider_id: &str) {
+        let dataset_ids = self
+            .entries
+            .keys()
+            .filter(|(provider, _)| provider == provider_id)
+            .map(|(_, dataset_id)| dataset_id.clone())
+            .collect::<Vec<_>>();
+
+        for dataset_id in dataset_ids {
+            self.remove(provider_id, &dataset_id);
+        }
+    }
+
+    pub fn get(&self, provider_id: &str, dataset_id: &str) -> Option<&Dataset> {
+        self.entries
+            .get(&(provider_id.to_string(), dataset_id.to_string()))
+            .map(|entry| &entry.dataset)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Every dataset along with its provider id.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Dataset)> {
+        self.entries
+            .iter()
+            .map(|((provider_id, _), entry)| (provider_id.as_str(), &entry.dataset))
+    }
+
+    /// The datasets matching the query along with their provider id, ordered
+    /// by provider id and dataset id.
+    pub fn search(&self, query: &IndexQuery) -> Vec<(&str, &Dataset)> {
+        let evaluator = PolicyEvaluator::default();
+        let candidates = self.text_matches(query.text.as_deref());
+
+        self.entries
+            .iter()
+            .filter(|(key, _)| candidates.as_ref().is_none_or(|keys| keys.contains(key)))
+            .filter(|((provider_id, _), _)| {
+                query
+                    .provider_id
+                    .as_ref()
+                    .is_none_or(|id| id == provider_id)
+            })
+            .filter(|(_, entry)| {
+                query
+                    .criteria
+                    .iter()
+                    .all(|criterion| criterion.matches(&entry.value))
+            })
+            .filter(|(_, entry)| {
+                query.policy_filters.iter().all(|filter| {
+                    entry
+                        .dataset
+                        .offers()
+                        .iter()
+                        .any(|offer| filter.matches(&evaluator, offer))
+                })
+            })
+            .map(|((provider_id, _), entry)| (provider_id.as_str(), &entry.dataset))
+            .collect()
+    }
+
+    /// The ids of the providers with datasets matching the query.
+    pub fn providers(&self, query: &IndexQuery) -> Vec<&str> {
+        self.search(query)
+            .into_iter()
+            .map(|(provider_id, _)| provider_id)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    fn text_matches(&self, text: Option<&str>) -> Option<BTreeSet<&Key>> {
+        let words = tokenize(text?);
+        if words.is_empty() {
+            return None;
+        }
+
+        let mut matches: Option<BTreeSet<&Key>> = None;
+        for word in words {
+            let keys = self
+                .terms
+                .range(word.clone()..)
+                .take_while(|(term, _)| term.starts_with(&word))
+                .flat_map(|(_, keys)| keys)
+                .collect::<BTreeSet<_>>();
+            matches = Some(match matches {
+                Some(matches) => matches.intersection(&keys).copied().collect(),
+                None => keys,
+            });
+        }
+        matches
+    }
+
+    /// Writes the datasets as JSON.
+    pub fn to_writer(&self, writer: impl Write) -> serde_json::Result<()> {
+        let persisted = PersistedIndex {
+            version: INDEX_VERSION,
+            entries: self
+                .entries
+                .iter()
+                .map(|((provider_id, _), entry)| PersistedEntry {
+                    provider_id: provider_id.clone(),
+                    dataset: entry.dataset.clone(),
+                })
+                .collect(),
+        };
+        serde_json::to_writer(writer, &persisted)
+    }
+
+    /// Reads datasets written by [`CatalogIndex::to_writer`], rebuilding the
+    /// index.
+    pub fn from_reader(reader: impl Read) -> serde_json::Result<CatalogIndex> {
+        let persisted: PersistedIndex = serde_json::from_reader(reader)?;
+        if persisted.version != INDEX_VERSION {
+            return Err(serde::de::Error::custom(format!(
+                "unsupported index version {}",
+                persisted.version
+            )));
+        }
+
+        let mut index = CatalogIndex::default();
+        for entry in persisted.entries {
+            index.insert(&entry.provider_id, entry.dataset)?;
+        }
+        Ok(index)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.to_writer(&mut writer)?;
+        writer.flush()
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<CatalogIndex> {
+        Ok(CatalogIndex::from_reader(BufReader::new(File::open(
+            path,
+        )?))?)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PersistedIndex {
+    version: u32,
+    entries: Vec<PersistedEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PersistedEntry {
+    provider_id: String,
+    dataset: Dataset,
+}
+
+fn terms_of(dataset: &Dataset) -> BTreeSet<String> {
+    [
+        Some(dataset.id().to_string()),
+        dataset.name(),
+        dataset.description(),
+    ]
+    .into_iter()
+    .flatten()
+    .flat_map(|text| tokenize(&text))
+    .collect()
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn index() -> CatalogIndex {
+        let dataset = |id: &str, name: &str, purpose: &str, format: &str| -> Dataset {
+            serde_json::from_value(json!({
+                "@id": id,
+                "@type": "dcat:Dataset",
+                "odrl:hasPolicy": {
+                    "@id": format!("{id}-offer"),
+                    "@type": "odrl:Offer",
+                    "odrl:permission": {
+                        "odrl:action": { "@id": "odrl:use" },
+                        "odrl:constraint": {
+                            "odrl:leftOperand": { "@id": "odrl:purpose" },
+                            "odrl:operator": { "@id": "odrl:eq" },
+                            "odrl:rightOperand": purpose
+                        }
+                    }
+                },
+                "name": name,
+                "https://w3id.org/edc/v0.0.1/ns/contenttype": format
+            }))
+            .unwrap()
+        };
+
+        let mut index = CatalogIndex::default();
+        index
+            .insert(
+                "provider-1",
+                dataset("parts", "Part Traceability", "research", "application/json"),
+            )
+            .unwrap();
+        index
+            .insert(
+                "provider-1",
+                dataset("emissions", "CO2 Emissions", "reporting", "text/csv"),
+            )
+            .unwrap();
+        index
+            .insert(
+                "provider-2",
+                dataset(
+                    "batteries",
+                    "Battery Passport",
+                    "research",
+                    "application/json",
+                ),
+            )
+            .unwrap();
+        index
+    }
+
+    #[test]
+    fn should_search_by_text_properties_and_policies() {
+        let index = index();
+
+        let ids = |query: &IndexQuery| {
+            index
+                .search(query)
+                .into_iter()
+                .map(|(provider_id, dataset)| format!("{provider_id}/{}", dataset.id()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            ids(&IndexQuery::builder().text("trace part").build()),
+            ["provider-1/parts"]
+        );
+        assert_eq!(
+            ids(&IndexQuery::builder()
+                .filter("contenttype", "=", "application/json")
+                .build()),
+            ["provider-1/parts", "provider-2/batteries"]
+        );
+        assert_eq!(
+            ids(&IndexQuery::builder()
+                .filter("id", "in", vec!["emissions", "batteries"])
+                .constraint("purpose", "research")
+                .build()),
+            ["provider-2/batteries"]
+        );
+
+        let research = IndexQuery::builder()
+            .permitted_by(
+                EvaluationContext::builder()
+                    .value("purpose", "research")
+                    .build(),
+            )
+            .build();
+        assert_eq!(index.providers(&research), ["provider-1", "provider-2"]);
+        assert_eq!(
+            index.providers(
+                &IndexQuery::builder()
+                    .provider_id("provider-1")
+                    .text("emissions")
+                    .permitted_by(
+                        EvaluationContext::builder()
+                            .value("purpose", "research")
+                            .build()
+                    )
+                    .build()
+            ),
+            Vec::<&str>::new()
+        );
+    }
+
+    #[test]
+    fn should_persist_and_reload() {
+        let mut index = index();
+        index.remove_provider("provider-2");
+
+        let mut buffer = Vec::new();
+        index.to_writer(&mut buffer).unwrap();
+        let reloaded = CatalogIndex::from_reader(buffer.as_slice()).unwrap();
+
+        assert_eq!(reloaded.len(), 2);
+        assert!(reloaded.get("provider-2", "batteries").is_none());
+        assert_eq!(
+            reloaded.search(&IndexQuery::builder().text("co2").build())[0]
+                .1
+                .offers()
+                .len(),
+            1
+        );
+        assert!(CatalogIndex::from_reader(r#"{"version":2,"entries":[]}"#.as_bytes()).is_err());
+    }
+}
@@ -66,7 +66,7 @@ impl<S: contract_definition_builder::State> ContractDefinitionBuilder<S> {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Builder)]
+#[derive(Debug, Serialize, Deserialize, Clone, Builder)]
 #[serde(rename_all = "camelCase")]
 pub struct NewContractDefinition {
     #[builder(field)]
@@ -87,6 +87,25 @@ pub struct NewContractDefinition {
     contract_policy_id: String,
 }
 
+impl NewContractDefinition {
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+}
+
+impl From<ContractDefinition> for NewContractDefinition {
+    fn from(contract_definition: ContractDefinition) -> Self {
+        NewContractDefinition {
+            private_properties: contract_definition.private_properties,
+            assets_selector: contract_definition.assets_selector,
+            id: Some(contract_definition.id),
+            ty: contract_definition.ty,
+            access_policy_id: contract_definition.access_policy_id,
+            contract_policy_id: contract_definition.contract_policy_id,
+        }
+    }
+}
+
 impl<S: new_contract_definition_builder::State> NewContractDefinitionBuilder<S> {
     pub fn private_property<T>(mut self, property: &str, value: T) -> Self
     where
@@ -36,7 +36,7 @@ impl<S: contract_request_builder::State> ContractRequestBuilder<S> {
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ContractNegotiation {
     #[serde(rename = "@id")]
@@ -55,14 +55,14 @@ pub struct ContractNegotiation {
     kind: ContractNegotiationKind,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ContractNegotiationKind {
     Consumer,
     Provider,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ContractNegotiationState {
     Initial,
@@ -84,6 +84,29 @@ pub enum ContractNegotiationState {
     Other(String),
 }
 
+impl ContractNegotiationState {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ContractNegotiationState::Initial => "INITIAL",
+            ContractNegotiationState::Requesting => "REQUESTING",
+            ContractNegotiationState::Requested => "REQUESTED",
+            ContractNegotiationState::Offering => "OFFERING",
+            ContractNegotiationState::Offered => "OFFERED",
+            ContractNegotiationState::Accepting => "ACCEPTING",
+            ContractNegotiationState::Accepted => "ACCEPTED",
+            ContractNegotiationState::Agreeing => "AGREEING",
+            ContractNegotiationState::Agreed => "AGREED",
+            ContractNegotiationState::Verifying => "VERIFYING",
+            ContractNegotiationState::Verified => "VERIFIED",
+            ContractNegotiationState::Finalizing => "FINALIZING",
+            ContractNegotiationState::Finalized => "FINALIZED",
+            ContractNegotiationState::Terminating => "TERMINATING",
+            ContractNegotiationState::Terminated => "TERMINATED",
+            ContractNegotiationState::Other(state) => state,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NegotiationState {
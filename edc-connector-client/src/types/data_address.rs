@@ -1,4 +1,7 @@
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::{error::BuilderError, ConversionError};
 
@@ -23,8 +26,70 @@ impl DataAddress {
     {
         self.properties.get(property)
     }
+
+    pub fn kind(&self) -> Option<&str> {
+        self.str_property("type")
+    }
+
+    /// The data plane endpoint of an EDR.
+    pub fn endpoint(&self) -> Option<&str> {
+        self.str_property("endpoint")
+    }
+
+    /// The token for accessing the data plane endpoint of an EDR.
+    pub fn authorization(&self) -> Option<&str> {
+        self.str_property("authorization")
+    }
+
+    pub fn auth_type(&self) -> Option<&str> {
+        self.str_property("authType")
+    }
+
+    pub fn refresh_endpoint(&self) -> Option<&str> {
+        self.str_property("refreshEndpoint")
+    }
+
+    pub fn refresh_token(&self) -> Option<&str> {
+        self.str_property("refreshToken")
+    }
+
+    pub fn refresh_audience(&self) -> Option<&str> {
+        self.str_property("refreshAudience")
+    }
+
+    /// The validity of the token, sent either as a number or a string of seconds.
+    pub fn expires_in(&self) -> Option<Duration> {
+        match self.raw_property("expiresIn")? {
+            Value::Number(seconds) => seconds.as_u64(),
+            Value::String(seconds) => seconds.parse().ok(),
+            _ => None,
+        }
+        .map(Duration::from_secs)
+    }
+
+    /// Whether the EDR can be refreshed through the connector.
+    pub fn is_refreshable(&self) -> bool {
+        self.refresh_endpoint().is_some()
+    }
+
+    fn str_property(&self, property: &str) -> Option<&str> {
+        self.raw_property(property).and_then(Value::as_str)
+    }
+
+    /// Properties of EDRs issued by Tractus-X connectors may be namespaced.
+    fn raw_property(&self, property: &str) -> Option<&Value> {
+        self.properties
+            .get_raw(property)
+            .or_else(|| {
+                self.properties
+                    .get_raw(&format!("{}{}", TX_AUTH_NAMESPACE, property))
+            })
+            .map(|value| &value.0)
+    }
 }
 
+const TX_AUTH_NAMESPACE: &str = "https://w3id.org/tractusx/auth/";
+
 #[derive(Default)]
 pub struct DataAddressBuilder(Properties);
 
@@ -54,3 +119,52 @@ impl DataAddressBuilder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use super::DataAddress;
+
+    #[test]
+    fn should_read_edr_properties() {
+        let data_address = serde_json::from_value::<DataAddress>(json!({
+            "@type": "DataAddress",
+            "type": "https://w3id.org/idsa/v4.1/HTTP",
+            "endpoint": "http://provider/public",
+            "authType": "bearer",
+            "authorization": "token",
+            "https://w3id.org/tractusx/auth/refreshEndpoint": "http://provider/refresh",
+            "https://w3id.org/tractusx/auth/refreshToken": "refresh",
+            "https://w3id.org/tractusx/auth/expiresIn": "300"
+        }))
+        .unwrap();
+
+        assert_eq!(data_address.endpoint(), Some("http://provider/public"));
+        assert_eq!(data_address.authorization(), Some("token"));
+        assert_eq!(data_address.auth_type(), Some("bearer"));
+        assert_eq!(
+            data_address.refresh_endpoint(),
+            Some("http://provider/refresh")
+        );
+        assert_eq!(data_address.refresh_token(), Some("refresh"));
+        assert_eq!(data_address.expires_in(), Some(Duration::from_secs(300)));
+        assert!(data_address.is_refreshable());
+    }
+
+    #[test]
+    fn should_read_numeric_expiry() {
+        let data_address = serde_json::from_value::<DataAddress>(json!({
+            "@type": "DataAddress",
+            "type": "HttpData",
+            "expiresIn": 60
+        }))
+        .unwrap();
+
+        assert_eq!(data_address.expires_in(), Some(Duration::from_secs(60)));
+        assert!(data_address.endpoint().is_none());
+        assert!(!data_address.is_refreshable());
+    }
+}
@@ -0,0 +1,349 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::callback_address::CallbackAddress;
+
+/// Event delivered by the connector to a registered [`CallbackAddress`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "RawEventEnvelope")]
+pub struct EventEnvelope {
+    id: String,
+    at: i64,
+    event: Event,
+}
+
+impl EventEnvelope {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn at(&self) -> i64 {
+        self.at
+    }
+
+    pub fn event(&self) -> &Event {
+        &self.event
+    }
+
+    pub fn into_event(self) -> Event {
+        self.event
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    ContractNegotiation(ContractNegotiationEvent),
+    TransferProcess(TransferProcessEvent),
+    /// Any event not related to negotiations or transfers, kept as raw json.
+    Other {
+        ty: String,
+        payload: Value,
+    },
+}
+
+impl Event {
+    /// The event type as sent by the connector, e.g. `ContractNegotiationFinalized`.
+    pub fn ty(&self) -> String {
+        match self {
+            Event::ContractNegotiation(event) => {
+                format!("ContractNegotiation{}", event.kind.as_str())
+            }
+            Event::TransferProcess(event) => format!("TransferProcess{}", event.kind.as_str()),
+            Event::Other { ty, .. } => ty.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContractNegotiationEventKind {
+    Initiated,
+    Requested,
+    Offered,
+    Accepted,
+    Agreed,
+    Verified,
+    Finalized,
+    Terminated,
+    Other(String),
+}
+
+impl ContractNegotiationEventKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ContractNegotiationEventKind::Initiated => "Initiated",
+            ContractNegotiationEventKind::Requested => "Requested",
+            ContractNegotiationEventKind::Offered => "Offered",
+            ContractNegotiationEventKind::Accepted => "Accepted",
+            ContractNegotiationEventKind::Agreed => "Agreed",
+            ContractNegotiationEventKind::Verified => "Verified",
+            ContractNegotiationEventKind::Finalized => "Finalized",
+            ContractNegotiationEventKind::Terminated => "Terminated",
+            ContractNegotiationEventKind::Other(kind) => kind,
+        }
+    }
+}
+
+impl From<&str> for ContractNegotiationEventKind {
+    fn from(kind: &str) -> Self {
+        match kind {
+            "Initiated" => ContractNegotiationEventKind::Initiated,
+            "Requested" => ContractNegotiationEventKind::Requested,
+            "Offered" => ContractNegotiationEventKind::Offered,
+            "Accepted" => ContractNegotiationEventKind::Accepted,
+            "Agreed" => ContractNegotiationEventKind::Agreed,
+            "Verified" => ContractNegotiationEventKind::Verified,
+            "Finalized" => ContractNegotiationEventKind::Finalized,
+            "Terminated" => ContractNegotiationEventKind::Terminated,
+            other => ContractNegotiationEventKind::Other(other.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransferProcessEventKind {
+    Initiated,
+    ProvisioningRequested,
+    Provisioned,
+    Requested,
+    Started,
+    Suspended,
+    Completed,
+    Terminated,
+    DeprovisioningRequested,
+    Deprovisioned,
+    Other(String),
+}
+
+impl TransferProcessEventKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            TransferProcessEventKind::Initiated => "Initiated",
+            TransferProcessEventKind::ProvisioningRequested => "ProvisioningRequested",
+            TransferProcessEventKind::Provisioned => "Provisioned",
+            TransferProcessEventKind::Requested => "Requested",
+            TransferProcessEventKind::Started => "Started",
+            TransferProcessEventKind::Suspended => "Suspended",
+            TransferProcessEventKind::Completed => "Completed",
+            TransferProcessEventKind::Terminated => "Terminated",
+            TransferProcessEventKind::DeprovisioningRequested => "DeprovisioningRequested",
+            TransferProcessEventKind::Deprovisioned => "Deprovisioned",
+            TransferProcessEventKind::Other(kind) => kind,
+        }
+    }
+}
+
+impl From<&str> for TransferProcessEventKind {
+    fn from(kind: &str) -> Self {
+        match kind {
+            "Initiated" => TransferProcessEventKind::Initiated,
+            "ProvisioningRequested" => TransferProcessEventKind::ProvisioningRequested,
+            "Provisioned" => TransferProcessEventKind::Provisioned,
+            "Requested" => TransferProcessEventKind::Requested,
+            "Started" => TransferProcessEventKind::Started,
+            "Suspended" => TransferProcessEventKind::Suspended,
+            "Completed" => TransferProcessEventKind::Completed,
+            "Terminated" => TransferProcessEventKind::Terminated,
+            "DeprovisioningRequested" => TransferProcessEventKind::DeprovisioningRequested,
+            "Deprovisioned" => TransferProcessEventKind::Deprovisioned,
+            other => TransferProcessEventKind::Other(other.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractNegotiationEvent {
+    #[serde(skip, default = "default_negotiation_kind")]
+    kind: ContractNegotiationEventKind,
+    contract_negotiation_id: String,
+    counter_party_address: Option<String>,
+    counter_party_id: Option<String>,
+    protocol: Option<String>,
+    #[serde(default)]
+    callback_addresses: Vec<CallbackAddress>,
+    contract_agreement: Option<Value>,
+    last_contract_offer: Option<Value>,
+}
+
+impl ContractNegotiationEvent {
+    pub fn kind(&self) -> &ContractNegotiationEventKind {
+        &self.kind
+    }
+
+    pub fn contract_negotiation_id(&self) -> &str {
+        &self.contract_negotiation_id
+    }
+
+    pub fn counter_party_address(&self) -> Option<&String> {
+        self.counter_party_address.as_ref()
+    }
+
+    pub fn counter_party_id(&self) -> Option<&String> {
+        self.counter_party_id.as_ref()
+    }
+
+    pub fn protocol(&self) -> Option<&String> {
+        self.protocol.as_ref()
+    }
+
+    pub fn callback_addresses(&self) -> &[CallbackAddress] {
+        &self.callback_addresses
+    }
+
+    /// The agreement, only present on `Finalized` events.
+    pub fn contract_agreement(&self) -> Option<&Value> {
+        self.contract_agreement.as_ref()
+    }
+
+    /// The id of the agreement, only present on `Finalized` events.
+    pub fn contract_agreement_id(&self) -> Option<&str> {
+        self.contract_agreement
+            .as_ref()
+            .and_then(|agreement| agreement.get("id"))
+            .and_then(Value::as_str)
+    }
+
+    pub fn last_contract_offer(&self) -> Option<&Value> {
+        self.last_contract_offer.as_ref()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferProcessEvent {
+    #[serde(skip, default = "default_transfer_kind")]
+    kind: TransferProcessEventKind,
+    transfer_process_id: String,
+    asset_id: Option<String>,
+    contract_id: Option<String>,
+    #[serde(rename = "type")]
+    transfer_kind: Option<String>,
+    #[serde(default)]
+    callback_addresses: Vec<CallbackAddress>,
+    data_address: Option<Value>,
+}
+
+impl TransferProcessEvent {
+    pub fn kind(&self) -> &TransferProcessEventKind {
+        &self.kind
+    }
+
+    pub fn transfer_process_id(&self) -> &str {
+        &self.transfer_process_id
+    }
+
+    pub fn asset_id(&self) -> Option<&String> {
+        self.asset_id.as_ref()
+    }
+
+    pub fn contract_id(&self) -> Option<&String> {
+        self.contract_id.as_ref()
+    }
+
+    /// `CONSUMER` or `PROVIDER`.
+    pub fn transfer_kind(&self) -> Option<&String> {
+        self.transfer_kind.as_ref()
+    }
+
+    pub fn callback_addresses(&self) -> &[CallbackAddress] {
+        &self.callback_addresses
+    }
+
+    /// The data address, only present on `Started` events.
+    pub fn data_address(&self) -> Option<&Value> {
+        self.data_address.as_ref()
+    }
+}
+
+fn default_negotiation_kind() -> ContractNegotiationEventKind {
+    ContractNegotiationEventKind::Other(String::new())
+}
+
+fn default_transfer_kind() -> TransferProcessEventKind {
+    TransferProcessEventKind::Other(String::new())
+}
+
+#[derive(Deserialize)]
+struct RawEventEnvelope {
+    id: String,
+    at: i64,
+    #[serde(rename = "type")]
+    ty: String,
+    #[serde(default)]
+    payload: Value,
+}
+
+impl TryFrom<RawEventEnvelope> for EventEnvelope {
+    type Error = serde_json::Error;
+
+    fn try_from(raw: RawEventEnvelope) -> Result<Self, Self::Error> {
+        let event = if let Some(kind) = raw.ty.strip_prefix("ContractNegotiation") {
+            let mut event = serde_json::from_value::<ContractNegotiationEvent>(raw.payload)?;
+            event.kind = kind.into();
+            Event::ContractNegotiation(event)
+        } else if let Some(kind) = raw.ty.strip_prefix("TransferProcess") {
+            let mut event = serde_json::from_value::<TransferProcessEvent>(raw.payload)?;
+            event.kind = kind.into();
+            Event::TransferProcess(event)
+        } else {
+            Event::Other {
+                ty: raw.ty,
+                payload: raw.payload,
+            }
+        };
+
+        Ok(EventEnvelope {
+            id: raw.id,
+            at: raw.at,
+            event,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn should_deserialize_negotiation_finalized() {
+        let envelope = serde_json::from_value::<EventEnvelope>(json!({
+            "id": "event",
+            "at": 1,
+            "type": "ContractNegotiationFinalized",
+            "payload": {
+                "contractNegotiationId": "negotiation",
+                "counterPartyId": "provider",
+                "callbackAddresses": [],
+                "contractAgreement": { "id": "agreement", "assetId": "asset" }
+            }
+        }))
+        .unwrap();
+
+        let Event::ContractNegotiation(event) = envelope.event() else {
+            panic!("expected negotiation event");
+        };
+
+        assert_eq!(event.kind(), &ContractNegotiationEventKind::Finalized);
+        assert_eq!(event.contract_negotiation_id(), "negotiation");
+        assert_eq!(event.contract_agreement_id(), Some("agreement"));
+        assert_eq!(envelope.event().ty(), "ContractNegotiationFinalized");
+    }
+
+    #[test]
+    fn should_keep_unknown_events() {
+        let envelope = serde_json::from_value::<EventEnvelope>(json!({
+            "id": "event",
+            "at": 1,
+            "type": "AssetCreated",
+            "payload": { "assetId": "asset" }
+        }))
+        .unwrap();
+
+        assert!(matches!(
+            envelope.event(),
+            Event::Other { ty, payload } if ty == "AssetCreated" && payload["assetId"] == "asset"
+        ));
+    }
+}
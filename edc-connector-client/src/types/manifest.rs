@@ -0,0 +1,253 @@
+use std::{collections::HashSet, fmt};
+
+use bon::Builder;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::error::ManifestError;
+
+use super::{
+    asset::NewAsset, contract_definition::NewContractDefinition, policy::NewPolicyDefinition,
+    secret::NewSecret,
+};
+
+/// Declarative description of the entities a connector should provide.
+///
+/// The `@type` of the entities can be omitted, and every entity needs an `@id`
+/// so that the manifest can be reconciled with the connector more than once.
+///
+/// ```yaml
+/// secrets:
+///   - "@id": api-key
+///     value: secret
+/// assets:
+///   - "@id": asset-1
+///     properties:
+///       name: Asset 1
+///     dataAddress:
+///       type: HttpData
+///       baseUrl: https://example.com
+/// policyDefinitions:
+///   - "@id": use
+///     policy:
+///       permission:
+///         - action: use
+/// contractDefinitions:
+///   - "@id": asset-1-use
+///     accessPolicyId: use
+///     contractPolicyId: use
+///     assetsSelector:
+///       - operandLeft: https://w3id.org/edc/v0.0.1/ns/id
+///         operator: "="
+///         operandRight: asset-1
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Builder)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    #[builder(field)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    secrets: Vec<NewSecret>,
+    #[builder(field)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    assets: Vec<NewAsset>,
+    #[builder(field)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    policy_definitions: Vec<NewPolicyDefinition>,
+    #[builder(field)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    contract_definitions: Vec<NewContractDefinition>,
+}
+
+/// The kinds of entities managed by a [`Manifest`], in dependency order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ResourceKind {
+    Secret,
+    Asset,
+    PolicyDefinition,
+    ContractDefinition,
+}
+
+impl fmt::Display for ResourceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            ResourceKind::Secret => "secret",
+            ResourceKind::Asset => "asset",
+            ResourceKind::PolicyDefinition => "policy definition",
+            ResourceKind::ContractDefinition => "contract definition",
+        };
+        f.write_str(kind)
+    }
+}
+
+impl Manifest {
+    pub fn from_json(content: &str) -> Result<Manifest, ManifestError> {
+        Manifest::from_value(serde_json::from_str(content)?)
+    }
+
+    /// Parses a YAML manifest, which includes the JSON ones.
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(content: &str) -> Result<Manifest, ManifestError> {
+        Manifest::from_value(serde_yaml::from_str(content)?)
+    }
+
+    pub fn from_value(mut value: Value) -> Result<Manifest, ManifestError> {
+        if let Some(manifest) = value.as_object_mut() {
+            with_default_types(manifest);
+        }
+        let manifest: Manifest = serde_json::from_value(value)?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    pub fn secrets(&self) -> &[NewSecret] {
+        &self.secrets
+    }
+
+    pub fn assets(&self) -> &[NewAsset] {
+        &self.assets
+    }
+
+    pub fn policy_definitions(&self) -> &[NewPolicyDefinition] {
+        &self.policy_definitions
+    }
+
+    pub fn contract_definitions(&self) -> &[NewContractDefinition] {
+        &self.contract_definitions
+    }
+
+    /// Checks that every entity has an unique `@id`.
+    pub fn validate(&self) -> Result<(), ManifestError> {
+        check_ids(ResourceKind::Secret, self.secrets.iter().map(NewSecret::id))?;
+        check_ids(ResourceKind::Asset, self.assets.iter().map(NewAsset::id))?;
+        check_ids(
+            ResourceKind::PolicyDefinition,
+            self.policy_definitions.iter().map(NewPolicyDefinition::id),
+        )?;
+        check_ids(
+            ResourceKind::ContractDefinition,
+            self.contract_definitions
+                .iter()
+                .map(NewContractDefinition::id),
+        )
+    }
+}
+
+impl<S: manifest_builder::State> ManifestBuilder<S> {
+    pub fn secret(mut self, secret: NewSecret) -> Self {
+        self.secrets.push(secret);
+        self
+    }
+
+    pub fn asset(mut self, asset: NewAsset) -> Self {
+        self.assets.push(asset);
+        self
+    }
+
+    pub fn policy_definition(mut self, policy_definition: NewPolicyDefinition) -> Self {
+        self.policy_definitions.push(policy_definition);
+        self
+    }
+
+    pub fn contract_definition(mut self, contract_definition: NewContractDefinition) -> Self {
+        self.contract_definitions.push(contract_definition);
+        self
+    }
+}
+
+fn check_ids<'a>(
+    kind: ResourceKind,
+    ids: impl Iterator<Item = Option<&'a str>>,
+) -> Result<(), ManifestError> {
+    let mut seen = HashSet::new();
+    for id in ids {
+        let id = id.ok_or(ManifestError::MissingId(kind))?;
+        if !seen.insert(id) {
+            return Err(ManifestError::DuplicateId {
+                kind,
+                id: id.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+fn with_default_types(manifest: &mut Map<String, Value>) {
+    let sections = [
+        ("secrets", "Secret"),
+        ("assets", "Asset"),
+        ("policyDefinitions", "PolicyDefinition"),
+        ("contractDefinitions", "ContractDefinition"),
+    ];
+
+    for (section, ty) in sections {
+        let Some(entries) = manifest.get_mut(section).and_then(Value::as_array_mut) else {
+            continue;
+        };
+        for entry in entries.iter_mut().filter_map(Value::as_object_mut) {
+            default_type(entry, ty);
+            if let Some(data_address) = entry.get_mut("dataAddress").and_then(Value::as_object_mut)
+            {
+                default_type(data_address, "DataAddress");
+            }
+            if let Some(policy) = entry.get_mut("policy").and_then(Value::as_object_mut) {
+                default_type(policy, "Set");
+            }
+            if let Some(selector) = entry
+                .get_mut("assetsSelector")
+                .and_then(Value::as_array_mut)
+            {
+                for criterion in selector.iter_mut().filter_map(Value::as_object_mut) {
+                    default_type(criterion, "Criterion");
+                }
+            }
+        }
+    }
+}
+
+fn default_type(entry: &mut Map<String, Value>, ty: &str) {
+    entry
+        .entry("@type")
+        .or_insert_with(|| Value::String(ty.to_string()));
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_manifest_with_default_types() {
+        let manifest = Manifest::from_value(json!({
+            "assets": [{ "@id": "asset", "properties": {}, "dataAddress": { "type": "HttpData" } }],
+            "policyDefinitions": [{ "@id": "policy", "policy": { "permission": [{ "action": "use" }] } }],
+            "contractDefinitions": [{ "@id": "definition", "accessPolicyId": "policy", "contractPolicyId": "policy" }]
+        }))
+        .unwrap();
+
+        assert_eq!(manifest.assets()[0].id(), Some("asset"));
+        assert_eq!(manifest.policy_definitions().len(), 1);
+        assert_eq!(manifest.contract_definitions().len(), 1);
+        assert!(manifest.secrets().is_empty());
+    }
+
+    #[test]
+    fn should_reject_missing_and_duplicated_ids() {
+        let missing = Manifest::from_value(json!({ "secrets": [{ "value": "secret" }] }));
+        assert!(matches!(
+            missing,
+            Err(ManifestError::MissingId(ResourceKind::Secret))
+        ));
+
+        let duplicated = Manifest::from_value(json!({
+            "secrets": [{ "@id": "secret", "value": "a" }, { "@id": "secret", "value": "b" }]
+        }));
+        assert!(matches!(
+            duplicated,
+            Err(ManifestError::DuplicateId {
+                kind: ResourceKind::Secret,
+                ..
+            })
+        ));
+    }
+}
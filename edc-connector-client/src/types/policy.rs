@@ -1,11 +1,18 @@
+pub mod evaluation;
+pub mod matching;
+pub mod normalization;
 mod odrl;
+pub mod render;
+pub mod template;
+pub mod vocabulary;
 
 use bon::Builder;
 use serde::{Deserialize, Serialize};
 use serde_with::{formats::PreferMany, serde_as, OneOrMany};
 
-use crate::ConversionError;
+use crate::{ConversionError, ODRL_NAMESPACE};
 
+use self::vocabulary::ActionKind;
 use super::properties::{FromValue, Properties, PropertyValue, ToValue};
 
 #[derive(Debug, Serialize, Deserialize, Clone, Builder)]
@@ -50,7 +57,7 @@ impl PolicyDefinition {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Builder)]
+#[derive(Debug, Serialize, Deserialize, Clone, Builder)]
 #[serde(rename_all = "camelCase")]
 pub struct NewPolicyDefinition {
     #[builder(field)]
@@ -65,6 +72,23 @@ pub struct NewPolicyDefinition {
     policy: Policy,
 }
 
+impl NewPolicyDefinition {
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+}
+
+impl From<PolicyDefinition> for NewPolicyDefinition {
+    fn from(policy_definition: PolicyDefinition) -> Self {
+        NewPolicyDefinition {
+            private_properties: policy_definition.private_properties,
+            id: Some(policy_definition.id),
+            ty: policy_definition.ty,
+            policy: policy_definition.policy,
+        }
+    }
+}
+
 impl<S: new_policy_definition_builder::State> NewPolicyDefinitionBuilder<S> {
     pub fn private_property<T>(mut self, property: &str, value: T) -> Self
     where
@@ -146,6 +170,16 @@ impl Policy {
     pub fn prohibitions(&self) -> &[Prohibition] {
         &self.prohibitions
     }
+
+    /// Turns a catalog offer into the policy of a [`ContractRequest`](super::contract_negotiation::ContractRequest),
+    /// filling the assigner and the target when missing.
+    pub fn to_contract_offer(&self, assigner: &str, target: &str) -> Policy {
+        let mut offer = self.clone();
+        offer.kind = PolicyKind::Offer;
+        offer.assigner.get_or_insert_with(|| assigner.to_string());
+        offer.target.get_or_insert_with(|| Target::simple(target));
+        offer
+    }
 }
 
 impl<S: policy_builder::State> PolicyBuilder<S> {
@@ -183,14 +217,28 @@ impl<S: policy_builder::State> PolicyBuilder<S> {
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 pub enum PolicyKind {
     #[default]
-    #[serde(alias = "odrl:Set")]
+    #[serde(alias = "odrl:Set", alias = "http://www.w3.org/ns/odrl/2/Set")]
     Set,
-    #[serde(alias = "odrl:Offer")]
+    #[serde(alias = "odrl:Offer", alias = "http://www.w3.org/ns/odrl/2/Offer")]
     Offer,
-    #[serde(alias = "odrl:Agreement")]
+    #[serde(
+        alias = "odrl:Agreement",
+        alias = "http://www.w3.org/ns/odrl/2/Agreement"
+    )]
     Agreement,
 }
 
+impl PolicyKind {
+    pub fn iri(&self) -> String {
+        let name = match self {
+            PolicyKind::Set => "Set",
+            PolicyKind::Offer => "Offer",
+            PolicyKind::Agreement => "Agreement",
+        };
+        format!("{ODRL_NAMESPACE}{name}")
+    }
+}
+
 #[serde_as]
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Builder)]
 pub struct Permission {
@@ -332,7 +380,7 @@ impl Target {
 
 impl Default for Action {
     fn default() -> Self {
-        Action::new("http://www.w3.org/ns/odrl/2/use".to_string())
+        ActionKind::Use.into()
     }
 }
 
@@ -394,6 +442,13 @@ impl LeftOperand {
     pub fn id(op: &str) -> LeftOperand {
         LeftOperand::Id { id: op.to_string() }
     }
+
+    pub fn get_id(&self) -> &str {
+        match self {
+            LeftOperand::Simple(op) => op,
+            LeftOperand::Id { id } => id,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -432,6 +487,13 @@ impl Operator {
     pub fn id(op: &str) -> Operator {
         Operator::Id { id: op.to_string() }
     }
+
+    pub fn get_id(&self) -> &str {
+        match self {
+            Operator::Simple(op) => op,
+            Operator::Id { id } => id,
+        }
+    }
 }
 
 impl AtomicConstraint {
@@ -463,3 +525,12 @@ impl From<&str> for LeftOperand {
         }
     }
 }
+
+/// The name of a term without namespace or prefix.
+pub(crate) fn local_name(term: &str) -> &str {
+    if term.contains("://") {
+        term.rsplit(['/', '#']).next().unwrap_or(term)
+    } else {
+        term.rsplit(':').next().unwrap_or(term)
+    }
+}
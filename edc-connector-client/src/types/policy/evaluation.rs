@@ -0,0 +1,588 @@
+//! Local evaluation of ODRL policies, e.g. to pre-check catalog offers before
+//! negotiating them.
+//!
+//! ```rust
+//! use edc_connector_client::types::policy::{
+//!     evaluation::{EvaluationContext, PolicyEvaluator},
+//!     AtomicConstraint, Constraint, Permission, Policy,
+//! };
+//!
+//! let policy = Policy::builder()
+//!     .permission(
+//!         Permission::builder()
+//!             .constraint(Constraint::atomic(AtomicConstraint::new(
+//!                 "purpose", "eq", "research",
+//!             )))
+//!             .build(),
+//!     )
+//!     .build();
+//!
+//! let context = EvaluationContext::builder().value("purpose", "research").build();
+//! let evaluation = PolicyEvaluator::default().evaluate(&policy, &context);
+//!
+//! assert!(evaluation.is_permitted());
+//! println!("{}", evaluation.explanation());
+//! ```
+
+use std::{cmp::Ordering, collections::HashMap, fmt, sync::Arc};
+
+use bon::Builder;
+use serde_json::Value;
+
+use super::{local_name, Action, AtomicConstraint, Constraint, MultiplicityConstraint, Policy};
+
+/// Custom evaluation of the constraints on a left operand, receiving the
+/// operator without prefix, the right operand and the evaluation context.
+pub type LeftOperandFunction = Arc<dyn Fn(&str, &Value, &EvaluationContext) -> bool + Send + Sync>;
+
+/// The values of the left operands and the action the policy is evaluated for.
+///
+/// Values are looked up by the left operand as written in the policy, then by
+/// its name without namespace, so `purpose` matches `odrl:purpose` as well.
+#[derive(Debug, Clone, Default, Builder)]
+pub struct EvaluationContext {
+    #[builder(field)]
+    values: HashMap<String, Value>,
+    /// Only the rules of this action are evaluated, all of them when missing.
+    #[builder(into)]
+    action: Option<String>,
+}
+
+impl<S: evaluation_context_builder::State> EvaluationContextBuilder<S> {
+    pub fn value(mut self, left_operand: &str, value: impl Into<Value>) -> Self {
+        self.values.insert(left_operand.to_string(), value.into());
+        self
+    }
+}
+
+impl EvaluationContext {
+    pub fn value(&self, left_operand: &str) -> Option<&Value> {
+        self.values.get(left_operand).or_else(|| {
+            let name = local_name(left_operand);
+            self.values
+                .iter()
+                .find(|(key, _)| local_name(key) == name)
+                .map(|(_, value)| value)
+        })
+    }
+
+    pub fn action(&self) -> Option<&String> {
+        self.action.as_ref()
+    }
+}
+
+/// Evaluates policies with the standard ODRL operators and the registered
+/// left operand functions.
+#[derive(Clone, Default, Builder)]
+pub struct PolicyEvaluator {
+    #[builder(field)]
+    functions: HashMap<String, LeftOperandFunction>,
+}
+
+impl<S: policy_evaluator_builder::State> PolicyEvaluatorBuilder<S> {
+    /// Registers a function evaluating the constraints on `left_operand`
+    /// instead of comparing the value of the context.
+    pub fn function(
+        mut self,
+        left_operand: &str,
+        function: impl Fn(&str, &Value, &EvaluationContext) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.functions
+            .insert(local_name(left_operand).to_string(), Arc::new(function));
+        self
+    }
+}
+
+impl fmt::Debug for PolicyEvaluator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PolicyEvaluator")
+            .field("functions", &self.functions.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    Permit,
+    Deny,
+}
+
+#[derive(Debug, Clone)]
+pub struct Evaluation {
+    decision: Decision,
+    explanation: Explanation,
+}
+
+impl Evaluation {
+    pub fn decision(&self) -> Decision {
+        self.decision
+    }
+
+    pub fn is_permitted(&self) -> bool {
+        self.decision == Decision::Permit
+    }
+
+    pub fn explanation(&self) -> &Explanation {
+        &self.explanation
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExplanationNode {
+    Policy,
+    Permission {
+        index: usize,
+        action: String,
+    },
+    Prohibition {
+        index: usize,
+        action: String,
+    },
+    Obligation {
+        index: usize,
+        action: String,
+    },
+    And,
+    Or,
+    Xone,
+    Constraint {
+        left_operand: String,
+        operator: String,
+        right_operand: Value,
+        /// The value of the context, missing when resolved by a function.
+        actual: Option<Value>,
+        /// Why the constraint could not be evaluated.
+        error: Option<String>,
+    },
+}
+
+/// The evaluation tree of a policy, with the outcome of every rule and constraint.
+///
+/// A prohibition is satisfied when all its constraints are, which means that
+/// it applies and denies the policy.
+#[derive(Debug, Clone)]
+pub struct Explanation {
+    node: ExplanationNode,
+    satisfied: bool,
+    children: Vec<Explanation>,
+}
+
+impl Explanation {
+    pub fn node(&self) -> &ExplanationNode {
+        &self.node
+    }
+
+    pub fn satisfied(&self) -> bool {
+        self.satisfied
+    }
+
+    pub fn children(&self) -> &[Explanation] {
+        &self.children
+    }
+
+    fn write(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let mark = if self.satisfied { "✓" } else { "✗" };
+        write!(f, "{:indent$}{mark} ", "", indent = depth * 2)?;
+        match &self.node {
+            ExplanationNode::Policy => write!(f, "policy")?,
+            ExplanationNode::Permission { index, action } => {
+                write!(f, "permission #{index} ({action})")?
+            }
+            ExplanationNode::Prohibition { index, action } => {
+                write!(f, "prohibition #{index} ({action})")?
+            }
+            ExplanationNode::Obligation { index, action } => {
+                write!(f, "obligation #{index} ({action})")?
+            }
+            ExplanationNode::And => write!(f, "and")?,
+            ExplanationNode::Or => write!(f, "or")?,
+            ExplanationNode::Xone => write!(f, "xone")?,
+            ExplanationNode::Constraint {
+                left_operand,
+                operator,
+                right_operand,
+                actual,
+                error,
+            } => {
+                write!(f, "{left_operand} {operator} {right_operand}")?;
+                if let Some(actual) = actual {
+                    write!(f, " (actual {actual})")?;
+                }
+                if let Some(error) = error {
+                    write!(f, " ({error})")?;
+                }
+            }
+        }
+        writeln!(f)?;
+        self.children
+            .iter()
+            .try_for_each(|child| child.write(f, depth + 1))
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+impl PolicyEvaluator {
+    /// Permits when no prohibition applies, every obligation is satisfied and
+    /// at least one permission is, if the policy has any.
+    pub fn evaluate(&self, policy: &Policy, context: &EvaluationContext) -> Evaluation {
+        let applies = |action: &Action| match context.action() {
+            Some(expected) => local_name(action.id()) == local_name(expected),
+            None => true,
+        };
+
+        let permissions = policy
+            .permissions()
+            .iter()
+            .enumerate()
+            .filter(|(_, permission)| applies(permission.action()))
+            .map(|(index, permission)| {
+                self.rule(
+                    ExplanationNode::Permission {
+                        index,
+                        action: permission.action().id().clone(),
+                    },
+                    permission.constraints(),
+                    context,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let prohibitions = policy
+            .prohibitions()
+            .iter()
+            .enumerate()
+            .filter(|(_, prohibition)| applies(prohibition.action()))
+            .map(|(index, prohibition)| {
+                self.rule(
+                    ExplanationNode::Prohibition {
+                        index,
+                        action: prohibition.action().id().clone(),
+                    },
+                    prohibition.constraints(),
+                    context,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let obligations = policy
+            .obligations()
+            .iter()
+            .enumerate()
+            .filter(|(_, obligation)| applies(obligation.action()))
+            .map(|(index, obligation)| {
+                self.rule(
+                    ExplanationNode::Obligation {
+                        index,
+                        action: obligation.action().id().clone(),
+                    },
+                    obligation.constraints(),
+                    context,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let permitted = (policy.permissions().is_empty()
+            || permissions.iter().any(Explanation::satisfied))
+            && !prohibitions.iter().any(Explanation::satisfied)
+            && obligations.iter().all(Explanation::satisfied);
+
+        let explanation = Explanation {
+            node: ExplanationNode::Policy,
+            satisfied: permitted,
+            children: permissions
+                .into_iter()
+                .chain(prohibitions)
+                .chain(obligations)
+                .collect(),
+        };
+
+        Evaluation {
+            decision: if permitted {
+                Decision::Permit
+            } else {
+                Decision::Deny
+            },
+            explanation,
+        }
+    }
+
+    fn rule(
+        &self,
+        node: ExplanationNode,
+        constraints: &[Constraint],
+        context: &EvaluationContext,
+    ) -> Explanation {
+        let children = constraints
+            .iter()
+            .map(|constraint| self.constraint(constraint, context))
+            .collect::<Vec<_>>();
+
+        Explanation {
+            node,
+            satisfied: children.iter().all(Explanation::satisfied),
+            children,
+        }
+    }
+
+    fn constraint(&self, constraint: &Constraint, context: &EvaluationContext) -> Explanation {
+        let (node, constraints) = match constraint {
+            Constraint::Atomic(atomic) => return self.atomic(atomic, context),
+            Constraint::MultiplicityConstraint(MultiplicityConstraint::And(constraints)) => {
+                (ExplanationNode::And, constraints)
+            }
+            Constraint::MultiplicityConstraint(MultiplicityConstraint::Or(constraints)) => {
+                (ExplanationNode::Or, constraints)
+            }
+            Constraint::MultiplicityConstraint(MultiplicityConstraint::Xone(constraints)) => {
+                (ExplanationNode::Xone, constraints)
+            }
+        };
+
+        let children = constraints
+            .iter()
+            .map(|constraint| self.constraint(constraint, context))
+            .collect::<Vec<_>>();
+        let count = children.iter().filter(|child| child.satisfied).count();
+        let satisfied = match node {
+            ExplanationNode::Or => count > 0,
+            ExplanationNode::Xone => count == 1,
+            _ => count == children.len(),
+        };
+
+        Explanation {
+            node,
+            satisfied,
+            children,
+        }
+    }
+
+    fn atomic(&self, constraint: &AtomicConstraint, context: &EvaluationContext) -> Explanation {
+        let left_operand = constraint.left_operand.get_id();
+        let operator = local_name(constraint.operator.get_id());
+        let right_operand = &constraint.right_operand.0;
+
+        let (satisfied, actual, error) = match self.functions.get(local_name(left_operand)) {
+            Some(function) => (function(operator, right_operand, context), None, None),
+            None => match context.value(left_operand) {
+                Some(actual) => match compare(operator, actual, right_operand) {
+                    Ok(satisfied) => (satisfied, Some(actual.clone()), None),
+                    Err(error) => (false, Some(actual.clone()), Some(error)),
+                },
+                None => (false, None, Some("no value in the context".to_string())),
+            },
+        };
+
+        Explanation {
+            node: ExplanationNode::Constraint {
+                left_operand: left_operand.to_string(),
+                operator: operator.to_string(),
+                right_operand: right_operand.clone(),
+                actual,
+                error,
+            },
+            satisfied,
+            children: vec![],
+        }
+    }
+}
+
+fn compare(operator: &str, actual: &Value, expected: &Value) -> Result<bool, String> {
+    let ordering = || {
+        order(actual, expected).ok_or_else(|| format!("cannot compare {actual} with {expected}"))
+    };
+
+    match operator {
+        "eq" | "isA" => Ok(equals(actual, expected)),
+        "neq" => Ok(!equals(actual, expected)),
+        "gt" => Ok(ordering()? == Ordering::Greater),
+        "gteq" => Ok(ordering()? != Ordering::Less),
+        "lt" => Ok(ordering()? == Ordering::Less),
+        "lteq" => Ok(ordering()? != Ordering::Greater),
+        "hasPart" => Ok(contains(actual, expected)),
+        "isPartOf" => Ok(contains(expected, actual)),
+        "isAnyOf" => Ok(elements(actual)
+            .iter()
+            .any(|value| elements(expected).iter().any(|e| equals(value, e)))),
+        "isAllOf" => Ok(elements(expected)
+            .iter()
+            .all(|value| elements(actual).iter().any(|a| equals(a, value)))),
+        "isNoneOf" => Ok(!elements(actual)
+            .iter()
+            .any(|value| elements(expected).iter().any(|e| equals(value, e)))),
+        operator => Err(format!("unsupported operator {operator}")),
+    }
+}
+
+fn equals(actual: &Value, expected: &Value) -> bool {
+    match (number(actual), number(expected)) {
+        (Some(actual), Some(expected)) => actual == expected,
+        _ => actual == expected,
+    }
+}
+
+/// Numbers, also as strings, compare numerically, other strings
+/// lexicographically, which orders ISO 8601 dates in the same format.
+fn order(actual: &Value, expected: &Value) -> Option<Ordering> {
+    match (number(actual), number(expected)) {
+        (Some(actual), Some(expected)) => actual.partial_cmp(&expected),
+        _ => match (actual, expected) {
+            (Value::String(actual), Value::String(expected)) => Some(actual.cmp(expected)),
+            _ => None,
+        },
+    }
+}
+
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(value) => value.parse().ok(),
+        _ => None,
+    }
+}
+
+fn contains(container: &Value, part: &Value) -> bool {
+    match (container, part) {
+        (Value::String(container), Value::String(part)) => container.contains(part.as_str()),
+        (Value::Array(values), part) => values.iter().any(|value| equals(value, part)),
+        _ => false,
+    }
+}
+
+fn elements(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::Array(values) => values.iter().collect(),
+        value => vec![value],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::types::policy::{Permission, Prohibition};
+
+    use super::*;
+
+    fn constraint(left: &str, operator: &str, right: Value) -> Constraint {
+        Constraint::Atomic(AtomicConstraint {
+            left_operand: left.into(),
+            operator: super::super::Operator::simple(operator),
+            right_operand: crate::types::properties::PropertyValue(right),
+        })
+    }
+
+    fn policy(constraints: Vec<Constraint>) -> Policy {
+        Policy::builder()
+            .permission(Permission::builder().constraints(constraints).build())
+            .build()
+    }
+
+    #[test]
+    fn should_evaluate_standard_operators() {
+        let context = EvaluationContext::builder()
+            .value(
+                "https://w3id.org/edc/v0.0.1/ns/BusinessPartnerNumber",
+                "BPNL1",
+            )
+            .value("purpose", "research")
+            .value("odrl:count", 5)
+            .value("inForceDate", "2024-06-01T00:00:00Z")
+            .value("regions", json!(["eu", "us"]))
+            .build();
+        let evaluator = PolicyEvaluator::default();
+
+        let cases = [
+            (
+                constraint("BusinessPartnerNumber", "eq", json!("BPNL1")),
+                true,
+            ),
+            (constraint("purpose", "odrl:neq", json!("research")), false),
+            (constraint("count", "gt", json!(3)), true),
+            (constraint("count", "lteq", json!("4")), false),
+            (
+                constraint("inForceDate", "lt", json!("2025-01-01T00:00:00Z")),
+                true,
+            ),
+            (
+                constraint("purpose", "isAnyOf", json!(["research", "ai"])),
+                true,
+            ),
+            (constraint("regions", "isAllOf", json!(["eu"])), true),
+            (constraint("regions", "isNoneOf", json!(["cn"])), true),
+            (constraint("purpose", "isPartOf", json!(["research"])), true),
+            (constraint("regions", "hasPart", json!("us")), true),
+            (constraint("purpose", "unknown", json!("x")), false),
+            (constraint("missing", "eq", json!("x")), false),
+        ];
+
+        for (constraint, expected) in cases {
+            let evaluation = evaluator.evaluate(&policy(vec![constraint.clone()]), &context);
+            assert_eq!(evaluation.is_permitted(), expected, "{constraint:?}");
+        }
+    }
+
+    #[test]
+    fn should_evaluate_logical_constraints_and_prohibitions() {
+        let context = EvaluationContext::builder()
+            .value("purpose", "research")
+            .value("region", "eu")
+            .build();
+        let evaluator = PolicyEvaluator::default();
+
+        let or = Constraint::or(vec![
+            constraint("purpose", "eq", json!("ai")),
+            constraint("region", "eq", json!("eu")),
+        ]);
+        assert!(evaluator
+            .evaluate(&policy(vec![or]), &context)
+            .is_permitted());
+
+        let xone = Constraint::xone(vec![
+            constraint("purpose", "eq", json!("research")),
+            constraint("region", "eq", json!("eu")),
+        ]);
+        assert!(!evaluator
+            .evaluate(&policy(vec![xone]), &context)
+            .is_permitted());
+
+        let prohibited = Policy::builder()
+            .permission(Permission::builder().build())
+            .prohibition(
+                Prohibition::builder()
+                    .action(Action::simple("use"))
+                    .constraint(constraint("region", "eq", json!("eu")))
+                    .build(),
+            )
+            .build();
+        let evaluation = evaluator.evaluate(&prohibited, &context);
+        assert_eq!(evaluation.decision(), Decision::Deny);
+        assert!(matches!(
+            evaluation.explanation().children()[1].node(),
+            ExplanationNode::Prohibition { index: 0, .. }
+        ));
+        assert!(evaluation
+            .explanation()
+            .to_string()
+            .contains("✓ region eq \"eu\""));
+    }
+
+    #[test]
+    fn should_use_custom_left_operand_functions() {
+        let evaluator = PolicyEvaluator::builder()
+            .function(
+                "https://w3id.org/catenax/policy/Membership",
+                |operator, right, _| operator == "eq" && right == "active",
+            )
+            .build();
+
+        let membership = policy(vec![constraint("Membership", "odrl:eq", json!("active"))]);
+        let evaluation = evaluator.evaluate(&membership, &EvaluationContext::default());
+
+        assert!(evaluation.is_permitted());
+    }
+}
@@ -0,0 +1,327 @@
+//! Selection of the offer of a dataset to negotiate, by comparing the offers
+//! with the policy the consumer is willing to accept.
+//!
+//! Policies are compared by their rules only, once normalized: `@id`, `@type`,
+//! assigner and target are ignored, as well as the order of rules and
+//! constraints and how actions, operators and left operands are written.
+
+use bon::Builder;
+
+use crate::types::catalog::Dataset;
+
+use super::{
+    evaluation::{Evaluation, EvaluationContext, PolicyEvaluator},
+    normalization::{rules, Normalizer, RuleKind},
+    Policy,
+};
+
+/// How closely an offer matches the acceptance policy, the closest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchLevel {
+    /// The offer has the same rules as the acceptance policy.
+    Exact,
+    /// Every permission of the offer is in the acceptance policy with the same
+    /// or fewer constraints, and every prohibition and obligation is in it as is.
+    Contained,
+}
+
+/// Ranks the offers of datasets against an acceptance policy.
+#[derive(Debug, Clone, Builder)]
+pub struct OfferMatcher {
+    acceptance: Policy,
+    /// When set, only the offers permitted in this context are candidates.
+    context: Option<EvaluationContext>,
+    #[builder(default)]
+    evaluator: PolicyEvaluator,
+    #[builder(default)]
+    normalizer: Normalizer,
+}
+
+#[derive(Debug, Clone)]
+pub struct OfferCandidate<'a> {
+    offer: &'a Policy,
+    index: usize,
+    level: MatchLevel,
+    constraints: usize,
+    evaluation: Option<Evaluation>,
+}
+
+impl<'a> OfferCandidate<'a> {
+    pub fn offer(&self) -> &'a Policy {
+        self.offer
+    }
+
+    /// Position of the offer in the offers of the dataset.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn level(&self) -> MatchLevel {
+        self.level
+    }
+
+    /// The evaluation of the offer, when the matcher has a context.
+    pub fn evaluation(&self) -> Option<&Evaluation> {
+        self.evaluation.as_ref()
+    }
+
+    /// The policy of a [`ContractRequest`](crate::types::contract_negotiation::ContractRequest)
+    /// for this offer, see [`Policy::to_contract_offer`].
+    pub fn to_contract_offer(&self, assigner: &str, target: &str) -> Policy {
+        self.offer.to_contract_offer(assigner, target)
+    }
+}
+
+impl OfferMatcher {
+    /// Returns the offers matching the acceptance policy, exact matches first
+    /// and then the ones with fewer constraints. Offers with the same rank
+    /// keep their order.
+    pub fn rank<'a>(&self, offers: &'a [Policy]) -> Vec<OfferCandidate<'a>> {
+        let acceptance = Rules::of(&self.normalizer.normalize(&self.acceptance));
+
+        let mut candidates = offers
+            .iter()
+            .enumerate()
+            .filter_map(|(index, offer)| {
+                let rules = Rules::of(&self.normalizer.normalize(offer));
+                let level = acceptance.compare(&rules)?;
+                let evaluation = match &self.context {
+                    Some(context) => {
+                        let evaluation = self.evaluator.evaluate(offer, context);
+                        if !evaluation.is_permitted() {
+                            return None;
+                        }
+                        Some(evaluation)
+                    }
+                    None => None,
+                };
+
+                Some(OfferCandidate {
+                    offer,
+                    index,
+                    level,
+                    constraints: rules.constraints(),
+                    evaluation,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        candidates.sort_by_key(|candidate| (candidate.level, candidate.constraints));
+        candidates
+    }
+
+    pub fn best<'a>(&self, dataset: &'a Dataset) -> Option<OfferCandidate<'a>> {
+        self.rank(dataset.offers()).into_iter().next()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Rule {
+    action: String,
+    constraints: Vec<String>,
+}
+
+/// The permissions, prohibitions and obligations of a normalized policy in a
+/// canonical form, sorted for comparison.
+#[derive(Debug, PartialEq)]
+struct Rules([Vec<Rule>; 3]);
+
+impl Rules {
+    fn of(policy: &Policy) -> Rules {
+        Rules(
+            [
+                RuleKind::Permission,
+                RuleKind::Prohibition,
+                RuleKind::Obligation,
+            ]
+            .map(|kind| {
+                sorted(
+                    rules(kind, policy)
+                        .iter()
+                        .map(|rule| Rule {
+                            action: rule.action.clone(),
+                            constraints: rule
+                                .canonical_constraints()
+                                .into_iter()
+                                .map(String::from)
+                                .collect(),
+                        })
+                        .collect(),
+                )
+            }),
+        )
+    }
+
+    fn compare(&self, offer: &Rules) -> Option<MatchLevel> {
+        if self == offer {
+            return Some(MatchLevel::Exact);
+        }
+
+        let [permissions, prohibitions, obligations] = &offer.0;
+        let contained = permissions.iter().all(|rule| {
+            self.0[0].iter().any(|accepted| {
+                rule.action == accepted.action
+                    && is_subset(&rule.constraints, &accepted.constraints)
+            })
+        }) && prohibitions.iter().all(|rule| self.0[1].contains(rule))
+            && obligations.iter().all(|rule| self.0[2].contains(rule));
+
+        contained.then_some(MatchLevel::Contained)
+    }
+
+    fn constraints(&self) -> usize {
+        self.0
+            .iter()
+            .flatten()
+            .map(|rule| rule.constraints.len())
+            .sum()
+    }
+}
+
+fn sorted<T: Ord>(mut values: Vec<T>) -> Vec<T> {
+    values.sort();
+    values
+}
+
+fn is_subset(values: &[String], of: &[String]) -> bool {
+    let mut remaining = of.iter().collect::<Vec<_>>();
+    values.iter().all(
+        |value| match remaining.iter().position(|candidate| *candidate == value) {
+            Some(position) => {
+                remaining.remove(position);
+                true
+            }
+            None => false,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use crate::types::policy::{Action, AtomicConstraint, Constraint, Permission};
+
+    use super::*;
+
+    fn acceptance() -> Policy {
+        Policy::builder()
+            .permission(
+                Permission::builder()
+                    .action(Action::simple("use"))
+                    .constraint(Constraint::atomic(AtomicConstraint::new(
+                        "BusinessPartnerNumber",
+                        "eq",
+                        "BPNL1",
+                    )))
+                    .constraint(Constraint::atomic(AtomicConstraint::new(
+                        "purpose", "eq", "research",
+                    )))
+                    .build(),
+            )
+            .build()
+    }
+
+    fn offer(id: &str, constraints: Value) -> Value {
+        json!({
+            "@id": id,
+            "@type": "odrl:Offer",
+            "odrl:permission": {
+                "odrl:action": { "@id": "odrl:use" },
+                "odrl:constraint": constraints
+            }
+        })
+    }
+
+    #[test]
+    fn should_ignore_ids_order_and_prefixes() {
+        let offer: Policy = serde_json::from_value(offer(
+            "offer-1",
+            json!([
+                {
+                    "odrl:leftOperand": { "@id": "odrl:purpose" },
+                    "odrl:operator": { "@id": "odrl:eq" },
+                    "odrl:rightOperand": "research"
+                },
+                {
+                    "odrl:leftOperand": { "@id": "https://w3id.org/edc/v0.0.1/ns/BusinessPartnerNumber" },
+                    "odrl:operator": { "@id": "http://www.w3.org/ns/odrl/2/eq" },
+                    "odrl:rightOperand": "BPNL1"
+                }
+            ]),
+        ))
+        .unwrap();
+
+        let candidates = OfferMatcher::builder()
+            .acceptance(acceptance())
+            .build()
+            .rank(std::slice::from_ref(&offer));
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].level(), MatchLevel::Exact);
+    }
+
+    #[test]
+    fn should_rank_matching_offers() {
+        let purpose = json!({
+            "odrl:leftOperand": "purpose",
+            "odrl:operator": "eq",
+            "odrl:rightOperand": "research"
+        });
+        let bpn = json!({
+            "odrl:leftOperand": "BusinessPartnerNumber",
+            "odrl:operator": "eq",
+            "odrl:rightOperand": "BPNL1"
+        });
+        let region = json!({
+            "odrl:leftOperand": "region",
+            "odrl:operator": "eq",
+            "odrl:rightOperand": "eu"
+        });
+
+        let dataset: Dataset = serde_json::from_value(json!({
+            "@id": "asset-1",
+            "hasPolicy": [
+                offer("stricter", json!([purpose, bpn, region])),
+                offer("looser", json!([purpose])),
+                offer("exact", json!([bpn, purpose])),
+            ]
+        }))
+        .unwrap();
+
+        let matcher = OfferMatcher::builder().acceptance(acceptance()).build();
+        let candidates = matcher.rank(dataset.offers());
+
+        assert_eq!(
+            candidates
+                .iter()
+                .map(|candidate| (candidate.index(), candidate.level()))
+                .collect::<Vec<_>>(),
+            vec![(2, MatchLevel::Exact), (1, MatchLevel::Contained)]
+        );
+
+        let best = matcher.best(&dataset).unwrap();
+        let contract_offer = best.to_contract_offer("provider", dataset.id());
+        assert_eq!(contract_offer.id().map(String::as_str), Some("exact"));
+        assert_eq!(
+            contract_offer.assigner().map(String::as_str),
+            Some("provider")
+        );
+    }
+
+    #[test]
+    fn should_discard_offers_not_permitted_in_context() {
+        let offer = acceptance();
+        let matcher = OfferMatcher::builder()
+            .acceptance(acceptance())
+            .context(
+                EvaluationContext::builder()
+                    .value("BusinessPartnerNumber", "BPNL2")
+                    .value("purpose", "research")
+                    .build(),
+            )
+            .build();
+
+        assert!(matcher.rank(std::slice::from_ref(&offer)).is_empty());
+    }
+}
@@ -0,0 +1,695 @@
+//! Normalization of policies to full IRIs, for comparing policies written
+//! with different prefixes or in compact and expanded form.
+//!
+//! ```rust
+//! use edc_connector_client::types::policy::{Action, Permission, Policy};
+//!
+//! let compact = Policy::builder()
+//!     .permission(Permission::builder().action(Action::simple("use")).build())
+//!     .build();
+//! let expanded = Policy::builder()
+//!     .id("policy-1")
+//!     .permission(Permission::builder().build())
+//!     .build();
+//!
+//! assert_ne!(compact, expanded);
+//! assert!(compact.equivalent(&expanded));
+//! assert!(compact.diff(&expanded).is_empty());
+//! ```
+
+use std::{collections::HashMap, fmt};
+
+use bon::Builder;
+use serde_json::Value;
+
+use crate::{EDC_NAMESPACE, ODRL_NAMESPACE};
+
+use super::{
+    local_name,
+    vocabulary::{LeftOperandKind, Term},
+    Action, AtomicConstraint, Constraint, LeftOperand, MultiplicityConstraint, Operator, Policy,
+    PolicyKind, Target,
+};
+
+/// Expands the terms of policies to full IRIs.
+///
+/// Prefixed terms are expanded with the known prefixes (`odrl`, `edc`,
+/// `cx-policy` and `tx` by default). Actions and operators without prefix are
+/// ODRL terms, left operands without prefix are ODRL terms when part of the
+/// core vocabulary and otherwise terms of the vocab, the EDC namespace by default.
+#[derive(Debug, Clone, Builder)]
+pub struct Normalizer {
+    #[builder(field = default_prefixes())]
+    prefixes: HashMap<String, String>,
+    #[builder(default = EDC_NAMESPACE.to_string(), into)]
+    vocab: String,
+}
+
+impl<S: normalizer_builder::State> NormalizerBuilder<S> {
+    pub fn prefix(mut self, prefix: &str, iri: &str) -> Self {
+        self.prefixes.insert(prefix.to_string(), iri.to_string());
+        self
+    }
+}
+
+impl Default for Normalizer {
+    fn default() -> Self {
+        Normalizer::builder().build()
+    }
+}
+
+fn default_prefixes() -> HashMap<String, String> {
+    [
+        ("odrl", ODRL_NAMESPACE),
+        ("edc", EDC_NAMESPACE),
+        ("cx-policy", "https://w3id.org/catenax/policy/"),
+        ("tx", "https://w3id.org/tractusx/v0.0.1/ns/"),
+    ]
+    .into_iter()
+    .map(|(prefix, iri)| (prefix.to_string(), iri.to_string()))
+    .collect()
+}
+
+impl Normalizer {
+    /// Returns the policy with all actions, operators, left operands and the
+    /// target as full IRIs in their `@id` form.
+    pub fn normalize(&self, policy: &Policy) -> Policy {
+        let mut policy = policy.clone();
+        for permission in &mut policy.permissions {
+            permission.action = self.action(&permission.action);
+            self.constraints(&mut permission.constraints);
+        }
+        for prohibition in &mut policy.prohibitions {
+            prohibition.action = self.action(&prohibition.action);
+            self.constraints(&mut prohibition.constraints);
+        }
+        for obligation in &mut policy.obligations {
+            obligation.action = self.action(&obligation.action);
+            self.constraints(&mut obligation.constraints);
+        }
+        policy.target = policy
+            .target
+            .as_ref()
+            .map(|target| Target::id(&self.target(target.get_id())));
+        policy
+    }
+
+    /// Whether the policies have the same meaning, regardless of the `@id`,
+    /// the order of rules and constraints and how terms are written.
+    pub fn equivalent(&self, left: &Policy, right: &Policy) -> bool {
+        self.diff(left, right).is_empty()
+    }
+
+    /// The changes turning `left` into `right`, after normalization.
+    pub fn diff(&self, left: &Policy, right: &Policy) -> PolicyDiff {
+        let left = self.normalize(left);
+        let right = self.normalize(right);
+        let mut changes = Vec::new();
+
+        if left.kind != right.kind {
+            changes.push(PolicyChange::Kind {
+                from: left.kind.clone(),
+                to: right.kind.clone(),
+            });
+        }
+        if left.assigner != right.assigner {
+            changes.push(PolicyChange::Assigner {
+                from: left.assigner.clone(),
+                to: right.assigner.clone(),
+            });
+        }
+        if left.assignee != right.assignee {
+            changes.push(PolicyChange::Assignee {
+                from: left.assignee.clone(),
+                to: right.assignee.clone(),
+            });
+        }
+        let target = |policy: &Policy| policy.target.as_ref().map(|t| t.get_id().to_string());
+        if target(&left) != target(&right) {
+            changes.push(PolicyChange::Target {
+                from: target(&left),
+                to: target(&right),
+            });
+        }
+
+        for kind in [
+            RuleKind::Permission,
+            RuleKind::Prohibition,
+            RuleKind::Obligation,
+        ] {
+            diff_rules(kind, rules(kind, &left), rules(kind, &right), &mut changes);
+        }
+
+        PolicyDiff { changes }
+    }
+
+    fn action(&self, action: &Action) -> Action {
+        Action::new(
+            self.expand(action.id())
+                .unwrap_or_else(|| format!("{ODRL_NAMESPACE}{}", action.id())),
+        )
+    }
+
+    fn operator(&self, operator: &Operator) -> Operator {
+        let id = operator.get_id();
+        Operator::id(
+            &self
+                .expand(id)
+                .unwrap_or_else(|| format!("{ODRL_NAMESPACE}{id}")),
+        )
+    }
+
+    fn left_operand(&self, left_operand: &LeftOperand) -> LeftOperand {
+        let id = left_operand.get_id();
+        LeftOperand::id(&self.expand(id).unwrap_or_else(|| {
+            let odrl =
+                LeftOperandKind::parse(id).filter(|term| term.iri().starts_with(ODRL_NAMESPACE));
+            if let Some(term) = odrl {
+                term.iri().to_string()
+            } else {
+                format!("{}{id}", self.vocab)
+            }
+        }))
+    }
+
+    /// Targets are asset ids, which are only expanded when prefixed.
+    fn target(&self, target: &str) -> String {
+        self.expand(target).unwrap_or_else(|| target.to_string())
+    }
+
+    fn constraints(&self, constraints: &mut [Constraint]) {
+        for constraint in constraints {
+            match constraint {
+                Constraint::Atomic(atomic) => {
+                    *atomic = AtomicConstraint {
+                        left_operand: self.left_operand(&atomic.left_operand),
+                        operator: self.operator(&atomic.operator),
+                        right_operand: atomic.right_operand.clone(),
+                    }
+                }
+                Constraint::MultiplicityConstraint(
+                    MultiplicityConstraint::And(constraints)
+                    | MultiplicityConstraint::Or(constraints)
+                    | MultiplicityConstraint::Xone(constraints),
+                ) => self.constraints(constraints),
+            }
+        }
+    }
+
+    /// Expands IRIs and prefixed terms, `None` for terms without prefix.
+    fn expand(&self, term: &str) -> Option<String> {
+        if term.contains("://") {
+            return Some(term.to_string());
+        }
+        let (prefix, name) = term.split_once(':')?;
+        self.prefixes.get(prefix).map(|iri| format!("{iri}{name}"))
+    }
+}
+
+impl Policy {
+    /// See [`Normalizer::normalize`].
+    pub fn normalized(&self) -> Policy {
+        Normalizer::default().normalize(self)
+    }
+
+    /// See [`Normalizer::equivalent`].
+    pub fn equivalent(&self, other: &Policy) -> bool {
+        Normalizer::default().equivalent(self, other)
+    }
+
+    /// See [`Normalizer::diff`].
+    pub fn diff(&self, other: &Policy) -> PolicyDiff {
+        Normalizer::default().diff(self, other)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleKind {
+    Permission,
+    Prohibition,
+    Obligation,
+}
+
+impl fmt::Display for RuleKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleKind::Permission => write!(f, "permission"),
+            RuleKind::Prohibition => write!(f, "prohibition"),
+            RuleKind::Obligation => write!(f, "obligation"),
+        }
+    }
+}
+
+/// A difference between two normalized policies. Rules are identified by
+/// their kind and action.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PolicyChange {
+    Kind {
+        from: PolicyKind,
+        to: PolicyKind,
+    },
+    Assigner {
+        from: Option<String>,
+        to: Option<String>,
+    },
+    Assignee {
+        from: Option<String>,
+        to: Option<String>,
+    },
+    Target {
+        from: Option<String>,
+        to: Option<String>,
+    },
+    RuleAdded {
+        kind: RuleKind,
+        action: String,
+        constraints: Vec<Constraint>,
+    },
+    RuleRemoved {
+        kind: RuleKind,
+        action: String,
+        constraints: Vec<Constraint>,
+    },
+    ConstraintAdded {
+        kind: RuleKind,
+        action: String,
+        constraint: Constraint,
+    },
+    ConstraintRemoved {
+        kind: RuleKind,
+        action: String,
+        constraint: Constraint,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PolicyDiff {
+    changes: Vec<PolicyChange>,
+}
+
+impl PolicyDiff {
+    pub fn changes(&self) -> &[PolicyChange] {
+        &self.changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// One change per line, terms shortened to their local name:
+///
+/// ```text
+/// ~ target: asset-1 -> asset-2
+/// + permission use [purpose eq "research"]
+/// - prohibition distribute
+/// ```
+impl fmt::Display for PolicyDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = |value: &Option<String>| value.clone().unwrap_or_else(|| "none".to_string());
+        for change in &self.changes {
+            match change {
+                PolicyChange::Kind { from, to } => writeln!(f, "~ type: {from:?} -> {to:?}")?,
+                PolicyChange::Assigner { from, to } => {
+                    writeln!(f, "~ assigner: {} -> {}", value(from), value(to))?
+                }
+                PolicyChange::Assignee { from, to } => {
+                    writeln!(f, "~ assignee: {} -> {}", value(from), value(to))?
+                }
+                PolicyChange::Target { from, to } => {
+                    writeln!(f, "~ target: {} -> {}", value(from), value(to))?
+                }
+                PolicyChange::RuleAdded {
+                    kind,
+                    action,
+                    constraints,
+                } => writeln!(
+                    f,
+                    "+ {kind} {}{}",
+                    local_name(action),
+                    describe_all(constraints)
+                )?,
+                PolicyChange::RuleRemoved {
+                    kind,
+                    action,
+                    constraints,
+                } => writeln!(
+                    f,
+                    "- {kind} {}{}",
+                    local_name(action),
+                    describe_all(constraints)
+                )?,
+                PolicyChange::ConstraintAdded {
+                    kind,
+                    action,
+                    constraint,
+                } => writeln!(
+                    f,
+                    "+ {kind} {} constraint {}",
+                    local_name(action),
+                    describe(constraint)
+                )?,
+                PolicyChange::ConstraintRemoved {
+                    kind,
+                    action,
+                    constraint,
+                } => writeln!(
+                    f,
+                    "- {kind} {} constraint {}",
+                    local_name(action),
+                    describe(constraint)
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A normalized rule with the canonical form of its constraints.
+pub(super) struct Rule {
+    pub(super) action: String,
+    pub(super) constraints: Vec<(String, Constraint)>,
+}
+
+impl Rule {
+    pub(super) fn canonical_constraints(&self) -> Vec<&str> {
+        let mut constraints = self
+            .constraints
+            .iter()
+            .map(|(canonical, _)| canonical.as_str())
+            .collect::<Vec<_>>();
+        constraints.sort();
+        constraints
+    }
+
+    fn same_as(&self, other: &Rule) -> bool {
+        self.action == other.action && self.canonical_constraints() == other.canonical_constraints()
+    }
+}
+
+/// The rules of a kind of a normalized policy.
+pub(super) fn rules(kind: RuleKind, policy: &Policy) -> Vec<Rule> {
+    let rule = |action: &Action, constraints: &[Constraint]| Rule {
+        action: action.id().clone(),
+        constraints: constraints
+            .iter()
+            .map(|constraint| (canonical(constraint), constraint.clone()))
+            .collect(),
+    };
+
+    match kind {
+        RuleKind::Permission => policy
+            .permissions
+            .iter()
+            .map(|permission| rule(&permission.action, &permission.constraints))
+            .collect(),
+        RuleKind::Prohibition => policy
+            .prohibitions
+            .iter()
+            .map(|prohibition| rule(&prohibition.action, &prohibition.constraints))
+            .collect(),
+        RuleKind::Obligation => policy
+            .obligations
+            .iter()
+            .map(|obligation| rule(&obligation.action, &obligation.constraints))
+            .collect(),
+    }
+}
+
+/// Pairs the identical rules first and then the remaining ones by action, in
+/// order, reporting the constraints of paired rules that differ.
+fn diff_rules(
+    kind: RuleKind,
+    left: Vec<Rule>,
+    mut right: Vec<Rule>,
+    changes: &mut Vec<PolicyChange>,
+) {
+    let mut unpaired = Vec::new();
+    for rule in left {
+        match right.iter().position(|other| rule.same_as(other)) {
+            Some(position) => {
+                right.remove(position);
+            }
+            None => unpaired.push(rule),
+        }
+    }
+
+    for rule in unpaired {
+        let Some(position) = right.iter().position(|other| other.action == rule.action) else {
+            changes.push(PolicyChange::RuleRemoved {
+                kind,
+                action: rule.action,
+                constraints: rule.constraints.into_iter().map(|(_, c)| c).collect(),
+            });
+            continue;
+        };
+
+        let mut added = right.remove(position).constraints;
+        for (canonical, constraint) in rule.constraints {
+            match added.iter().position(|(other, _)| *other == canonical) {
+                Some(position) => {
+                    added.remove(position);
+                }
+                None => changes.push(PolicyChange::ConstraintRemoved {
+                    kind,
+                    action: rule.action.clone(),
+                    constraint,
+                }),
+            }
+        }
+        changes.extend(
+            added
+                .into_iter()
+                .map(|(_, constraint)| PolicyChange::ConstraintAdded {
+                    kind,
+                    action: rule.action.clone(),
+                    constraint,
+                }),
+        );
+    }
+
+    changes.extend(right.into_iter().map(|rule| PolicyChange::RuleAdded {
+        kind,
+        action: rule.action,
+        constraints: rule.constraints.into_iter().map(|(_, c)| c).collect(),
+    }));
+}
+
+/// The canonical form of a normalized constraint, independent of the order
+/// of nested constraints and list operands.
+pub(super) fn canonical(constraint: &Constraint) -> String {
+    match constraint {
+        Constraint::Atomic(atomic) => format!(
+            "{} {} {}",
+            atomic.left_operand.get_id(),
+            atomic.operator.get_id(),
+            canonical_value(&atomic.right_operand.0)
+        ),
+        Constraint::MultiplicityConstraint(multiplicity) => {
+            let (operator, constraints) = multiplicity_parts(multiplicity);
+            let mut constraints = constraints.iter().map(canonical).collect::<Vec<_>>();
+            constraints.sort();
+            format!("{operator}({})", constraints.join(", "))
+        }
+    }
+}
+
+/// Lists are compared regardless of their order and JSON-LD value objects by
+/// their value.
+fn canonical_value(value: &Value) -> String {
+    match value {
+        Value::Array(values) => {
+            let mut values = values.iter().map(canonical_value).collect::<Vec<_>>();
+            values.sort();
+            format!("[{}]", values.join(", "))
+        }
+        Value::Object(object) if object.len() == 1 => object
+            .get("@value")
+            .or_else(|| object.get("@id"))
+            .map(canonical_value)
+            .unwrap_or_else(|| value.to_string()),
+        value => value.to_string(),
+    }
+}
+
+fn multiplicity_parts(multiplicity: &MultiplicityConstraint) -> (&'static str, &[Constraint]) {
+    match multiplicity {
+        MultiplicityConstraint::And(constraints) => ("and", constraints),
+        MultiplicityConstraint::Or(constraints) => ("or", constraints),
+        MultiplicityConstraint::Xone(constraints) => ("xone", constraints),
+    }
+}
+
+fn describe(constraint: &Constraint) -> String {
+    match constraint {
+        Constraint::Atomic(atomic) => format!(
+            "{} {} {}",
+            local_name(atomic.left_operand.get_id()),
+            local_name(atomic.operator.get_id()),
+            atomic.right_operand.0
+        ),
+        Constraint::MultiplicityConstraint(multiplicity) => {
+            let (operator, constraints) = multiplicity_parts(multiplicity);
+            let constraints = constraints.iter().map(describe).collect::<Vec<_>>();
+            format!("{operator}({})", constraints.join(", "))
+        }
+    }
+}
+
+fn describe_all(constraints: &[Constraint]) -> String {
+    if constraints.is_empty() {
+        return String::new();
+    }
+    let constraints = constraints.iter().map(describe).collect::<Vec<_>>();
+    format!(" [{}]", constraints.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::types::policy::{Permission, Prohibition};
+
+    use super::*;
+
+    #[test]
+    fn should_expand_terms_to_iris() {
+        let policy = Policy::builder()
+            .target(Target::simple("edc:asset-1"))
+            .permission(
+                Permission::builder()
+                    .action(Action::simple("odrl:use"))
+                    .constraint(Constraint::atomic(AtomicConstraint::new(
+                        "purpose", "eq", "research",
+                    )))
+                    .constraint(Constraint::atomic(AtomicConstraint::new(
+                        "cx-policy:Membership",
+                        "odrl:eq",
+                        "active",
+                    )))
+                    .constraint(Constraint::atomic(AtomicConstraint::new(
+                        "BusinessPartnerNumber",
+                        "isAnyOf",
+                        "BPNL1",
+                    )))
+                    .build(),
+            )
+            .build();
+
+        let normalized = serde_json::to_value(policy.normalized()).unwrap();
+
+        assert_eq!(
+            normalized["target"],
+            json!({ "@id": "https://w3id.org/edc/v0.0.1/ns/asset-1" })
+        );
+        let permission = &normalized["permission"][0];
+        assert_eq!(
+            permission["action"],
+            json!({ "@id": "http://www.w3.org/ns/odrl/2/use" })
+        );
+        let left_operands = permission["constraint"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|constraint| constraint["leftOperand"]["@id"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            left_operands,
+            vec![
+                "http://www.w3.org/ns/odrl/2/purpose",
+                "https://w3id.org/catenax/policy/Membership",
+                "https://w3id.org/edc/v0.0.1/ns/BusinessPartnerNumber",
+            ]
+        );
+        assert_eq!(
+            permission["constraint"][2]["operator"],
+            json!({ "@id": "http://www.w3.org/ns/odrl/2/isAnyOf" })
+        );
+    }
+
+    #[test]
+    fn should_compare_policies_semantically() {
+        let compact: Policy = serde_json::from_value(json!({
+            "@type": "Offer",
+            "permission": [{
+                "action": "use",
+                "constraint": [
+                    { "leftOperand": "purpose", "operator": "eq", "rightOperand": "research" },
+                    { "leftOperand": "region", "operator": "isAnyOf", "rightOperand": ["eu", "us"] }
+                ]
+            }]
+        }))
+        .unwrap();
+        let expanded: Policy = serde_json::from_value(json!({
+            "@id": "offer-1",
+            "@type": "http://www.w3.org/ns/odrl/2/Offer",
+            "odrl:permission": {
+                "odrl:action": { "@id": "http://www.w3.org/ns/odrl/2/use" },
+                "odrl:constraint": [
+                    {
+                        "odrl:leftOperand": { "@id": "edc:region" },
+                        "odrl:operator": { "@id": "odrl:isAnyOf" },
+                        "odrl:rightOperand": ["us", "eu"]
+                    },
+                    {
+                        "odrl:leftOperand": { "@id": "odrl:purpose" },
+                        "odrl:operator": { "@id": "odrl:eq" },
+                        "odrl:rightOperand": "research"
+                    }
+                ]
+            }
+        }))
+        .unwrap();
+
+        assert_ne!(compact, expanded);
+        assert!(compact.equivalent(&expanded));
+    }
+
+    #[test]
+    fn should_diff_policies() {
+        let left = Policy::builder()
+            .target(Target::simple("asset-1"))
+            .permission(
+                Permission::builder()
+                    .action(Action::simple("use"))
+                    .constraint(Constraint::atomic(AtomicConstraint::new(
+                        "purpose", "eq", "research",
+                    )))
+                    .build(),
+            )
+            .prohibition(
+                Prohibition::builder()
+                    .action(Action::simple("distribute"))
+                    .build(),
+            )
+            .build();
+        let right = Policy::builder()
+            .target(Target::simple("asset-2"))
+            .permission(
+                Permission::builder()
+                    .constraint(Constraint::atomic(AtomicConstraint::new(
+                        "purpose", "eq", "ai",
+                    )))
+                    .build(),
+            )
+            .build();
+
+        let diff = left.diff(&right);
+
+        assert_eq!(diff.changes().len(), 4);
+        assert_eq!(
+            diff.changes()[0],
+            PolicyChange::Target {
+                from: Some("asset-1".to_string()),
+                to: Some("asset-2".to_string())
+            }
+        );
+        assert_eq!(
+            diff.to_string(),
+            "~ target: asset-1 -> asset-2\n\
+             - permission use constraint purpose eq \"research\"\n\
+             + permission use constraint purpose eq \"ai\"\n\
+             - prohibition distribute\n"
+        );
+    }
+}
@@ -0,0 +1,414 @@
+//! Rendering of policies as English text or Markdown for business users.
+//!
+//! ```rust
+//! use edc_connector_client::{policy, types::policy::render::PolicyRenderer};
+//!
+//! let policy = policy! {
+//!     permission(use) {
+//!         "purpose" eq "research";
+//!         "BusinessPartnerNumber" isAnyOf ["BPNL1", "BPNL2"];
+//!     }
+//! };
+//!
+//! assert_eq!(
+//!     PolicyRenderer::default().render(&policy),
+//!     "Use is permitted if purpose equals research and the consumer is one of [BPNL1, BPNL2]."
+//! );
+//! ```
+
+use std::{collections::HashMap, fmt::Write, sync::Arc};
+
+use bon::Builder;
+use serde_json::Value;
+
+use super::{local_name, Action, AtomicConstraint, Constraint, MultiplicityConstraint, Policy};
+
+/// Custom rendering of the constraints on a left operand, receiving the
+/// operator without prefix and the right operand.
+pub type ConstraintTranslation = Arc<dyn Fn(&str, &Value) -> String + Send + Sync>;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RenderFormat {
+    /// One sentence per rule.
+    #[default]
+    Text,
+    /// A list item per rule, with nested items for the constraints.
+    Markdown,
+}
+
+/// Renders policies, with labels or translations for the left operands.
+///
+/// Left operands are matched by their name without namespace, and without
+/// label they are rendered from their name, `inForceDate` as `in force date`.
+#[derive(Clone, Builder)]
+pub struct PolicyRenderer {
+    #[builder(field = default_labels())]
+    labels: HashMap<String, String>,
+    #[builder(field)]
+    translations: HashMap<String, ConstraintTranslation>,
+    #[builder(default)]
+    format: RenderFormat,
+}
+
+impl<S: policy_renderer_builder::State> PolicyRendererBuilder<S> {
+    /// Names the left operand, e.g. `the consumer` for `BusinessPartnerNumber`.
+    pub fn label(mut self, left_operand: &str, label: &str) -> Self {
+        self.labels
+            .insert(local_name(left_operand).to_string(), label.to_string());
+        self
+    }
+
+    /// Renders the constraints on the left operand with a function instead.
+    pub fn translation(
+        mut self,
+        left_operand: &str,
+        translation: impl Fn(&str, &Value) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.translations
+            .insert(local_name(left_operand).to_string(), Arc::new(translation));
+        self
+    }
+}
+
+impl Default for PolicyRenderer {
+    fn default() -> Self {
+        PolicyRenderer::builder().build()
+    }
+}
+
+impl std::fmt::Debug for PolicyRenderer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PolicyRenderer")
+            .field("labels", &self.labels)
+            .field(
+                "translations",
+                &self.translations.keys().collect::<Vec<_>>(),
+            )
+            .field("format", &self.format)
+            .finish()
+    }
+}
+
+fn default_labels() -> HashMap<String, String> {
+    [
+        ("BusinessPartnerNumber", "the consumer"),
+        ("BusinessPartnerGroup", "the consumer group"),
+        ("inForceDate", "the contract date"),
+        ("dateTime", "the date"),
+        ("count", "the usage count"),
+    ]
+    .into_iter()
+    .map(|(left_operand, label)| (left_operand.to_string(), label.to_string()))
+    .collect()
+}
+
+impl PolicyRenderer {
+    pub fn render(&self, policy: &Policy) -> String {
+        let rules =
+            policy
+                .permissions()
+                .iter()
+                .map(|permission| {
+                    (
+                        permission.action(),
+                        "is permitted",
+                        permission.constraints(),
+                    )
+                })
+                .chain(policy.prohibitions().iter().map(|prohibition| {
+                    (
+                        prohibition.action(),
+                        "is prohibited",
+                        prohibition.constraints(),
+                    )
+                }))
+                .chain(policy.obligations().iter().map(|obligation| {
+                    (obligation.action(), "is required", obligation.constraints())
+                }))
+                .collect::<Vec<_>>();
+
+        // Like the evaluator, a policy without rules permits everything.
+        if rules.is_empty() {
+            return match self.format {
+                RenderFormat::Text => "No restrictions.".to_string(),
+                RenderFormat::Markdown => "- No restrictions\n".to_string(),
+            };
+        }
+
+        match self.format {
+            RenderFormat::Text => rules
+                .into_iter()
+                .map(|(action, verb, constraints)| self.sentence(action, verb, constraints))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            RenderFormat::Markdown => {
+                let mut markdown = String::new();
+                for (action, verb, constraints) in rules {
+                    self.markdown_rule(&mut markdown, action, verb, constraints);
+                }
+                markdown
+            }
+        }
+    }
+
+    fn sentence(&self, action: &Action, verb: &str, constraints: &[Constraint]) -> String {
+        let mut sentence = format!("{} {verb}", capitalize(&humanize(local_name(action.id()))));
+        if !constraints.is_empty() {
+            let single = constraints.len() == 1;
+            let constraints = constraints
+                .iter()
+                .map(|constraint| self.text(constraint, single))
+                .collect::<Vec<_>>();
+            let _ = write!(sentence, " if {}", constraints.join(" and "));
+        }
+        sentence.push('.');
+        sentence
+    }
+
+    fn text(&self, constraint: &Constraint, top_level: bool) -> String {
+        let (constraints, separator) = match constraint {
+            Constraint::Atomic(atomic) => return self.atomic(atomic, false),
+            Constraint::MultiplicityConstraint(MultiplicityConstraint::And(constraints)) => {
+                (constraints, " and ")
+            }
+            Constraint::MultiplicityConstraint(MultiplicityConstraint::Or(constraints)) => {
+                (constraints, " or ")
+            }
+            Constraint::MultiplicityConstraint(MultiplicityConstraint::Xone(constraints)) => {
+                let constraints = constraints
+                    .iter()
+                    .map(|constraint| self.text(constraint, false))
+                    .collect::<Vec<_>>();
+                return format!("exactly one of ({})", constraints.join(", "));
+            }
+        };
+
+        let text = constraints
+            .iter()
+            .map(|constraint| self.text(constraint, false))
+            .collect::<Vec<_>>()
+            .join(separator);
+        if top_level || constraints.len() < 2 {
+            text
+        } else {
+            format!("({text})")
+        }
+    }
+
+    fn markdown_rule(
+        &self,
+        markdown: &mut String,
+        action: &Action,
+        verb: &str,
+        constraints: &[Constraint],
+    ) {
+        let action = capitalize(&humanize(local_name(action.id())));
+        if constraints.is_empty() {
+            let _ = writeln!(markdown, "- **{action}** {verb}");
+            return;
+        }
+        let _ = writeln!(markdown, "- **{action}** {verb} if:");
+        for constraint in constraints {
+            self.markdown_constraint(markdown, constraint, 1);
+        }
+    }
+
+    fn markdown_constraint(&self, markdown: &mut String, constraint: &Constraint, depth: usize) {
+        let indent = "  ".repeat(depth);
+        let (constraints, title) = match constraint {
+            Constraint::Atomic(atomic) => {
+                let _ = writeln!(markdown, "{indent}- {}", self.atomic(atomic, true));
+                return;
+            }
+            Constraint::MultiplicityConstraint(MultiplicityConstraint::And(constraints)) => {
+                (constraints, "all of")
+            }
+            Constraint::MultiplicityConstraint(MultiplicityConstraint::Or(constraints)) => {
+                (constraints, "any of")
+            }
+            Constraint::MultiplicityConstraint(MultiplicityConstraint::Xone(constraints)) => {
+                (constraints, "exactly one of")
+            }
+        };
+
+        let _ = writeln!(markdown, "{indent}- {title}:");
+        for constraint in constraints {
+            self.markdown_constraint(markdown, constraint, depth + 1);
+        }
+    }
+
+    fn atomic(&self, constraint: &AtomicConstraint, code: bool) -> String {
+        let left_operand = local_name(constraint.left_operand.get_id());
+        let operator = local_name(constraint.operator.get_id());
+        let right_operand = &constraint.right_operand.0;
+
+        if let Some(translation) = self.translations.get(left_operand) {
+            return translation(operator, right_operand);
+        }
+
+        let label = self
+            .labels
+            .get(left_operand)
+            .cloned()
+            .unwrap_or_else(|| humanize(left_operand));
+        let value = value(right_operand);
+        let value = if code { format!("`{value}`") } else { value };
+
+        format!("{label} {} {value}", operator_phrase(operator))
+    }
+}
+
+fn operator_phrase(operator: &str) -> &str {
+    match operator {
+        "eq" => "equals",
+        "neq" => "does not equal",
+        "gt" => "is greater than",
+        "gteq" => "is at least",
+        "lt" => "is less than",
+        "lteq" => "is at most",
+        "isA" => "is a",
+        "hasPart" => "contains",
+        "isPartOf" => "is part of",
+        "isAllOf" => "includes all of",
+        "isAnyOf" => "is one of",
+        "isNoneOf" => "is none of",
+        operator => operator,
+    }
+}
+
+fn value(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        Value::Array(values) => {
+            let values = values.iter().map(self::value).collect::<Vec<_>>();
+            format!("[{}]", values.join(", "))
+        }
+        Value::Object(object) if object.len() == 1 => object
+            .get("@value")
+            .or_else(|| object.get("@id"))
+            .map(self::value)
+            .unwrap_or_else(|| value.to_string()),
+        value => value.to_string(),
+    }
+}
+
+/// Splits a camel case name into lowercase words, keeping acronyms.
+fn humanize(name: &str) -> String {
+    let mut words = String::new();
+    let chars = name.chars().collect::<Vec<_>>();
+    for (index, c) in chars.iter().enumerate() {
+        let boundary = index > 0
+            && c.is_uppercase()
+            && (chars[index - 1].is_lowercase()
+                || chars.get(index + 1).is_some_and(|next| next.is_lowercase()));
+        if boundary {
+            words.push(' ');
+        }
+        words.push(*c);
+    }
+    words
+        .split(' ')
+        .map(|word| {
+            if word.chars().all(char::is_uppercase) && word.len() > 1 {
+                word.to_string()
+            } else {
+                word.to_lowercase()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use crate::{
+        policy,
+        types::policy::evaluation::{EvaluationContext, PolicyEvaluator},
+    };
+
+    use super::*;
+
+    #[test]
+    fn should_render_text() {
+        let policy = policy! {
+            permission(use) {
+                "odrl:purpose" eq "research";
+                or {
+                    "region" eq "eu";
+                    and { "region" eq "us"; "dateTime" lt "2025-01-01" }
+                };
+            }
+            prohibition(grantUse) {}
+            obligation(delete) {
+                xone { "https://w3id.org/edc/v0.0.1/ns/inForceDate" gteq "contractAgreement+30d"; "count" gt 5 }
+            }
+        };
+
+        assert_eq!(
+            PolicyRenderer::default().render(&policy),
+            "Use is permitted if purpose equals research and (region equals eu or (region equals us and the date is less than 2025-01-01)).\n\
+             Grant use is prohibited.\n\
+             Delete is required if exactly one of (the contract date is at least contractAgreement+30d, the usage count is greater than 5)."
+        );
+    }
+
+    #[test]
+    fn should_render_empty_policy_as_the_evaluator_permits_it() {
+        let policy = Policy::builder().build();
+
+        assert!(PolicyEvaluator::default()
+            .evaluate(&policy, &EvaluationContext::default())
+            .is_permitted());
+        assert_eq!(
+            PolicyRenderer::default().render(&policy),
+            "No restrictions."
+        );
+        assert_eq!(
+            PolicyRenderer::builder()
+                .format(RenderFormat::Markdown)
+                .build()
+                .render(&policy),
+            "- No restrictions\n"
+        );
+    }
+
+    #[test]
+    fn should_render_markdown_with_translations() {
+        let policy = policy! {
+            permission(use) {
+                "cx-policy:Membership" eq "active";
+                or { "region" eq "eu"; "MaxDataAgeDays" lteq 30 };
+            }
+        };
+
+        let renderer = PolicyRenderer::builder()
+            .format(RenderFormat::Markdown)
+            .label("region", "the consumer region")
+            .translation("Membership", |_, value: &Value| {
+                format!(
+                    "the consumer is an {} Catena-X member",
+                    value.as_str().unwrap_or_default()
+                )
+            })
+            .build();
+
+        assert_eq!(
+            renderer.render(&policy),
+            "- **Use** is permitted if:\n  \
+               - the consumer is an active Catena-X member\n  \
+               - any of:\n    \
+                 - the consumer region equals `eu`\n    \
+                 - max data age days is at most `30`\n"
+        );
+    }
+}
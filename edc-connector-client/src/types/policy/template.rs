@@ -0,0 +1,338 @@
+//! Ready-made policies for common cases, and the [`policy!`](crate::policy)
+//! and [`policy_definition!`](crate::policy_definition) macros for writing
+//! the others.
+//!
+//! ```rust
+//! use edc_connector_client::{policy, types::policy::template};
+//!
+//! let policy = policy! {
+//!     permission(use) {
+//!         "purpose" eq "research";
+//!         or {
+//!             "BusinessPartnerNumber" eq "BPNL000000000001";
+//!             "BusinessPartnerNumber" eq "BPNL000000000002";
+//!         };
+//!     }
+//!     prohibition(distribute) {}
+//! };
+//!
+//! assert_eq!(policy.permissions()[0].constraints().len(), 2);
+//! assert_eq!(template::allow_all().permissions().len(), 1);
+//! ```
+
+use super::{
+    vocabulary::{ActionKind, LeftOperandKind, OperatorKind, Term},
+    Action, AtomicConstraint, Constraint, Permission, Policy,
+};
+
+/// Permits the use without constraints.
+pub fn allow_all() -> Policy {
+    Policy::builder()
+        .permission(Permission::builder().build())
+        .build()
+}
+
+/// Permits the use to the given participants, with the Catena-X
+/// `BusinessPartnerNumber` left operand.
+pub fn restrict_to_participants<I, S>(participant_ids: I) -> Policy
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let participant_ids = participant_ids
+        .into_iter()
+        .map(Into::into)
+        .collect::<Vec<String>>();
+
+    permit(vec![constraint(
+        LeftOperandKind::BusinessPartnerNumber,
+        OperatorKind::IsAnyOf,
+        participant_ids,
+    )])
+}
+
+/// Permits the use between the given dates, with the EDC `inForceDate` left
+/// operand. Dates are ISO 8601 or relative to the agreement, like
+/// `contractAgreement+30d`.
+pub fn in_force(from: Option<&str>, until: Option<&str>) -> Policy {
+    let from = from.map(|from| constraint(LeftOperandKind::InForceDate, OperatorKind::Gteq, from));
+    let until =
+        until.map(|until| constraint(LeftOperandKind::InForceDate, OperatorKind::Lteq, until));
+
+    permit(from.into_iter().chain(until).collect())
+}
+
+/// Permits the use for the given purpose.
+pub fn purpose(purpose: &str) -> Policy {
+    permit(vec![constraint(
+        LeftOperandKind::Purpose,
+        OperatorKind::Eq,
+        purpose,
+    )])
+}
+
+/// Permits the use at most `max` times.
+pub fn usage_count(max: u64) -> Policy {
+    permit(vec![constraint(
+        LeftOperandKind::Count,
+        OperatorKind::Lteq,
+        max,
+    )])
+}
+
+fn permit(constraints: Vec<Constraint>) -> Policy {
+    Policy::builder()
+        .permission(Permission::builder().constraints(constraints).build())
+        .build()
+}
+
+fn constraint<T: super::ToValue>(
+    left_operand: LeftOperandKind,
+    operator: OperatorKind,
+    right_operand: T,
+) -> Constraint {
+    AtomicConstraint::new_with_operator(left_operand, operator.into(), right_operand).into()
+}
+
+/// The action of the [`policy!`](crate::policy) macro, as IRI when part of the
+/// vocabulary.
+#[doc(hidden)]
+pub fn action(action: &str) -> Action {
+    ActionKind::parse(action)
+        .map(Action::from)
+        .unwrap_or_else(|| Action::simple(action))
+}
+
+/// Builds a [`Policy`] from its rules.
+///
+/// Each rule has an action and a list of constraints separated by `;`. A
+/// constraint is a left operand, an operator and a right operand, any JSON
+/// value, or an `and`, `or` or `xone` block of constraints.
+///
+/// ```rust
+/// use edc_connector_client::policy;
+///
+/// let limit = 10;
+/// let policy = policy! {
+///     permission(use) {
+///         "count" lteq limit;
+///         xone { "region" eq "eu"; "region" isAnyOf ["us", "ca"] };
+///     }
+///     obligation(attribute) {}
+/// };
+///
+/// assert_eq!(policy.obligations().len(), 1);
+/// ```
+#[macro_export]
+macro_rules! policy {
+    (@rules $builder:expr;) => {
+        $builder.build()
+    };
+    (@rules $builder:expr; $rule:ident($action:ident) { $($constraints:tt)* } $($rest:tt)*) => {
+        $crate::policy!(@rules $crate::policy!(@rule $builder, $rule, $action, $($constraints)*); $($rest)*)
+    };
+    (@rule $builder:expr, permission, $action:ident, $($constraints:tt)*) => {
+        $builder.permission(
+            $crate::types::policy::Permission::builder()
+                .action($crate::types::policy::template::action(stringify!($action)))
+                .constraints($crate::policy!(@constraints [] $($constraints)*))
+                .build(),
+        )
+    };
+    (@rule $builder:expr, prohibition, $action:ident, $($constraints:tt)*) => {
+        $builder.prohibition(
+            $crate::types::policy::Prohibition::builder()
+                .action($crate::types::policy::template::action(stringify!($action)))
+                .constraints($crate::policy!(@constraints [] $($constraints)*))
+                .build(),
+        )
+    };
+    (@rule $builder:expr, obligation, $action:ident, $($constraints:tt)*) => {
+        $builder.obligation(
+            $crate::types::policy::Obligation::builder()
+                .action($crate::types::policy::template::action(stringify!($action)))
+                .constraints($crate::policy!(@constraints [] $($constraints)*))
+                .build(),
+        )
+    };
+    (@constraints [$($out:expr),*]) => {
+        vec![$($out),*]
+    };
+    (@constraints [$($out:expr),*] $kind:ident { $($inner:tt)* } $(; $($rest:tt)*)?) => {
+        $crate::policy!(@constraints [
+            $($out,)*
+            $crate::types::policy::Constraint::$kind($crate::policy!(@constraints [] $($inner)*))
+        ] $($($rest)*)?)
+    };
+    (@constraints [$($out:expr),*] $left:literal $operator:ident $right:tt $(; $($rest:tt)*)?) => {
+        $crate::policy!(@constraints [
+            $($out,)*
+            $crate::types::policy::Constraint::atomic(
+                $crate::types::policy::AtomicConstraint::new_with_operator(
+                    $crate::types::policy::LeftOperand::simple($left),
+                    $crate::types::policy::Operator::simple(stringify!($operator)),
+                    $crate::serde_json::json!($right),
+                )
+            )
+        ] $($($rest)*)?)
+    };
+    ($($rules:tt)*) => {
+        $crate::policy!(@rules $crate::types::policy::Policy::builder(); $($rules)*)
+    };
+}
+
+/// Builds a [`NewPolicyDefinition`](super::NewPolicyDefinition) with the
+/// given id and the rules of [`policy!`](crate::policy).
+///
+/// ```rust
+/// use edc_connector_client::policy_definition;
+///
+/// let definition = policy_definition!("purpose-research", permission(use) {
+///     "purpose" eq "research";
+/// });
+///
+/// assert_eq!(definition.id(), Some("purpose-research"));
+/// ```
+#[macro_export]
+macro_rules! policy_definition {
+    ($id:expr, $($rules:tt)*) => {
+        $crate::types::policy::NewPolicyDefinition::builder()
+            .id($id)
+            .policy($crate::policy!($($rules)*))
+            .build()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use crate::types::policy::{NewPolicyDefinition, Policy};
+
+    use super::*;
+
+    fn assert_round_trip(policy: &Policy, expected: Value) {
+        let serialized = serde_json::to_value(policy).unwrap();
+        assert_eq!(serialized, expected);
+        assert_eq!(
+            &serde_json::from_value::<Policy>(serialized).unwrap(),
+            policy
+        );
+    }
+
+    fn permission(constraints: Value) -> Value {
+        json!({
+            "@type": "Set",
+            "permission": [{
+                "constraint": constraints,
+                "action": { "@id": "http://www.w3.org/ns/odrl/2/use" }
+            }]
+        })
+    }
+
+    fn atomic(left_operand: &str, operator: &str, right_operand: Value) -> Value {
+        json!({
+            "leftOperand": { "@id": left_operand },
+            "operator": { "@id": format!("http://www.w3.org/ns/odrl/2/{operator}") },
+            "rightOperand": right_operand
+        })
+    }
+
+    #[test]
+    fn should_round_trip_templates() {
+        assert_round_trip(&allow_all(), permission(json!([])));
+        assert_round_trip(
+            &restrict_to_participants(["BPNL1", "BPNL2"]),
+            permission(json!([atomic(
+                "https://w3id.org/catenax/policy/BusinessPartnerNumber",
+                "isAnyOf",
+                json!(["BPNL1", "BPNL2"])
+            )])),
+        );
+        assert_round_trip(
+            &in_force(Some("2024-01-01T00:00:00Z"), Some("contractAgreement+30d")),
+            permission(json!([
+                atomic(
+                    "https://w3id.org/edc/v0.0.1/ns/inForceDate",
+                    "gteq",
+                    json!("2024-01-01T00:00:00Z")
+                ),
+                atomic(
+                    "https://w3id.org/edc/v0.0.1/ns/inForceDate",
+                    "lteq",
+                    json!("contractAgreement+30d")
+                ),
+            ])),
+        );
+        assert_round_trip(
+            &purpose("research"),
+            permission(json!([atomic(
+                "http://www.w3.org/ns/odrl/2/purpose",
+                "eq",
+                json!("research")
+            )])),
+        );
+        assert_round_trip(
+            &usage_count(5),
+            permission(json!([atomic(
+                "http://www.w3.org/ns/odrl/2/count",
+                "lteq",
+                json!(5)
+            )])),
+        );
+    }
+
+    #[test]
+    fn should_round_trip_macro() {
+        let regions = vec!["us", "ca"];
+        let policy = crate::policy! {
+            permission(use) {
+                "purpose" eq "research";
+                or {
+                    "region" eq "eu";
+                    "region" isAnyOf regions
+                };
+            }
+            prohibition(distribute) {}
+            obligation(customAction) {
+                "count" lteq 1
+            }
+        };
+
+        assert_round_trip(
+            &policy,
+            json!({
+                "@type": "Set",
+                "permission": [{
+                    "constraint": [
+                        { "leftOperand": "purpose", "operator": "eq", "rightOperand": "research" },
+                        { "or": [
+                            { "leftOperand": "region", "operator": "eq", "rightOperand": "eu" },
+                            { "leftOperand": "region", "operator": "isAnyOf", "rightOperand": ["us", "ca"] }
+                        ]}
+                    ],
+                    "action": { "@id": "http://www.w3.org/ns/odrl/2/use" }
+                }],
+                "prohibition": [{
+                    "constraint": [],
+                    "action": { "@id": "http://www.w3.org/ns/odrl/2/distribute" }
+                }],
+                "obligation": [{
+                    "constraint": [
+                        { "leftOperand": "count", "operator": "lteq", "rightOperand": 1 }
+                    ],
+                    "action": "customAction"
+                }]
+            }),
+        );
+
+        let definition: NewPolicyDefinition =
+            crate::policy_definition!("policy-1", permission(use) {});
+        let serialized = serde_json::to_value(&definition).unwrap();
+        assert_eq!(serialized["@id"], json!("policy-1"));
+        assert_eq!(
+            serde_json::from_value::<Policy>(serialized["policy"].clone()).unwrap(),
+            allow_all()
+        );
+    }
+}
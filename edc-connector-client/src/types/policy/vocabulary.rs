@@ -0,0 +1,389 @@
+//! Typed terms of the ODRL core vocabulary and of common EDC and Catena-X
+//! left operands.
+//!
+//! Terms serialize to their compact form (`use`, `eq`, `cx-policy:Membership`)
+//! and to their IRI with [`iri`], and deserialize from the compact, prefixed or
+//! IRI form, as string or `@id` object.
+//!
+//! ```rust
+//! use edc_connector_client::types::policy::{
+//!     vocabulary::{ActionKind, LeftOperandKind, OperatorKind, Term},
+//!     Action, AtomicConstraint, Permission,
+//! };
+//!
+//! let permission = Permission::builder()
+//!     .action(ActionKind::Use.into())
+//!     .constraint(
+//!         AtomicConstraint::new_with_operator(
+//!             LeftOperandKind::Membership,
+//!             OperatorKind::Eq.into(),
+//!             "active",
+//!         )
+//!         .into(),
+//!     )
+//!     .build();
+//!
+//! assert_eq!(permission.action(), &Action::default());
+//! assert_eq!(
+//!     ActionKind::parse("odrl:use").map(|action| action.iri()),
+//!     Some("http://www.w3.org/ns/odrl/2/use")
+//! );
+//! ```
+
+use std::{fmt, str::FromStr};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{Action, AtomicConstraint, Constraint, LeftOperand, Operator};
+
+/// A term of a vocabulary.
+pub trait Term: Sized + 'static {
+    /// The form used in policies written with the EDC context, e.g. `use`.
+    fn compact(&self) -> &'static str;
+
+    fn iri(&self) -> &'static str;
+
+    /// Parses the compact, prefixed or IRI form of the term.
+    fn parse(term: &str) -> Option<Self>;
+}
+
+/// Declares a vocabulary enum. The terms are grouped by namespace, with the
+/// prefix of the namespace and the prefix of their compact form.
+macro_rules! vocabulary {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $(
+                $namespace:literal as $prefix:literal, compact $compact:literal => {
+                    $($variant:ident => $term:literal),* $(,)?
+                }
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($($variant,)*)*
+        }
+
+        impl $name {
+            pub const ALL: &'static [$name] = &[$($($name::$variant,)*)*];
+        }
+
+        impl Term for $name {
+            fn compact(&self) -> &'static str {
+                match self {
+                    $($($name::$variant => concat!($compact, $term),)*)*
+                }
+            }
+
+            fn iri(&self) -> &'static str {
+                match self {
+                    $($($name::$variant => concat!($namespace, $term),)*)*
+                }
+            }
+
+            fn parse(term: &str) -> Option<Self> {
+                match term {
+                    $($(
+                        concat!($compact, $term)
+                        | concat!($prefix, ":", $term)
+                        | concat!($namespace, $term) => Some($name::$variant),
+                    )*)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.compact())
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = UnknownTerm;
+
+            fn from_str(term: &str) -> Result<Self, Self::Err> {
+                Self::parse(term).ok_or_else(|| UnknownTerm(term.to_string()))
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.compact())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserialize_term(deserializer)
+            }
+        }
+    };
+}
+
+vocabulary! {
+    pub enum ActionKind {
+        "http://www.w3.org/ns/odrl/2/" as "odrl", compact "" => {
+            Use => "use",
+            Transfer => "transfer",
+            Distribute => "distribute",
+            Reproduce => "reproduce",
+            Read => "read",
+            Modify => "modify",
+            Delete => "delete",
+            Display => "display",
+            Play => "play",
+            Print => "print",
+            Execute => "execute",
+            Archive => "archive",
+            Aggregate => "aggregate",
+            Anonymize => "anonymize",
+            Derive => "derive",
+            Digitize => "digitize",
+            Index => "index",
+            Install => "install",
+            Present => "present",
+            Sell => "sell",
+            Give => "give",
+            GrantUse => "grantUse",
+            Translate => "translate",
+            Attribute => "attribute",
+            Compensate => "compensate",
+            Inform => "inform",
+            ObtainConsent => "obtainConsent",
+            NextPolicy => "nextPolicy",
+            Watermark => "watermark",
+        }
+    }
+}
+
+vocabulary! {
+    pub enum OperatorKind {
+        "http://www.w3.org/ns/odrl/2/" as "odrl", compact "" => {
+            Eq => "eq",
+            Neq => "neq",
+            Lt => "lt",
+            Lteq => "lteq",
+            Gt => "gt",
+            Gteq => "gteq",
+            IsA => "isA",
+            HasPart => "hasPart",
+            IsPartOf => "isPartOf",
+            IsAllOf => "isAllOf",
+            IsAnyOf => "isAnyOf",
+            IsNoneOf => "isNoneOf",
+        }
+    }
+}
+
+vocabulary! {
+    pub enum LeftOperandKind {
+        "http://www.w3.org/ns/odrl/2/" as "odrl", compact "" => {
+            AbsolutePosition => "absolutePosition",
+            AbsoluteSize => "absoluteSize",
+            AbsoluteSpatialPosition => "absoluteSpatialPosition",
+            AbsoluteTemporalPosition => "absoluteTemporalPosition",
+            Count => "count",
+            DateTime => "dateTime",
+            DelayPeriod => "delayPeriod",
+            DeliveryChannel => "deliveryChannel",
+            Device => "device",
+            ElapsedTime => "elapsedTime",
+            Event => "event",
+            FileFormat => "fileFormat",
+            Industry => "industry",
+            Language => "language",
+            Media => "media",
+            MeteredTime => "meteredTime",
+            PayAmount => "payAmount",
+            Percentage => "percentage",
+            Product => "product",
+            Purpose => "purpose",
+            Recipient => "recipient",
+            RelativePosition => "relativePosition",
+            RelativeSize => "relativeSize",
+            RelativeSpatialPosition => "relativeSpatialPosition",
+            RelativeTemporalPosition => "relativeTemporalPosition",
+            Resolution => "resolution",
+            Spatial => "spatial",
+            SpatialCoordinates => "spatialCoordinates",
+            System => "system",
+            SystemDevice => "systemDevice",
+            TimeInterval => "timeInterval",
+            UnitOfCount => "unitOfCount",
+            Version => "version",
+            VirtualLocation => "virtualLocation",
+        }
+        "https://w3id.org/edc/v0.0.1/ns/" as "edc", compact "" => {
+            InForceDate => "inForceDate",
+        }
+        "https://w3id.org/catenax/policy/" as "cx-policy", compact "cx-policy:" => {
+            FrameworkAgreement => "FrameworkAgreement",
+            Membership => "Membership",
+            UsagePurpose => "UsagePurpose",
+            BusinessPartnerGroup => "BusinessPartnerGroup",
+            BusinessPartnerNumber => "BusinessPartnerNumber",
+            ContractReference => "ContractReference",
+            AffiliatesRegion => "AffiliatesRegion",
+            AffiliatesBpnl => "AffiliatesBpnl",
+            DataFrequency => "DataFrequency",
+            DataProvisioningEndDate => "DataProvisioningEndDate",
+            DataProvisioningEndDurationDays => "DataProvisioningEndDurationDays",
+            DataUsageEndDate => "DataUsageEndDate",
+            DataUsageEndDurationDays => "DataUsageEndDurationDays",
+            DataUsageEndDefinition => "DataUsageEndDefinition",
+            ContractTermination => "ContractTermination",
+            ConfidentialInformationMeasures => "ConfidentialInformationMeasures",
+            ConfidentialInformationSharing => "ConfidentialInformationSharing",
+            ExclusiveUsage => "ExclusiveUsage",
+            Warranty => "Warranty",
+            WarrantyDefinition => "WarrantyDefinition",
+            WarrantyDurationMonths => "WarrantyDurationMonths",
+            Liability => "Liability",
+            Jurisdiction => "Jurisdiction",
+            Precedence => "Precedence",
+            VersionChanges => "VersionChanges",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unknown term {0}")]
+pub struct UnknownTerm(pub String);
+
+fn deserialize_term<'de, T: Term, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Term(String),
+        Id {
+            #[serde(rename = "@id")]
+            id: String,
+        },
+    }
+
+    let term = match Raw::deserialize(deserializer)? {
+        Raw::Term(term) => term,
+        Raw::Id { id } => id,
+    };
+    T::parse(&term).ok_or_else(|| de::Error::custom(UnknownTerm(term)))
+}
+
+/// Serializes terms as IRIs, to be used with `#[serde(with = "iri")]`.
+pub mod iri {
+    use serde::{Deserializer, Serializer};
+
+    use super::Term;
+
+    pub fn serialize<T: Term, S: Serializer>(term: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(term.iri())
+    }
+
+    pub fn deserialize<'de, T: Term, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        super::deserialize_term(deserializer)
+    }
+}
+
+impl From<ActionKind> for Action {
+    fn from(action: ActionKind) -> Self {
+        Action::new(action.iri().to_string())
+    }
+}
+
+impl From<OperatorKind> for Operator {
+    fn from(operator: OperatorKind) -> Self {
+        Operator::id(operator.iri())
+    }
+}
+
+impl From<LeftOperandKind> for LeftOperand {
+    fn from(left_operand: LeftOperandKind) -> Self {
+        LeftOperand::id(left_operand.iri())
+    }
+}
+
+impl From<AtomicConstraint> for Constraint {
+    fn from(constraint: AtomicConstraint) -> Self {
+        Constraint::Atomic(constraint)
+    }
+}
+
+impl Action {
+    /// The typed action, if part of the vocabulary.
+    pub fn kind(&self) -> Option<ActionKind> {
+        ActionKind::parse(self.id())
+    }
+}
+
+impl Operator {
+    /// The typed operator, if part of the vocabulary.
+    pub fn kind(&self) -> Option<OperatorKind> {
+        OperatorKind::parse(self.get_id())
+    }
+}
+
+impl LeftOperand {
+    /// The typed left operand, if part of the vocabulary.
+    pub fn kind(&self) -> Option<LeftOperandKind> {
+        LeftOperandKind::parse(self.get_id())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{de::DeserializeOwned, Deserialize, Serialize};
+    use serde_json::json;
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    struct Iri<T: Term>(#[serde(with = "iri")] T);
+
+    fn round_trip<T>(terms: &[T])
+    where
+        T: Term + Serialize + DeserializeOwned + PartialEq + fmt::Debug + Copy,
+    {
+        for term in terms {
+            let compact = serde_json::to_value(term).unwrap();
+            assert_eq!(compact, json!(term.compact()));
+            assert_eq!(serde_json::from_value::<T>(compact).unwrap(), *term);
+
+            let iri = serde_json::to_value(Iri(*term)).unwrap();
+            assert_eq!(iri, json!(term.iri()));
+            assert_eq!(serde_json::from_value::<Iri<T>>(iri).unwrap().0, *term);
+
+            let id = json!({ "@id": term.iri() });
+            assert_eq!(serde_json::from_value::<T>(id).unwrap(), *term);
+        }
+    }
+
+    #[test]
+    fn should_round_trip_compact_and_iri_forms() {
+        round_trip(ActionKind::ALL);
+        round_trip(OperatorKind::ALL);
+        round_trip(LeftOperandKind::ALL);
+    }
+
+    #[test]
+    fn should_parse_prefixed_terms() {
+        assert_eq!(
+            OperatorKind::parse("odrl:isAnyOf"),
+            Some(OperatorKind::IsAnyOf)
+        );
+        assert_eq!(
+            "edc:inForceDate".parse::<LeftOperandKind>(),
+            Ok(LeftOperandKind::InForceDate)
+        );
+        assert_eq!(
+            LeftOperandKind::parse("cx-policy:Membership"),
+            Some(LeftOperandKind::Membership)
+        );
+        assert_eq!(LeftOperandKind::parse("Membership"), None);
+        assert!(serde_json::from_value::<ActionKind>(json!("odrl:fly")).is_err());
+
+        assert_eq!(Action::default().kind(), Some(ActionKind::Use));
+        assert_eq!(Operator::simple("gteq").kind(), Some(OperatorKind::Gteq));
+    }
+}
@@ -64,3 +64,27 @@ where
         Value::Array(values)
     }
 }
+
+impl ToValue for Value {
+    fn into_value(self) -> Value {
+        self
+    }
+}
+
+impl ToValue for bool {
+    fn into_value(self) -> Value {
+        Value::Bool(self)
+    }
+}
+
+impl ToValue for i64 {
+    fn into_value(self) -> Value {
+        Value::from(self)
+    }
+}
+
+impl ToValue for u64 {
+    fn into_value(self) -> Value {
+        Value::from(self)
+    }
+}
@@ -1,7 +1,14 @@
+use std::{borrow::Cow, fmt, str::FromStr};
+
+use bon::Builder;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use super::properties::{PropertyValue, ToValue};
 
+pub mod fields;
+pub(crate) mod matching;
+
 #[derive(Serialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Query {
@@ -43,21 +50,28 @@ impl Query {
 pub struct QueryBuilder(Query);
 
 impl QueryBuilder {
-    pub fn filter<T>(mut self, left: &str, operator: &str, right: T) -> Self
+    pub fn filter<T>(
+        mut self,
+        left: impl Into<Field>,
+        operator: impl Into<Operator>,
+        right: T,
+    ) -> Self
     where
         T: ToValue,
     {
-        self.0.filter_expression.push(Criterion {
-            ty: "Criterion".to_string(),
-            operand_left: left.to_string(),
-            operator: operator.to_string(),
-            operand_right: PropertyValue(right.into_value()),
-        });
+        self.0
+            .filter_expression
+            .push(Criterion::new(left, operator, right));
         self
     }
 
-    pub fn sort(mut self, field: &str, order: SortOrder) -> Self {
-        self.0.sort = Some(Sort::new(field.to_owned(), order));
+    pub fn criterion(mut self, criterion: Criterion) -> Self {
+        self.0.filter_expression.push(criterion);
+        self
+    }
+
+    pub fn sort(mut self, field: impl Into<Field>, order: SortOrder) -> Self {
+        self.0.sort = Some(Sort::new(field.into().as_str().to_owned(), order));
         self
     }
 
@@ -116,11 +130,15 @@ pub struct Criterion {
 }
 
 impl Criterion {
-    pub fn new<T: ToValue>(operand_left: &str, operator: &str, operand_right: T) -> Self {
+    pub fn new<T: ToValue>(
+        operand_left: impl Into<Field>,
+        operator: impl Into<Operator>,
+        operand_right: T,
+    ) -> Self {
         Self {
             ty: "Criterion".to_string(),
-            operand_left: operand_left.to_string(),
-            operator: operator.to_string(),
+            operand_left: operand_left.into().as_str().to_string(),
+            operator: operator.into().as_str().to_string(),
             operand_right: PropertyValue(operand_right.into_value()),
         }
     }
@@ -133,9 +151,160 @@ impl Criterion {
         &self.operator
     }
 
+    /// The operator, as [`Operator::Custom`] when outside the enum.
+    pub fn typed_operator(&self) -> Operator {
+        Operator::from(self.operator.as_str())
+    }
+
     pub fn operand_right(&self) -> &PropertyValue {
         &self.operand_right
     }
+
+    /// Evaluates the criterion locally against a JSON entity, like the
+    /// connector does on its stores.
+    ///
+    /// The left operand is a path whose segments are separated by `.`, quoted
+    /// with `'` when they contain dots, and an unqualified segment also matches
+    /// the same property in the EDC namespace.
+    pub fn matches(&self, entity: &Value) -> bool {
+        matching::matches(
+            entity,
+            &self.operand_left,
+            &self.operator,
+            &self.operand_right.0,
+        )
+    }
+}
+
+/// Operators supported by the connector when evaluating a [`Criterion`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operator {
+    Eq,
+    Neq,
+    In,
+    Like,
+    Ilike,
+    Contains,
+    Lt,
+    Leq,
+    Gt,
+    Geq,
+    /// Escape hatch for operators registered by connector extensions.
+    Custom(String),
+}
+
+impl Operator {
+    /// The operator of the enum written as `op`, [`Operator::Custom`] being
+    /// never returned.
+    pub fn parse(op: &str) -> Option<Operator> {
+        let op = match op {
+            "=" => Operator::Eq,
+            "!=" => Operator::Neq,
+            "in" => Operator::In,
+            "like" => Operator::Like,
+            "ilike" => Operator::Ilike,
+            "contains" => Operator::Contains,
+            "<" => Operator::Lt,
+            "<=" => Operator::Leq,
+            ">" => Operator::Gt,
+            ">=" => Operator::Geq,
+            _ => return None,
+        };
+        Some(op)
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Operator::Eq => "=",
+            Operator::Neq => "!=",
+            Operator::In => "in",
+            Operator::Like => "like",
+            Operator::Ilike => "ilike",
+            Operator::Contains => "contains",
+            Operator::Lt => "<",
+            Operator::Leq => "<=",
+            Operator::Gt => ">",
+            Operator::Geq => ">=",
+            Operator::Custom(op) => op,
+        }
+    }
+}
+
+impl From<&str> for Operator {
+    /// Converts an operator like `"="`, any unknown one being taken as
+    /// [`Operator::Custom`]. Use [`str::parse`] to reject unknown operators.
+    fn from(op: &str) -> Self {
+        Operator::parse(op).unwrap_or_else(|| Operator::Custom(op.to_string()))
+    }
+}
+
+impl FromStr for Operator {
+    type Err = UnknownOperator;
+
+    fn from_str(op: &str) -> Result<Self, Self::Err> {
+        Operator::parse(op).ok_or_else(|| UnknownOperator(op.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unknown operator {0}")]
+pub struct UnknownOperator(pub String);
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Path of an entity field used as left operand of a [`Criterion`] or as sort field.
+///
+/// Typed paths for each entity are available in [`fields`], while raw strings
+/// can be converted for custom properties.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field(Cow<'static, str>);
+
+impl Field {
+    pub const fn new(path: &'static str) -> Field {
+        Field(Cow::Borrowed(path))
+    }
+
+    pub fn raw(path: impl Into<String>) -> Field {
+        Field(Cow::Owned(path.into()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for Field {
+    fn from(path: &str) -> Self {
+        Field::raw(path)
+    }
+}
+
+impl From<&String> for Field {
+    fn from(path: &String) -> Self {
+        Field::raw(path.as_str())
+    }
+}
+
+impl From<String> for Field {
+    fn from(path: String) -> Self {
+        Field::raw(path)
+    }
+}
+
+impl From<&Field> for Field {
+    fn from(field: &Field) -> Self {
+        field.clone()
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 impl Default for Query {
@@ -149,3 +318,117 @@ impl Default for Query {
         }
     }
 }
+
+/// Options for walking all the pages of a query.
+#[derive(Debug, Clone, Builder)]
+pub struct Pagination {
+    /// Number of pages fetched concurrently.
+    #[builder(default = 1)]
+    concurrency: usize,
+    /// Maximum number of items yielded.
+    max_items: Option<usize>,
+}
+
+impl Pagination {
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
+    pub fn max_items(&self) -> Option<usize> {
+        self.max_items
+    }
+}
+
+impl Default for Pagination {
+    fn default() -> Self {
+        Pagination::builder().build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{fields, Criterion, Operator, Query, SortOrder, UnknownOperator};
+
+    #[test]
+    fn should_serialize_typed_criterion() {
+        let criterion = Criterion::new(fields::transfer_process::ASSET_ID, Operator::Eq, "asset");
+
+        assert_eq!(
+            serde_json::to_value(&criterion).unwrap(),
+            json!({
+                "@type": "Criterion",
+                "operandLeft": "assetId",
+                "operator": "=",
+                "operandRight": "asset"
+            })
+        );
+    }
+
+    #[test]
+    fn should_build_query_with_typed_and_raw_fields() {
+        let query = Query::builder()
+            .filter(fields::asset::property("name"), Operator::Ilike, "%foo%")
+            .filter("customField", "in", vec!["a", "b"])
+            .sort(fields::asset::CREATED_AT, SortOrder::Desc)
+            .build();
+
+        let criteria = query.filter_expression();
+
+        assert_eq!(
+            criteria[0].operand_left(),
+            "https://w3id.org/edc/v0.0.1/ns/name"
+        );
+        assert_eq!(criteria[0].typed_operator(), Operator::Ilike);
+        assert_eq!(criteria[1].operand_left(), "customField");
+        assert_eq!(criteria[1].typed_operator(), Operator::In);
+        assert_eq!(query.sort().unwrap().field(), "createdAt");
+    }
+
+    #[test]
+    fn should_round_trip_operators() {
+        for op in [
+            Operator::Eq,
+            Operator::Neq,
+            Operator::In,
+            Operator::Like,
+            Operator::Ilike,
+            Operator::Contains,
+            Operator::Lt,
+            Operator::Leq,
+            Operator::Gt,
+            Operator::Geq,
+        ] {
+            assert_eq!(Operator::from(op.as_str()), op);
+        }
+
+        assert_eq!(Operator::from("=="), Operator::Custom("==".to_string()));
+        assert_eq!(
+            "==".parse::<Operator>(),
+            Err(UnknownOperator("==".to_string()))
+        );
+    }
+
+    #[test]
+    fn should_compare_numeric_strings_as_numbers() {
+        let entity = json!({ "@id": "asset", "properties": { "size": "10", "count": 10 } });
+
+        assert!(Criterion::new("size", Operator::Gt, 9_i64).matches(&entity));
+        assert!(Criterion::new("size", Operator::Leq, 10_i64).matches(&entity));
+        assert!(!Criterion::new("size", Operator::Lt, 10_i64).matches(&entity));
+        assert!(Criterion::new("count", Operator::Geq, "10").matches(&entity));
+        assert!(Criterion::new("count", Operator::Lt, "11").matches(&entity));
+    }
+
+    #[test]
+    fn should_not_match_incomparable_values() {
+        let entity = json!({ "@id": "asset", "properties": { "size": "large", "tags": ["a"] } });
+
+        for op in [Operator::Lt, Operator::Leq, Operator::Gt, Operator::Geq] {
+            assert!(!Criterion::new("size", op.clone(), 10_i64).matches(&entity));
+            assert!(!Criterion::new("tags", op.clone(), "a").matches(&entity));
+            assert!(!Criterion::new("missing", op, 10_i64).matches(&entity));
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Typed field paths of the entities exposed by the management api.
+
+use crate::EDC_NAMESPACE;
+
+use super::Field;
+
+fn namespaced(name: &str) -> String {
+    if name.contains(':') {
+        name.to_string()
+    } else {
+        format!("{}{}", EDC_NAMESPACE, name)
+    }
+}
+
+fn private_property(name: &str) -> Field {
+    Field::raw(format!("privateProperties.'{}'", namespaced(name)))
+}
+
+pub mod asset {
+    use super::{namespaced, Field};
+
+    pub const ID: Field = Field::new("id");
+    pub const CREATED_AT: Field = Field::new("createdAt");
+
+    /// Asset property, expanded with the EDC namespace when not already an IRI.
+    pub fn property(name: &str) -> Field {
+        Field::raw(namespaced(name))
+    }
+
+    pub fn private_property(name: &str) -> Field {
+        super::private_property(name)
+    }
+
+    pub fn data_address(name: &str) -> Field {
+        Field::raw(format!("dataAddress.'{}'", namespaced(name)))
+    }
+}
+
+pub mod policy_definition {
+    use super::Field;
+
+    pub const ID: Field = Field::new("id");
+    pub const CREATED_AT: Field = Field::new("createdAt");
+
+    pub fn private_property(name: &str) -> Field {
+        super::private_property(name)
+    }
+}
+
+pub mod contract_definition {
+    use super::Field;
+
+    pub const ID: Field = Field::new("id");
+    pub const CREATED_AT: Field = Field::new("createdAt");
+    pub const ACCESS_POLICY_ID: Field = Field::new("accessPolicyId");
+    pub const CONTRACT_POLICY_ID: Field = Field::new("contractPolicyId");
+
+    pub fn private_property(name: &str) -> Field {
+        super::private_property(name)
+    }
+}
+
+pub mod contract_negotiation {
+    use super::Field;
+
+    pub const ID: Field = Field::new("id");
+    pub const STATE: Field = Field::new("state");
+    pub const TYPE: Field = Field::new("type");
+    pub const COUNTER_PARTY_ID: Field = Field::new("counterPartyId");
+    pub const COUNTER_PARTY_ADDRESS: Field = Field::new("counterPartyAddress");
+    pub const CONTRACT_AGREEMENT_ID: Field = Field::new("contractAgreement.id");
+    pub const CORRELATION_ID: Field = Field::new("correlationId");
+    pub const PROTOCOL: Field = Field::new("protocol");
+    pub const CREATED_AT: Field = Field::new("createdAt");
+}
+
+pub mod contract_agreement {
+    use super::Field;
+
+    pub const ID: Field = Field::new("id");
+    pub const ASSET_ID: Field = Field::new("assetId");
+    pub const CONSUMER_ID: Field = Field::new("consumerId");
+    pub const PROVIDER_ID: Field = Field::new("providerId");
+    pub const CONTRACT_SIGNING_DATE: Field = Field::new("contractSigningDate");
+}
+
+pub mod transfer_process {
+    use super::Field;
+
+    pub const ID: Field = Field::new("id");
+    pub const STATE: Field = Field::new("state");
+    pub const TYPE: Field = Field::new("type");
+    pub const ASSET_ID: Field = Field::new("assetId");
+    pub const CONTRACT_ID: Field = Field::new("contractId");
+    pub const CORRELATION_ID: Field = Field::new("correlationId");
+    pub const TRANSFER_TYPE: Field = Field::new("transferType");
+    pub const CREATED_AT: Field = Field::new("createdAt");
+}
+
+pub mod edr {
+    use super::Field;
+
+    pub const ASSET_ID: Field = Field::new("assetId");
+    pub const AGREEMENT_ID: Field = Field::new("agreementId");
+    pub const TRANSFER_PROCESS_ID: Field = Field::new("transferProcessId");
+    pub const PROVIDER_ID: Field = Field::new("providerId");
+    pub const CONTRACT_NEGOTIATION_ID: Field = Field::new("contractNegotiationId");
+    pub const CREATED_AT: Field = Field::new("createdAt");
+}
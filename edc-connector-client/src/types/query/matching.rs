@@ -0,0 +1,140 @@
+use std::cmp::Ordering;
+
+use serde_json::Value;
+
+use crate::EDC_NAMESPACE;
+
+/// Evaluates a criterion against a JSON entity as the connector does, the
+/// left operand being a path resolved with [`resolve`].
+pub(crate) fn matches(entity: &Value, operand_left: &str, operator: &str, right: &Value) -> bool {
+    let Some(left) = resolve(entity, operand_left) else {
+        return false;
+    };
+
+    match operator.to_lowercase().as_str() {
+        "=" => equals(left, right),
+        "!=" => !equals(left, right),
+        "in" => match right {
+            Value::Array(values) => values.iter().any(|value| equals(left, value)),
+            value => equals(left, value),
+        },
+        "contains" => match left {
+            Value::Array(values) => values.iter().any(|value| equals(value, right)),
+            Value::String(value) => right.as_str().is_some_and(|r| value.contains(r)),
+            _ => false,
+        },
+        "like" => like(left, right, false),
+        "ilike" => like(left, right, true),
+        "<" => compare(left, right).is_some_and(Ordering::is_lt),
+        "<=" => compare(left, right).is_some_and(Ordering::is_le),
+        ">" => compare(left, right).is_some_and(Ordering::is_gt),
+        ">=" => compare(left, right).is_some_and(Ordering::is_ge),
+        _ => false,
+    }
+}
+
+/// Resolves a query path like `dataAddress.'https://w3id.org/edc/v0.0.1/ns/type'`,
+/// looking into the properties of assets for non qualified paths. An unquoted
+/// IRI is taken as a single segment.
+pub(crate) fn resolve<'a>(entity: &'a Value, path: &str) -> Option<&'a Value> {
+    let segments = if path.contains("://") && !path.contains('\'') {
+        vec![path.to_string()]
+    } else {
+        segments(path)
+    };
+
+    if let [segment] = segments.as_slice() {
+        if segment.strip_prefix(EDC_NAMESPACE).unwrap_or(segment) == "id" {
+            return entity.get("@id");
+        }
+    }
+
+    lookup(entity, &segments).or_else(|| {
+        entity
+            .get("properties")
+            .and_then(|properties| lookup(properties, &segments))
+    })
+}
+
+fn lookup<'a>(value: &'a Value, segments: &[String]) -> Option<&'a Value> {
+    segments.iter().try_fold(value, |current, segment| {
+        current
+            .get(segment)
+            .or_else(|| {
+                segment
+                    .strip_prefix(EDC_NAMESPACE)
+                    .and_then(|compacted| current.get(compacted))
+            })
+            .or_else(|| current.get(format!("{EDC_NAMESPACE}{segment}")))
+            .or_else(|| current.get(format!("edc:{segment}")))
+    })
+}
+
+fn segments(path: &str) -> Vec<String> {
+    let mut segments = vec![];
+    let mut current = String::new();
+    let mut quoted = false;
+
+    for c in path.chars() {
+        match c {
+            '\'' => quoted = !quoted,
+            '.' if !quoted => segments.push(std::mem::take(&mut current)),
+            c => current.push(c),
+        }
+    }
+    segments.push(current);
+    segments
+}
+
+fn equals(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::String(l), Value::Number(r)) | (Value::Number(r), Value::String(l)) => {
+            l == &r.to_string()
+        }
+        (l, r) => l == r,
+    }
+}
+
+fn like(left: &Value, right: &Value, ignore_case: bool) -> bool {
+    let (Some(value), Some(pattern)) = (left.as_str(), right.as_str()) else {
+        return false;
+    };
+
+    let (value, pattern) = if ignore_case {
+        (value.to_lowercase(), pattern.to_lowercase())
+    } else {
+        (value.to_string(), pattern.to_string())
+    };
+
+    let parts = pattern.split('%').collect::<Vec<_>>();
+    let mut rest = value.as_str();
+
+    for (idx, part) in parts.iter().enumerate() {
+        if idx == 0 {
+            let Some(stripped) = rest.strip_prefix(part) else {
+                return false;
+            };
+            rest = stripped;
+        } else if idx == parts.len() - 1 {
+            return rest.ends_with(part);
+        } else {
+            let Some(pos) = rest.find(part) else {
+                return false;
+            };
+            rest = &rest[pos + part.len()..];
+        }
+    }
+    rest.is_empty()
+}
+
+/// Orders two values, numeric strings being compared as numbers like
+/// [`equals`] does. `None` when the values can't be compared.
+fn compare(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Number(l), Value::Number(r)) => l.as_f64()?.partial_cmp(&r.as_f64()?),
+        (Value::String(l), Value::Number(r)) => l.parse::<f64>().ok()?.partial_cmp(&r.as_f64()?),
+        (Value::Number(l), Value::String(r)) => l.as_f64()?.partial_cmp(&r.parse::<f64>().ok()?),
+        (Value::String(l), Value::String(r)) => Some(l.cmp(r)),
+        _ => None,
+    }
+}
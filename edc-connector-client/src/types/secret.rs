@@ -14,6 +14,22 @@ pub struct NewSecret {
     ty: String,
 }
 
+impl NewSecret {
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+}
+
+impl From<Secret> for NewSecret {
+    fn from(secret: Secret) -> Self {
+        NewSecret {
+            id: Some(secret.id),
+            value: secret.value,
+            ty: secret.ty,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Builder)]
 #[serde(rename_all = "camelCase")]
 pub struct Secret {
@@ -44,7 +44,7 @@ impl<S: transfer_request_builder::State> TransferRequestBuilder<S> {
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TransferProcess {
     #[serde(rename = "@id")]
@@ -117,7 +117,7 @@ impl TransferProcess {
     }
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TransferProcessState {
     Initial,
@@ -143,7 +143,34 @@ pub enum TransferProcessState {
     Other(String),
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+impl TransferProcessState {
+    pub fn as_str(&self) -> &str {
+        match self {
+            TransferProcessState::Initial => "INITIAL",
+            TransferProcessState::Provisioning => "PROVISIONING",
+            TransferProcessState::ProvisioningRequested => "PROVISIONING_REQUESTED",
+            TransferProcessState::Provisioned => "PROVISIONED",
+            TransferProcessState::Requesting => "REQUESTING",
+            TransferProcessState::Requested => "REQUESTED",
+            TransferProcessState::Starting => "STARTING",
+            TransferProcessState::Started => "STARTED",
+            TransferProcessState::Suspending => "SUSPENDING",
+            TransferProcessState::Suspended => "SUSPENDED",
+            TransferProcessState::Resuming => "RESUMING",
+            TransferProcessState::Resumed => "RESUMED",
+            TransferProcessState::Completing => "COMPLETING",
+            TransferProcessState::Completed => "COMPLETED",
+            TransferProcessState::Terminating => "TERMINATING",
+            TransferProcessState::Terminated => "TERMINATED",
+            TransferProcessState::Deprovisioning => "DEPROVISIONING",
+            TransferProcessState::DeprovisioningRequested => "DEPROVISIONING_REQUESTED",
+            TransferProcessState::Deprovisioned => "DEPROVISIONED",
+            TransferProcessState::Other(state) => state,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TransferProcessKind {
     Consumer,
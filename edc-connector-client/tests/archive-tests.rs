@@ -0,0 +1,155 @@
+#[allow(clippy::unwrap_used)]
+mod archive {
+    use edc_connector_client::{
+        api::{ConflictStrategy, ExportOptions, ImportOptions},
+        mock::MockConnector,
+        types::{
+            archive::Archive,
+            asset::NewAsset,
+            contract_definition::NewContractDefinition,
+            data_address::DataAddress,
+            policy::{Action, NewPolicyDefinition, Permission, Policy},
+            query::Criterion,
+            secret::NewSecret,
+        },
+        ArchiveError, EdcConnectorApiVersion, EdcConnectorClient, Error, EDC_NAMESPACE,
+    };
+
+    async fn seed(client: &EdcConnectorClient) {
+        client
+            .secrets()
+            .create(&NewSecret::builder().id("secret").value("value").build())
+            .await
+            .unwrap();
+
+        for id in ["asset-1", "asset-2"] {
+            let asset = NewAsset::builder()
+                .id(id)
+                .property("name", id)
+                .data_address(DataAddress::builder().kind("HttpData").build().unwrap())
+                .build();
+            client.assets().create(&asset).await.unwrap();
+        }
+
+        let policy = NewPolicyDefinition::builder()
+            .id("policy")
+            .policy(
+                Policy::builder()
+                    .permission(Permission::builder().action(Action::simple("use")).build())
+                    .build(),
+            )
+            .build();
+        client.policies().create(&policy).await.unwrap();
+
+        let definition = NewContractDefinition::builder()
+            .id("definition")
+            .asset_selector(Criterion::new(
+                format!("{}id", EDC_NAMESPACE),
+                "=",
+                "asset-1",
+            ))
+            .access_policy_id("policy")
+            .contract_policy_id("policy")
+            .build();
+        client
+            .contract_definitions()
+            .create(&definition)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn should_migrate_state_between_connectors() {
+        let source = MockConnector::start().await.unwrap();
+        let source_client = source.client().unwrap();
+        seed(&source_client).await;
+
+        let archive = source_client
+            .archive()
+            .export_with_opts(
+                ExportOptions::builder()
+                    .secrets(vec!["secret".to_string()])
+                    .page_size(1)
+                    .build(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(archive.header().api_version(), "v3");
+        assert_eq!(archive.entries().len(), 5);
+
+        let mut content = Vec::new();
+        archive.write_to(&mut content).unwrap();
+        let archive = Archive::read_from(content.as_slice()).unwrap();
+
+        let target = MockConnector::start().await.unwrap();
+        let target_client = EdcConnectorClient::builder()
+            .management_url(target.url())
+            .version(EdcConnectorApiVersion::V4)
+            .participant_context("consumer")
+            .build()
+            .unwrap();
+
+        let summary = target_client.archive().import(&archive).await.unwrap();
+        assert_eq!(summary.created(), 5);
+
+        let asset = target_client.assets().get("asset-2").await.unwrap();
+        assert_eq!(
+            asset.property::<String>("name").unwrap(),
+            Some("asset-2".to_string())
+        );
+        assert_eq!(
+            target_client
+                .contract_definitions()
+                .get("definition")
+                .await
+                .unwrap()
+                .access_policy_id(),
+            "policy"
+        );
+        assert_eq!(
+            target_client.secrets().get("secret").await.unwrap().value(),
+            "value"
+        );
+    }
+
+    #[tokio::test]
+    async fn should_apply_conflict_strategies() {
+        let connector = MockConnector::start().await.unwrap();
+        let client = connector.client().unwrap();
+        seed(&client).await;
+
+        let archive = client.archive().export().await.unwrap();
+        assert_eq!(archive.entries().len(), 4);
+
+        let result = client.archive().import(&archive).await;
+        assert!(matches!(
+            result,
+            Err(Error::Archive(ArchiveError::Conflict { .. }))
+        ));
+
+        let skipped = client
+            .archive()
+            .import_with_opts(
+                &archive,
+                ImportOptions::builder()
+                    .on_conflict(ConflictStrategy::Skip)
+                    .build(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(skipped.skipped(), 4);
+
+        let overwritten = client
+            .archive()
+            .import_with_opts(
+                &archive,
+                ImportOptions::builder()
+                    .on_conflict(ConflictStrategy::Overwrite)
+                    .build(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(overwritten.overwritten(), 4);
+        assert_eq!(overwritten.created(), 0);
+    }
+}
@@ -63,7 +63,7 @@ mod assets {
 
             assert!(matches!(
                 response,
-                Err(Error::ManagementApi(ManagementApiError {
+                Err(Error::Conflict(ManagementApiError {
                     status_code: StatusCode::CONFLICT,
                     error_detail: ManagementApiErrorDetailKind::Parsed(..)
                 }))
@@ -118,7 +118,7 @@ mod assets {
 
             assert!(matches!(
                 response,
-                Err(Error::ManagementApi(ManagementApiError {
+                Err(Error::NotFound(ManagementApiError {
                     status_code: StatusCode::NOT_FOUND,
                     error_detail: ManagementApiErrorDetailKind::Parsed(..)
                 }))
@@ -211,7 +211,7 @@ mod assets {
 
             assert!(matches!(
                 response,
-                Err(Error::ManagementApi(ManagementApiError {
+                Err(Error::NotFound(ManagementApiError {
                     status_code: StatusCode::NOT_FOUND,
                     error_detail: ManagementApiErrorDetailKind::Parsed(..)
                 }))
@@ -283,7 +283,7 @@ mod assets {
 
             assert!(matches!(
                 response,
-                Err(Error::ManagementApi(ManagementApiError {
+                Err(Error::NotFound(ManagementApiError {
                     status_code: StatusCode::NOT_FOUND,
                     error_detail: ManagementApiErrorDetailKind::Parsed(..)
                 }))
@@ -327,8 +327,8 @@ mod assets {
                 .assets()
                 .query(
                     Query::builder()
-                        .filter(&format!("{}{}", EDC_NAMESPACE, "id"), "=", &id)
-                        .filter(&format!("{}{}", EDC_NAMESPACE, "foo"), "=", "bar")
+                        .filter(format!("{}{}", EDC_NAMESPACE, "id"), "=", &id)
+                        .filter(format!("{}{}", EDC_NAMESPACE, "foo"), "=", "bar")
                         .build(),
                 )
                 .await
@@ -375,8 +375,8 @@ mod assets {
             client.assets().create(&new_asset_2).await.unwrap();
 
             let query = Query::builder()
-                .filter(&format!("{}{}", EDC_NAMESPACE, "group"), "=", &group)
-                .sort(&format!("{}{}", EDC_NAMESPACE, "foo"), SortOrder::Desc)
+                .filter(format!("{}{}", EDC_NAMESPACE, "group"), "=", &group)
+                .sort(format!("{}{}", EDC_NAMESPACE, "foo"), SortOrder::Desc)
                 .build();
 
             let assets = client.assets().query(query).await.unwrap();
@@ -417,8 +417,8 @@ mod assets {
             client.assets().create(&new_asset_2).await.unwrap();
 
             let query = Query::builder()
-                .filter(&format!("{}{}", EDC_NAMESPACE, "group"), "=", &group)
-                .sort(&format!("{}{}", EDC_NAMESPACE, "foo"), SortOrder::Asc)
+                .filter(format!("{}{}", EDC_NAMESPACE, "group"), "=", &group)
+                .sort(format!("{}{}", EDC_NAMESPACE, "foo"), SortOrder::Asc)
                 .limit(1)
                 .build();
 
@@ -432,8 +432,8 @@ mod assets {
             );
 
             let query = Query::builder()
-                .filter(&format!("{}{}", EDC_NAMESPACE, "group"), "=", &group)
-                .sort(&format!("{}{}", EDC_NAMESPACE, "foo"), SortOrder::Asc)
+                .filter(format!("{}{}", EDC_NAMESPACE, "group"), "=", &group)
+                .sort(format!("{}{}", EDC_NAMESPACE, "foo"), SortOrder::Asc)
                 .offset(1)
                 .limit(1)
                 .build();
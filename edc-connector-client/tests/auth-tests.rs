@@ -24,7 +24,7 @@ mod auth {
 
         assert!(matches!(
             response,
-            Err(Error::ManagementApi(ManagementApiError {
+            Err(Error::Unauthorized(ManagementApiError {
                 status_code: StatusCode::UNAUTHORIZED,
                 error_detail: ManagementApiErrorDetailKind::Parsed(_)
             }))
@@ -0,0 +1,172 @@
+#[allow(clippy::unwrap_used)]
+mod callback {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use edc_connector_client::{
+        callback::{self, CallbackReceiver},
+        types::event::{Event, TransferProcessEventKind},
+    };
+    use reqwest::StatusCode;
+    use serde_json::{json, Value};
+    use tokio::net::TcpListener;
+
+    async fn serve(receiver: CallbackReceiver) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(receiver.serve(listener));
+        format!("http://{}{}", address, "/callbacks")
+    }
+
+    fn transfer_started() -> Value {
+        json!({
+            "id": "event",
+            "at": 1,
+            "type": "TransferProcessStarted",
+            "payload": {
+                "transferProcessId": "transfer",
+                "assetId": "asset",
+                "contractId": "agreement",
+                "type": "CONSUMER",
+                "callbackAddresses": [{
+                    "uri": "http://localhost/callbacks",
+                    "events": ["transfer.process"],
+                    "transactional": false
+                }],
+                "dataAddress": { "properties": { "endpoint": "http://provider/public" } }
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn should_dispatch_events_to_channel() {
+        let (handler, mut events) = callback::channel(4);
+        let url = serve(
+            CallbackReceiver::builder()
+                .path("/callbacks")
+                .handler(handler)
+                .build(),
+        )
+        .await;
+
+        let response = reqwest::Client::new()
+            .post(&url)
+            .json(&transfer_started())
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let envelope = events.recv().await.unwrap();
+        let Event::TransferProcess(event) = envelope.event() else {
+            panic!("expected transfer process event");
+        };
+
+        assert_eq!(envelope.id(), "event");
+        assert_eq!(event.kind(), &TransferProcessEventKind::Started);
+        assert_eq!(event.transfer_process_id(), "transfer");
+        assert_eq!(event.callback_addresses().len(), 1);
+        assert!(event.data_address().is_some());
+    }
+
+    #[tokio::test]
+    async fn should_validate_auth_key() {
+        let (handler, mut events) = callback::channel(4);
+        let url = serve(
+            CallbackReceiver::builder()
+                .path("/callbacks")
+                .auth("authKey", "secret")
+                .handler(handler)
+                .build(),
+        )
+        .await;
+
+        let client = reqwest::Client::new();
+
+        let missing = client
+            .post(&url)
+            .json(&transfer_started())
+            .send()
+            .await
+            .unwrap();
+
+        let wrong = client
+            .post(&url)
+            .header("authKey", "wrong")
+            .json(&transfer_started())
+            .send()
+            .await
+            .unwrap();
+
+        let valid = client
+            .post(&url)
+            .header("authKey", "secret")
+            .json(&transfer_started())
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(missing.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(wrong.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(valid.status(), StatusCode::NO_CONTENT);
+
+        assert!(events.recv().await.is_some());
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn should_dispatch_events_to_handler() {
+        let received = Arc::new(AtomicUsize::new(0));
+        let counter = received.clone();
+
+        let url = serve(
+            CallbackReceiver::builder()
+                .path("/callbacks")
+                .handler(callback::handler_fn(move |envelope| {
+                    let counter = counter.clone();
+                    async move {
+                        match envelope.event() {
+                            Event::ContractNegotiation(_) => {
+                                counter.fetch_add(1, Ordering::SeqCst);
+                                Ok(())
+                            }
+                            _ => Err("unexpected event".into()),
+                        }
+                    }
+                }))
+                .build(),
+        )
+        .await;
+
+        let client = reqwest::Client::new();
+
+        let negotiation = client
+            .post(&url)
+            .json(&json!({
+                "id": "event",
+                "at": 1,
+                "type": "ContractNegotiationFinalized",
+                "payload": { "contractNegotiationId": "negotiation" }
+            }))
+            .send()
+            .await
+            .unwrap();
+
+        let transfer = client
+            .post(&url)
+            .json(&transfer_started())
+            .send()
+            .await
+            .unwrap();
+
+        let invalid = client.post(&url).body("not an event").send().await.unwrap();
+
+        assert_eq!(negotiation.status(), StatusCode::NO_CONTENT);
+        assert_eq!(transfer.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(received.load(Ordering::SeqCst), 1);
+    }
+}
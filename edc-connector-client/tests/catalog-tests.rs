@@ -38,7 +38,7 @@ mod catalog {
                 .protocol(protocol)
                 .query_spec(
                     Query::builder()
-                        .filter(&format!("{}id", EDC_NAMESPACE), "=", asset_id.to_string())
+                        .filter(format!("{}id", EDC_NAMESPACE), "=", asset_id.to_string())
                         .build(),
                 )
                 .build();
@@ -4,6 +4,7 @@ use std::{collections::HashMap, future::Future, thread, time::Duration};
 
 use bon::Builder;
 use edc_connector_client::{
+    api::WaitOptions,
     types::{
         asset::NewAsset,
         catalog::DatasetRequest,
@@ -239,7 +240,7 @@ pub async fn seed(client: &EdcConnectorClient) -> (String, String, String) {
     let contract_definition = NewContractDefinition::builder()
         .id(Uuid::new_v4().to_string().as_str())
         .asset_selector(Criterion::new(
-            &format!("{}id", EDC_NAMESPACE),
+            format!("{}id", EDC_NAMESPACE),
             "=",
             asset_response.id(),
         ))
@@ -404,25 +405,11 @@ pub async fn wait_for_negotiation_state(
     id: &str,
     state: ContractNegotiationState,
 ) {
-    wait_for(|| {
-        let i_state = state.clone();
-        async {
-            client
-                .contract_negotiations()
-                .get_state(id)
-                .await
-                .map_err(|err| err.to_string())
-                .and_then(|s| {
-                    if s == state {
-                        Ok(i_state)
-                    } else {
-                        Err("State mismatch".to_string())
-                    }
-                })
-        }
-    })
-    .await
-    .unwrap();
+    client
+        .contract_negotiations()
+        .wait_for_state_with_opts(id, state, wait_options())
+        .await
+        .unwrap();
 }
 
 #[allow(clippy::unwrap_used)]
@@ -431,25 +418,18 @@ pub async fn wait_for_transfer_state(
     id: &str,
     state: TransferProcessState,
 ) {
-    wait_for(|| {
-        let i_state = state.clone();
-        async {
-            client
-                .transfer_processes()
-                .get_state(id)
-                .await
-                .map_err(|err| err.to_string())
-                .and_then(|s| {
-                    if s == state {
-                        Ok(i_state)
-                    } else {
-                        Err("State mismatch".to_string())
-                    }
-                })
-        }
-    })
-    .await
-    .unwrap();
+    client
+        .transfer_processes()
+        .wait_for_state_with_opts(id, state, wait_options())
+        .await
+        .unwrap();
+}
+
+fn wait_options() -> WaitOptions {
+    WaitOptions::builder()
+        .interval(Duration::from_millis(200))
+        .timeout(Duration::from_secs(30))
+        .build()
 }
 
 #[allow(clippy::unwrap_used)]
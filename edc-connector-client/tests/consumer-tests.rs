@@ -0,0 +1,332 @@
+#[allow(clippy::unwrap_used)]
+mod consumer {
+    use std::time::{Duration, Instant};
+
+    use edc_connector_client::{
+        api::{AcquireOptions, OfferSelection, Provider, WaitOptions},
+        EdcConnectorClient, Error, WorkflowStep,
+    };
+    use serde_json::{json, Value};
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    fn context() -> Value {
+        json!({ "@vocab": "https://w3id.org/edc/v0.0.1/ns/", "odrl": "http://www.w3.org/ns/odrl/2/" })
+    }
+
+    fn dataset() -> Value {
+        json!({
+            "@context": context(),
+            "@id": "asset",
+            "@type": "dcat:Dataset",
+            "odrl:hasPolicy": [
+                { "@id": "offer-1", "@type": "odrl:Offer", "odrl:permission": [] },
+                { "@id": "offer-2", "@type": "odrl:Offer", "odrl:permission": [] }
+            ]
+        })
+    }
+
+    fn negotiation(state: &str) -> Value {
+        json!({
+            "@context": context(),
+            "@id": "negotiation",
+            "state": state,
+            "contractAgreementId": "agreement",
+            "counterPartyId": "provider",
+            "counterPartyAddress": "http://provider",
+            "protocol": "dataspace-protocol-http:2025-1",
+            "createdAt": 1,
+            "callbackAddresses": [],
+            "type": "CONSUMER"
+        })
+    }
+
+    fn transfer(state: &str) -> Value {
+        json!({
+            "@context": context(),
+            "@id": "transfer",
+            "state": state,
+            "stateTimestamp": 1,
+            "assetId": "asset",
+            "contractId": "agreement",
+            "callbackAddresses": [],
+            "transferType": "HttpData-PULL",
+            "type": "CONSUMER"
+        })
+    }
+
+    fn id_response(id: &str) -> Value {
+        json!({ "@context": context(), "@id": id, "createdAt": 1 })
+    }
+
+    async fn mount(server: &MockServer, http_method: &str, at: &str, body: Value) {
+        Mock::given(method(http_method))
+            .and(path(at))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(server)
+            .await;
+    }
+
+    async fn setup(negotiation_state: &str) -> (MockServer, EdcConnectorClient) {
+        let server = MockServer::start().await;
+
+        mount(&server, "POST", "/v3/catalog/dataset/request", dataset()).await;
+        mount(
+            &server,
+            "POST",
+            "/v3/contractnegotiations",
+            id_response("negotiation"),
+        )
+        .await;
+        mount(
+            &server,
+            "GET",
+            "/v3/contractnegotiations/negotiation",
+            negotiation(negotiation_state),
+        )
+        .await;
+        mount(
+            &server,
+            "POST",
+            "/v3/transferprocesses",
+            id_response("transfer"),
+        )
+        .await;
+        mount(
+            &server,
+            "GET",
+            "/v3/transferprocesses/transfer",
+            transfer("STARTED"),
+        )
+        .await;
+
+        Mock::given(method("GET"))
+            .and(path("/v3/edrs/transfer/dataaddress"))
+            .respond_with(ResponseTemplate::new(404))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+
+        mount(
+            &server,
+            "GET",
+            "/v3/edrs/transfer/dataaddress",
+            json!({
+                "@context": context(),
+                "@type": "DataAddress",
+                "type": "https://w3id.org/idsa/v4.1/HTTP",
+                "endpoint": "http://provider/public",
+                "authorization": "token"
+            }),
+        )
+        .await;
+
+        let client = EdcConnectorClient::builder()
+            .management_url(server.uri())
+            .build()
+            .unwrap();
+
+        (server, client)
+    }
+
+    fn provider() -> Provider {
+        Provider::builder()
+            .id("provider")
+            .address("http://provider/protocol")
+            .build()
+    }
+
+    fn wait() -> WaitOptions {
+        WaitOptions::builder()
+            .interval(Duration::from_millis(10))
+            .timeout(Duration::from_millis(500))
+            .build()
+    }
+
+    fn options() -> AcquireOptions {
+        AcquireOptions::builder()
+            .negotiation_wait(wait())
+            .transfer_wait(wait())
+            .edr_wait(wait())
+            .build()
+    }
+
+    async fn requests_to(server: &MockServer, at: &str) -> Vec<Value> {
+        server
+            .received_requests()
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|r| r.url.path() == at)
+            .map(|r| serde_json::from_slice(&r.body).unwrap_or(Value::Null))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn should_acquire_data() {
+        let (server, client) = setup("FINALIZED").await;
+
+        let acquired = client
+            .consumer()
+            .acquire_with_opts("asset", &provider(), options())
+            .await
+            .unwrap();
+
+        assert_eq!(acquired.negotiation_id().unwrap(), "negotiation");
+        assert_eq!(acquired.agreement_id(), "agreement");
+        assert_eq!(acquired.transfer_process_id(), "transfer");
+        assert_eq!(
+            acquired
+                .data_address()
+                .property::<String>("endpoint")
+                .unwrap()
+                .unwrap(),
+            "http://provider/public"
+        );
+
+        let negotiations = requests_to(&server, "/v3/contractnegotiations").await;
+        let policy = &negotiations[0]["policy"];
+
+        assert_eq!(policy["@id"], "offer-1");
+        assert_eq!(policy["@type"], "Offer");
+        assert_eq!(policy["assigner"], "provider");
+        assert_eq!(policy["target"], "asset");
+
+        let transfers = requests_to(&server, "/v3/transferprocesses").await;
+
+        assert_eq!(transfers[0]["contractId"], "agreement");
+        assert_eq!(transfers[0]["transferType"], "HttpData-PULL");
+    }
+
+    #[tokio::test]
+    async fn should_select_offer_by_id() {
+        let (server, client) = setup("FINALIZED").await;
+
+        client
+            .consumer()
+            .acquire_with_opts(
+                "asset",
+                &provider(),
+                AcquireOptions::builder()
+                    .offer_selection(OfferSelection::ById("offer-2".to_string()))
+                    .negotiation_wait(wait())
+                    .transfer_wait(wait())
+                    .edr_wait(wait())
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        let negotiations = requests_to(&server, "/v3/contractnegotiations").await;
+
+        assert_eq!(negotiations[0]["policy"]["@id"], "offer-2");
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_no_offer_matches() {
+        let (_server, client) = setup("FINALIZED").await;
+
+        let result = client
+            .consumer()
+            .acquire_with_opts(
+                "asset",
+                &provider(),
+                AcquireOptions::builder()
+                    .offer_selection(OfferSelection::custom(|_| None))
+                    .build(),
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(Error::Workflow(err)) if err.step == WorkflowStep::OfferSelection && err.source.is_none()
+        ));
+    }
+
+    #[tokio::test]
+    async fn should_resume_from_agreement() {
+        let (server, client) = setup("FINALIZED").await;
+
+        let acquired = client
+            .consumer()
+            .acquire_with_opts(
+                "asset",
+                &provider(),
+                AcquireOptions::builder()
+                    .agreement_id("agreement")
+                    .transfer_wait(wait())
+                    .edr_wait(wait())
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        assert!(acquired.negotiation_id().is_none());
+        assert!(requests_to(&server, "/v3/catalog/dataset/request")
+            .await
+            .is_empty());
+        assert!(requests_to(&server, "/v3/contractnegotiations")
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_time_out_waiting_for_edr() {
+        let (server, client) = setup("FINALIZED").await;
+        Mock::given(method("GET"))
+            .and(path("/v3/edrs/transfer/dataaddress"))
+            .respond_with(ResponseTemplate::new(404))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+
+        let started = Instant::now();
+        let result = client
+            .consumer()
+            .acquire_with_opts(
+                "asset",
+                &provider(),
+                AcquireOptions::builder()
+                    .agreement_id("agreement")
+                    .transfer_wait(wait())
+                    .edr_wait(
+                        WaitOptions::builder()
+                            .interval(Duration::from_secs(5))
+                            .timeout(Duration::from_millis(100))
+                            .build(),
+                    )
+                    .build(),
+            )
+            .await;
+
+        let Err(Error::Workflow(err)) = result else {
+            panic!("expected workflow error");
+        };
+
+        assert_eq!(err.step, WorkflowStep::Edr);
+        assert!(err.source.as_ref().is_some_and(Error::is_not_found));
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn should_report_failed_negotiation() {
+        let (_server, client) = setup("TERMINATED").await;
+
+        let result = client
+            .consumer()
+            .acquire_with_opts("asset", &provider(), options())
+            .await;
+
+        let Err(Error::Workflow(err)) = result else {
+            panic!("expected workflow error");
+        };
+
+        assert_eq!(err.step, WorkflowStep::Negotiation);
+        assert_eq!(err.negotiation_id.as_deref(), Some("negotiation"));
+        assert!(err.agreement_id.is_none());
+        assert!(matches!(err.source, Some(Error::Wait(_))));
+    }
+}
@@ -74,7 +74,7 @@ mod contract_definition {
 
             assert!(matches!(
                 response,
-                Err(Error::ManagementApi(ManagementApiError {
+                Err(Error::Conflict(ManagementApiError {
                     status_code: StatusCode::CONFLICT,
                     error_detail: ManagementApiErrorDetailKind::Parsed(..)
                 }))
@@ -136,7 +136,7 @@ mod contract_definition {
 
             assert!(matches!(
                 response,
-                Err(Error::ManagementApi(ManagementApiError {
+                Err(Error::NotFound(ManagementApiError {
                     status_code: StatusCode::NOT_FOUND,
                     error_detail: ManagementApiErrorDetailKind::Parsed(..)
                 }))
@@ -203,7 +203,7 @@ mod contract_definition {
 
             assert!(matches!(
                 response,
-                Err(Error::ManagementApi(ManagementApiError {
+                Err(Error::NotFound(ManagementApiError {
                     status_code: StatusCode::NOT_FOUND,
                     error_detail: ManagementApiErrorDetailKind::Parsed(..)
                 }))
@@ -285,7 +285,7 @@ mod contract_definition {
 
             assert!(matches!(
                 response,
-                Err(Error::ManagementApi(ManagementApiError {
+                Err(Error::NotFound(ManagementApiError {
                     status_code: StatusCode::NOT_FOUND,
                     error_detail: ManagementApiErrorDetailKind::Parsed(..)
                 }))
@@ -124,7 +124,7 @@ mod contract_negotiations {
 
             assert!(matches!(
                 response,
-                Err(Error::ManagementApi(ManagementApiError {
+                Err(Error::BadRequest(ManagementApiError {
                     status_code: StatusCode::BAD_REQUEST,
                     error_detail: ManagementApiErrorDetailKind::Parsed(..)
                 }))
@@ -236,9 +236,7 @@ mod contract_negotiations {
 
     mod terminate {
 
-        use edc_connector_client::{
-            types::contract_negotiation::ContractNegotiationState, Error, ManagementApiError,
-        };
+        use edc_connector_client::types::contract_negotiation::ContractNegotiationState;
         use rstest::rstest;
 
         use crate::common::{
@@ -274,10 +272,7 @@ mod contract_negotiations {
                 .terminate(&contract_negotiation_id, "test")
                 .await;
 
-            assert!(matches!(
-                result,
-                Err(Error::ManagementApi(ManagementApiError { .. }))
-            ));
+            assert!(result.is_err_and(|err| err.management_api_error().is_some()));
         }
     }
 }
@@ -0,0 +1,288 @@
+#[allow(clippy::unwrap_used)]
+mod data_plane {
+    use edc_connector_client::{
+        types::data_address::DataAddress, DataPlaneError, EdcConnectorClient, Error,
+    };
+    use futures::TryStreamExt;
+    use reqwest::StatusCode;
+    use serde_json::{json, Value};
+    use wiremock::{
+        matchers::{body_json, header, method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    fn edr(server: &MockServer, token: &str, expires_in: &str) -> Value {
+        json!({
+            "@context": { "@vocab": "https://w3id.org/edc/v0.0.1/ns/" },
+            "@type": "DataAddress",
+            "type": "https://w3id.org/idsa/v4.1/HTTP",
+            "endpoint": format!("{}/public/", server.uri()),
+            "authType": "bearer",
+            "authorization": token,
+            "refreshEndpoint": format!("{}/refresh", server.uri()),
+            "refreshToken": "refresh",
+            "expiresIn": expires_in
+        })
+    }
+
+    async fn mount_edrs(server: &MockServer, first: Value, refreshed: Value) {
+        Mock::given(method("GET"))
+            .and(path("/v3/edrs/transfer/dataaddress"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(first))
+            .mount(server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/v3/edrs/transfer/refresh"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(refreshed))
+            .mount(server)
+            .await;
+    }
+
+    async fn mount_data(server: &MockServer, token: &str) {
+        Mock::given(method("GET"))
+            .and(path("/public/items"))
+            .and(header("Authorization", token))
+            .respond_with(ResponseTemplate::new(200).set_body_string("data"))
+            .with_priority(1)
+            .mount(server)
+            .await;
+
+        Mock::given(path("/public/items"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(server)
+            .await;
+    }
+
+    fn client(server: &MockServer) -> EdcConnectorClient {
+        EdcConnectorClient::builder()
+            .management_url(server.uri())
+            .build()
+            .unwrap()
+    }
+
+    async fn refresh_requests(server: &MockServer) -> usize {
+        server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|r| r.url.path() == "/v3/edrs/transfer/refresh")
+            .count()
+    }
+
+    #[tokio::test]
+    async fn should_forward_path_query_and_token() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/public/items/1"))
+            .and(query_param("page", "2"))
+            .and(header("Authorization", "token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": 1 })))
+            .mount(&server)
+            .await;
+
+        let data_address =
+            serde_json::from_value::<DataAddress>(edr(&server, "token", "300")).unwrap();
+
+        let item = client(&server)
+            .data_plane(&data_address)
+            .unwrap()
+            .get("/items/1")
+            .query(&[("page", "2")])
+            .send()
+            .await
+            .unwrap()
+            .json::<Value>()
+            .await
+            .unwrap();
+
+        assert_eq!(item, json!({ "id": 1 }));
+    }
+
+    #[tokio::test]
+    async fn should_treat_huge_expiry_as_no_expiry() {
+        let server = MockServer::start().await;
+        mount_data(&server, "token").await;
+
+        let expires_in = u64::MAX.to_string();
+        let data_address =
+            serde_json::from_value::<DataAddress>(edr(&server, "token", &expires_in)).unwrap();
+
+        let response = client(&server)
+            .data_plane(&data_address)
+            .unwrap()
+            .get("items")
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(refresh_requests(&server).await, 0);
+    }
+
+    #[tokio::test]
+    async fn should_use_the_data_plane_http_client() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/public/items"))
+            .and(header("x-data-plane", "yes"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let http = reqwest::Client::builder()
+            .default_headers(
+                [(
+                    reqwest::header::HeaderName::from_static("x-data-plane"),
+                    reqwest::header::HeaderValue::from_static("yes"),
+                )]
+                .into_iter()
+                .collect(),
+            )
+            .build()
+            .unwrap();
+        let client = EdcConnectorClient::builder()
+            .management_url(server.uri())
+            .with_data_plane_http_client(http)
+            .build()
+            .unwrap();
+        let data_address =
+            serde_json::from_value::<DataAddress>(edr(&server, "token", "300")).unwrap();
+
+        let response = client
+            .data_plane(&data_address)
+            .unwrap()
+            .get("items")
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn should_post_and_stream_the_body() {
+        let server = MockServer::start().await;
+        let payload = "x".repeat(1024 * 1024);
+
+        Mock::given(method("POST"))
+            .and(path("/public/items"))
+            .and(header("Authorization", "token"))
+            .and(body_json(json!({ "query": "all" })))
+            .respond_with(ResponseTemplate::new(200).set_body_string(payload.clone()))
+            .mount(&server)
+            .await;
+
+        let data_address =
+            serde_json::from_value::<DataAddress>(edr(&server, "token", "300")).unwrap();
+
+        let chunks = client(&server)
+            .data_plane(&data_address)
+            .unwrap()
+            .post("items")
+            .json(&json!({ "query": "all" }))
+            .send()
+            .await
+            .unwrap()
+            .bytes_stream()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), payload.len());
+    }
+
+    #[tokio::test]
+    async fn should_refresh_rejected_token() {
+        let server = MockServer::start().await;
+        mount_edrs(
+            &server,
+            edr(&server, "stale", "300"),
+            edr(&server, "fresh", "300"),
+        )
+        .await;
+        mount_data(&server, "fresh").await;
+
+        let data_plane = client(&server).data_plane_for("transfer").await.unwrap();
+
+        let body = data_plane
+            .get("items")
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+
+        assert_eq!(body, "data");
+        assert_eq!(refresh_requests(&server).await, 1);
+    }
+
+    #[tokio::test]
+    async fn should_refresh_expired_token_before_sending() {
+        let server = MockServer::start().await;
+        mount_edrs(
+            &server,
+            edr(&server, "stale", "0"),
+            edr(&server, "fresh", "300"),
+        )
+        .await;
+        mount_data(&server, "fresh").await;
+
+        let data_plane = client(&server).data_plane_for("transfer").await.unwrap();
+
+        data_plane.get("items").send().await.unwrap();
+
+        let data_requests = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|r| r.url.path() == "/public/items")
+            .count();
+
+        assert_eq!(refresh_requests(&server).await, 1);
+        assert_eq!(data_requests, 1);
+    }
+
+    #[tokio::test]
+    async fn should_fail_without_refresh_support() {
+        let server = MockServer::start().await;
+        mount_data(&server, "fresh").await;
+
+        let data_address =
+            serde_json::from_value::<DataAddress>(edr(&server, "stale", "300")).unwrap();
+
+        let result = client(&server)
+            .data_plane(&data_address)
+            .unwrap()
+            .get("items")
+            .send()
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(Error::DataPlane(DataPlaneError::Status { status_code, .. }))
+                if status_code == StatusCode::UNAUTHORIZED
+        ));
+    }
+
+    #[tokio::test]
+    async fn should_reject_data_address_without_endpoint() {
+        let data_address = DataAddress::builder().kind("HttpData").build().unwrap();
+
+        let result = EdcConnectorClient::builder()
+            .management_url("http://localhost")
+            .build()
+            .unwrap()
+            .data_plane(&data_address);
+
+        assert!(matches!(
+            result,
+            Err(Error::DataPlane(DataPlaneError::InvalidDataAddress(property))) if property == "endpoint"
+        ));
+    }
+}
@@ -0,0 +1,135 @@
+#[allow(clippy::unwrap_used)]
+mod edr_cache {
+    use std::time::Duration;
+
+    use edc_connector_client::{api::EdrCache, EdcConnectorClient};
+    use serde_json::{json, Value};
+    use wiremock::{
+        matchers::{method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    fn edr(token: &str, expires_in: Option<&str>) -> Value {
+        let mut edr = json!({
+            "@context": { "@vocab": "https://w3id.org/edc/v0.0.1/ns/" },
+            "@type": "DataAddress",
+            "type": "https://w3id.org/idsa/v4.1/HTTP",
+            "endpoint": "http://provider/public",
+            "authorization": token,
+            "refreshEndpoint": "http://provider/refresh",
+            "refreshToken": "refresh"
+        });
+        if let Some(expires_in) = expires_in {
+            edr["expiresIn"] = json!(expires_in);
+        }
+        edr
+    }
+
+    async fn setup(first: Value, refreshed: Value) -> (MockServer, EdcConnectorClient) {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/v3/edrs/transfer/dataaddress"))
+            .and(query_param("auto_refresh", "true"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(first))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/v3/edrs/transfer/refresh"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(refreshed))
+            .mount(&server)
+            .await;
+
+        let client = EdcConnectorClient::builder()
+            .management_url(server.uri())
+            .build()
+            .unwrap();
+
+        (server, client)
+    }
+
+    async fn requests(server: &MockServer) -> Vec<String> {
+        server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|r| format!("{} {}", r.method, r.url.path()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn should_refresh_edr_explicitly() {
+        let (_server, client) = setup(edr("first", None), edr("refreshed", Some("300"))).await;
+
+        let data_address = client.edrs().refresh("transfer").await.unwrap();
+
+        assert_eq!(data_address.authorization(), Some("refreshed"));
+        assert_eq!(data_address.expires_in(), Some(Duration::from_secs(300)));
+    }
+
+    #[tokio::test]
+    async fn should_get_data_address_with_auto_refresh() {
+        let (_server, client) = setup(edr("first", None), edr("refreshed", None)).await;
+
+        let data_address = client
+            .edrs()
+            .get_data_address_with_refresh("transfer", true)
+            .await
+            .unwrap();
+
+        assert_eq!(data_address.authorization(), Some("first"));
+        assert_eq!(data_address.refresh_token(), Some("refresh"));
+    }
+
+    #[tokio::test]
+    async fn should_serve_valid_tokens_from_cache() {
+        let (server, client) = setup(edr("first", Some("300")), edr("refreshed", None)).await;
+        let cache = EdrCache::new(client);
+
+        assert_eq!(cache.token("transfer").await.unwrap(), "first");
+        assert_eq!(cache.token("transfer").await.unwrap(), "first");
+        assert_eq!(requests(&server).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn should_handle_huge_expiries_and_margins() {
+        let expires_in = u64::MAX.to_string();
+        let (server, client) = setup(edr("first", Some(&expires_in)), edr("refreshed", None)).await;
+        let cache = EdrCache::new(client).refresh_margin(Duration::MAX);
+
+        assert_eq!(cache.token("transfer").await.unwrap(), "first");
+        assert_eq!(cache.token("transfer").await.unwrap(), "first");
+        assert_eq!(requests(&server).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn should_refresh_tokens_before_expiry() {
+        let (server, client) = setup(edr("first", Some("20")), edr("refreshed", Some("300"))).await;
+        let cache = EdrCache::new(client).refresh_margin(Duration::from_secs(30));
+
+        assert_eq!(cache.token("transfer").await.unwrap(), "first");
+        assert_eq!(cache.token("transfer").await.unwrap(), "refreshed");
+        assert_eq!(cache.token("transfer").await.unwrap(), "refreshed");
+        assert_eq!(
+            requests(&server).await,
+            vec![
+                "GET /v3/edrs/transfer/dataaddress",
+                "POST /v3/edrs/transfer/refresh"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_fetch_again_after_invalidation() {
+        let (server, client) = setup(edr("first", None), edr("refreshed", None)).await;
+        let cache = EdrCache::new(client);
+
+        cache.data_address("transfer").await.unwrap();
+        cache.invalidate("transfer");
+        cache.data_address("transfer").await.unwrap();
+
+        assert_eq!(requests(&server).await.len(), 2);
+    }
+}
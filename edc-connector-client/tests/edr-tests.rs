@@ -138,7 +138,7 @@ mod delete {
 
         assert!(matches!(
             response,
-            Err(Error::ManagementApi(ManagementApiError {
+            Err(Error::NotFound(ManagementApiError {
                 status_code: StatusCode::NOT_FOUND,
                 error_detail: ManagementApiErrorDetailKind::Raw(..)
             }))
@@ -0,0 +1,110 @@
+#[allow(clippy::unwrap_used)]
+mod errors {
+    use std::time::Duration;
+
+    use edc_connector_client::{EdcConnectorClient, Error};
+    use reqwest::StatusCode;
+    use serde_json::json;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    async fn setup(response: ResponseTemplate) -> (MockServer, EdcConnectorClient) {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v3/assets/1"))
+            .respond_with(response)
+            .mount(&server)
+            .await;
+
+        let client = EdcConnectorClient::builder()
+            .management_url(server.uri())
+            .timeout(Duration::from_millis(200))
+            .build()
+            .unwrap();
+
+        (server, client)
+    }
+
+    #[tokio::test]
+    async fn should_map_statuses_to_variants() {
+        for (status, predicate) in [
+            (401, Error::is_unauthorized as fn(&Error) -> bool),
+            (403, Error::is_forbidden),
+            (404, Error::is_not_found),
+            (409, Error::is_conflict),
+            (503, Error::is_server_error),
+        ] {
+            let (_server, client) = setup(ResponseTemplate::new(status)).await;
+
+            let err = client.assets().get("1").await.unwrap_err();
+
+            assert!(predicate(&err), "{status} mapped to {err:?}");
+            assert_eq!(err.status_code().unwrap().as_u16(), status);
+        }
+
+        let (_server, client) = setup(ResponseTemplate::new(418)).await;
+        let err = client.assets().get("1").await.unwrap_err();
+        assert!(matches!(err, Error::ManagementApi(_)));
+    }
+
+    #[tokio::test]
+    async fn should_expose_validation_violations() {
+        let (_server, client) = setup(ResponseTemplate::new(400).set_body_json(json!([
+            {
+                "message": "mandatory value missing",
+                "type": "ValidationFailure",
+                "path": "https://w3id.org/edc/v0.0.1/ns/dataAddress",
+                "invalidValue": null
+            },
+            {
+                "message": "invalid operator",
+                "type": "ValidationFailure",
+                "path": "operator",
+                "invalidValue": "~"
+            },
+            { "message": "generic", "type": "BadRequest" }
+        ])))
+        .await;
+
+        let err = client.assets().get("1").await.unwrap_err();
+        let Error::BadRequest(err) = &err else {
+            panic!("expected a bad request, got {err:?}");
+        };
+
+        assert_eq!(err.status_code, StatusCode::BAD_REQUEST);
+        assert_eq!(err.details().len(), 3);
+
+        let violations = err.violations().collect::<Vec<_>>();
+        assert_eq!(violations.len(), 2);
+        assert_eq!(
+            violations[0].path.as_deref(),
+            Some("https://w3id.org/edc/v0.0.1/ns/dataAddress")
+        );
+        assert_eq!(violations[1].invalid_value, Some(json!("~")));
+    }
+
+    #[tokio::test]
+    async fn should_keep_the_body_on_deserialization_errors() {
+        let (_server, client) =
+            setup(ResponseTemplate::new(200).set_body_string("{\"@id\": 1}")).await;
+
+        let err = client.assets().get("1").await.unwrap_err();
+
+        assert!(matches!(
+            err,
+            Error::Deserialization { ref body, .. } if body == "{\"@id\": 1}"
+        ));
+    }
+
+    #[tokio::test]
+    async fn should_report_timeouts() {
+        let (_server, client) =
+            setup(ResponseTemplate::new(200).set_delay(Duration::from_secs(2))).await;
+
+        let err = client.assets().get("1").await.unwrap_err();
+
+        assert!(err.is_timeout(), "expected a timeout, got {err:?}");
+    }
+}
@@ -0,0 +1,173 @@
+#[allow(clippy::unwrap_used)]
+mod middleware {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use edc_connector_client::{
+        async_trait, Auth, EdcConnectorClient, EdcResult, Error, Middleware, Next, RetryPolicy,
+    };
+    use reqwest::{header::HeaderValue, Request, Response, StatusCode};
+    use serde_json::json;
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    struct StaticHeader(&'static str, &'static str);
+
+    #[async_trait]
+    impl Middleware for StaticHeader {
+        async fn handle(&self, mut request: Request, next: Next<'_>) -> EdcResult<Response> {
+            request
+                .headers_mut()
+                .insert(self.0, HeaderValue::from_static(self.1));
+            next.run(request).await
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct Counter {
+        requests: Arc<AtomicUsize>,
+        failures: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Middleware for Counter {
+        async fn handle(&self, request: Request, next: Next<'_>) -> EdcResult<Response> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            let response = next.run(request).await?;
+            if !response.status().is_success() {
+                self.failures.fetch_add(1, Ordering::SeqCst);
+            }
+            Ok(response)
+        }
+    }
+
+    struct Reject;
+
+    #[async_trait]
+    impl Middleware for Reject {
+        async fn handle(&self, _request: Request, _next: Next<'_>) -> EdcResult<Response> {
+            Err(Error::Auth("rejected".into()))
+        }
+    }
+
+    fn asset_json() -> serde_json::Value {
+        json!({
+            "@context": { "@vocab": "https://w3id.org/edc/v0.0.1/ns/" },
+            "@id": "1",
+            "@type": "Asset",
+            "properties": {},
+            "dataAddress": { "@type": "DataAddress", "type": "HttpData" }
+        })
+    }
+
+    #[tokio::test]
+    async fn should_inject_headers_along_with_auth() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/v3/assets/1"))
+            .and(header("X-Api-Key", "123456"))
+            .and(header("X-Correlation-Id", "correlation"))
+            .and(header("X-Tenant", "tenant"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(asset_json()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = EdcConnectorClient::builder()
+            .management_url(server.uri())
+            .with_auth(Auth::api_token("123456"))
+            .with_middleware(StaticHeader("X-Correlation-Id", "correlation"))
+            .with_middleware(StaticHeader("X-Tenant", "tenant"))
+            .build()
+            .unwrap();
+
+        let asset = client.assets().get("1").await.unwrap();
+
+        assert_eq!(asset.id(), "1");
+    }
+
+    #[tokio::test]
+    async fn should_observe_every_attempt() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/v3/assets/1"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/v3/assets/1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(asset_json()))
+            .mount(&server)
+            .await;
+
+        let counter = Counter::default();
+
+        let client = EdcConnectorClient::builder()
+            .management_url(server.uri())
+            .with_retry(
+                RetryPolicy::builder()
+                    .initial_backoff(Duration::from_millis(10))
+                    .build(),
+            )
+            .with_middleware(counter.clone())
+            .build()
+            .unwrap();
+
+        client.assets().get("1").await.unwrap();
+
+        assert_eq!(counter.requests.load(Ordering::SeqCst), 2);
+        assert_eq!(counter.failures.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn should_short_circuit_the_chain() {
+        let server = MockServer::start().await;
+
+        let client = EdcConnectorClient::builder()
+            .management_url(server.uri())
+            .with_middleware(Reject)
+            .build()
+            .unwrap();
+
+        let response = client.assets().get("1").await;
+
+        assert!(matches!(response, Err(Error::Auth(_))));
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_surface_management_errors_after_middlewares() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/v3/assets/1"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let client = EdcConnectorClient::builder()
+            .management_url(server.uri())
+            .with_middleware(Counter::default())
+            .build()
+            .unwrap();
+
+        let response = client.assets().get("1").await;
+
+        assert!(matches!(
+            response,
+            Err(Error::NotFound(err)) if err.status_code == StatusCode::NOT_FOUND
+        ));
+    }
+}
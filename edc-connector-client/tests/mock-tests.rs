@@ -0,0 +1,435 @@
+#[allow(clippy::unwrap_used)]
+mod mock {
+    use std::time::Duration;
+
+    use edc_connector_client::{
+        api::{CatalogCrawler, CrawlerOptions, ParticipantRegistry, Provider, WaitOptions},
+        mock::{MockConnector, MockOptions, Progression},
+        types::{
+            asset::NewAsset,
+            catalog::CatalogRequest,
+            contract_definition::NewContractDefinition,
+            contract_negotiation::{ContractNegotiationState, ContractRequest},
+            data_address::DataAddress,
+            policy::{Action, NewPolicyDefinition, Permission, Policy, PolicyKind, Target},
+            query::{Criterion, Query, SortOrder},
+            secret::NewSecret,
+            transfer_process::{TransferProcessState, TransferRequest},
+        },
+        EdcConnectorClient, Error, ManagementApiError, EDC_NAMESPACE,
+    };
+    use futures::TryStreamExt;
+    use reqwest::StatusCode;
+
+    fn asset(id: &str, group: &str) -> NewAsset {
+        NewAsset::builder()
+            .id(id)
+            .property("group", group)
+            .data_address(DataAddress::builder().kind("HttpData").build().unwrap())
+            .build()
+    }
+
+    fn wait() -> WaitOptions {
+        WaitOptions::builder()
+            .interval(Duration::from_millis(10))
+            .timeout(Duration::from_secs(2))
+            .build()
+    }
+
+    async fn seed(client: &EdcConnectorClient) {
+        client.assets().create(&asset("asset", "a")).await.unwrap();
+
+        let policy = NewPolicyDefinition::builder()
+            .id("policy")
+            .policy(
+                Policy::builder()
+                    .permission(Permission::builder().action(Action::simple("use")).build())
+                    .build(),
+            )
+            .build();
+        client.policies().create(&policy).await.unwrap();
+
+        let definition = NewContractDefinition::builder()
+            .id("definition")
+            .asset_selector(Criterion::new(format!("{}id", EDC_NAMESPACE), "=", "asset"))
+            .access_policy_id("policy")
+            .contract_policy_id("policy")
+            .build();
+        client
+            .contract_definitions()
+            .create(&definition)
+            .await
+            .unwrap();
+    }
+
+    async fn negotiate(client: &EdcConnectorClient) -> String {
+        let request = ContractRequest::builder()
+            .counter_party_address("http://provider/protocol")
+            .counter_party_id("provider")
+            .policy(
+                Policy::builder()
+                    .id("offer")
+                    .kind(PolicyKind::Offer)
+                    .assigner("provider")
+                    .target(Target::simple("asset"))
+                    .permission(Permission::builder().action(Action::simple("use")).build())
+                    .build(),
+            )
+            .build();
+
+        client
+            .contract_negotiations()
+            .initiate(&request)
+            .await
+            .unwrap()
+            .id()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn should_manage_and_query_assets() {
+        let connector = MockConnector::start().await.unwrap();
+        let client = connector.client().unwrap();
+
+        for (id, group) in [("1", "a"), ("2", "b"), ("3", "a")] {
+            client.assets().create(&asset(id, group)).await.unwrap();
+        }
+
+        let asset = client.assets().get("1").await.unwrap();
+        assert_eq!(asset.id(), "1");
+        assert_eq!(
+            asset.property::<String>("group").unwrap().unwrap(),
+            "a".to_string()
+        );
+
+        let assets = client
+            .assets()
+            .query(
+                Query::builder()
+                    .filter(format!("{}group", EDC_NAMESPACE), "=", "a")
+                    .build(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(assets.len(), 2);
+
+        client.assets().delete("1").await.unwrap();
+        let result = client.assets().get("1").await;
+
+        assert!(matches!(
+            result,
+            Err(Error::NotFound(ManagementApiError {
+                status_code: StatusCode::NOT_FOUND,
+                ..
+            }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn should_sort_mixed_type_properties() {
+        let connector = MockConnector::start().await.unwrap();
+        let client = connector.client().unwrap();
+
+        let with_rank = |id: &str| {
+            NewAsset::builder()
+                .id(id)
+                .data_address(DataAddress::builder().kind("HttpData").build().unwrap())
+        };
+        for asset in [
+            with_rank("string").property("rank", "b").build(),
+            with_rank("number-2").property("rank", 2_i64).build(),
+            with_rank("missing").build(),
+            with_rank("bool").property("rank", true).build(),
+            with_rank("number-1").property("rank", 1_i64).build(),
+        ] {
+            client.assets().create(&asset).await.unwrap();
+        }
+
+        let assets = client
+            .assets()
+            .query(
+                Query::builder()
+                    .sort(format!("{}rank", EDC_NAMESPACE), SortOrder::Asc)
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        let ids = assets.iter().map(|asset| asset.id()).collect::<Vec<_>>();
+        assert_eq!(ids, ["missing", "bool", "number-1", "number-2", "string"]);
+    }
+
+    #[tokio::test]
+    async fn should_reject_duplicated_ids() {
+        let connector = MockConnector::start().await.unwrap();
+        let client = connector.client().unwrap();
+
+        client.assets().create(&asset("1", "a")).await.unwrap();
+        let result = client.assets().create(&asset("1", "a")).await;
+
+        assert!(matches!(
+            result,
+            Err(Error::Conflict(ManagementApiError {
+                status_code: StatusCode::CONFLICT,
+                ..
+            }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn should_manage_secrets() {
+        let connector = MockConnector::start().await.unwrap();
+        let client = connector.client().unwrap();
+
+        let secret = NewSecret::builder().id("secret").value("value").build();
+        client.secrets().create(&secret).await.unwrap();
+
+        assert_eq!(
+            client.secrets().get("secret").await.unwrap().value(),
+            "value"
+        );
+
+        client.secrets().delete("secret").await.unwrap();
+        assert!(client.secrets().get("secret").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_expose_offers_of_contract_definitions() {
+        let connector = MockConnector::start().await.unwrap();
+        let client = connector.client().unwrap();
+        seed(&client).await;
+
+        let policy = client.policies().get("policy").await.unwrap();
+        assert_eq!(policy.id(), "policy");
+
+        let definition = client
+            .contract_definitions()
+            .get("definition")
+            .await
+            .unwrap();
+        assert_eq!(definition.contract_policy_id(), "policy");
+
+        let acquired = client
+            .consumer()
+            .acquire(
+                "asset",
+                &Provider::builder()
+                    .id("provider")
+                    .address("http://provider/protocol")
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        assert!(!acquired.transfer_process_id().is_empty());
+        assert_eq!(
+            acquired.data_address().endpoint().unwrap(),
+            format!("{}/public", connector.url())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_serve_catalog() {
+        let connector = MockConnector::start().await.unwrap();
+        let client = connector.client().unwrap();
+        seed(&client).await;
+
+        let catalog = client
+            .catalogue()
+            .request(
+                &CatalogRequest::builder()
+                    .counter_party_address("http://provider/protocol")
+                    .query_spec(Query::default())
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        assert!(catalog.participant_id().is_some());
+        assert_eq!(
+            catalog.data_services()[0].endpoint_url(),
+            Some("http://provider/protocol")
+        );
+
+        let dataset = &catalog.datasets()[0];
+        assert_eq!(dataset.id(), "asset");
+        assert_eq!(dataset.property::<String>("group").unwrap().unwrap(), "a");
+        assert_eq!(
+            dataset.transfer_type(&["HttpData-PUSH"]),
+            Some("HttpData-PUSH")
+        );
+    }
+
+    #[tokio::test]
+    async fn should_stream_catalog_datasets() {
+        let connector = MockConnector::start().await.unwrap();
+        let client = connector.client().unwrap();
+        seed(&client).await;
+        for id in ["asset-1", "asset-2", "asset-3", "asset-4"] {
+            client.assets().create(&asset(id, "b")).await.unwrap();
+        }
+        let definition = NewContractDefinition::builder()
+            .id("group-b")
+            .asset_selector(Criterion::new(format!("{}group", EDC_NAMESPACE), "=", "b"))
+            .access_policy_id("policy")
+            .contract_policy_id("policy")
+            .build();
+        client
+            .contract_definitions()
+            .create(&definition)
+            .await
+            .unwrap();
+
+        let request = CatalogRequest::builder()
+            .counter_party_address("http://provider/protocol")
+            .query_spec(Query::builder().limit(2).build())
+            .build();
+
+        let datasets = client
+            .catalogue()
+            .datasets_stream(&request)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        let ids = datasets
+            .iter()
+            .map(|dataset| dataset.id())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["asset", "asset-1", "asset-2", "asset-3", "asset-4"]);
+    }
+
+    #[tokio::test]
+    async fn should_crawl_providers() {
+        let connector = MockConnector::start().await.unwrap();
+        let client = connector.client().unwrap();
+        seed(&client).await;
+
+        let registry = ParticipantRegistry::from_json(
+            r#"{
+                "participants": [
+                    { "id": "provider-1", "address": "http://provider-1/protocol" },
+                    { "id": "provider-2", "address": "http://provider-2/protocol" },
+                    { "id": "offline", "address": "offline" }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let crawler =
+            CatalogCrawler::new_with_opts(client, CrawlerOptions::builder().concurrency(2).build());
+
+        let catalog = crawler.crawl_registry(&registry).await;
+
+        assert_eq!(catalog.providers().count(), 2);
+        assert_eq!(catalog.providers_of("asset").len(), 2);
+        assert!(catalog.dataset("provider-1", "asset").is_some());
+        assert_eq!(catalog.search("ASS").len(), 2);
+        assert!(catalog.search("unknown").is_empty());
+
+        let failure = catalog.failure("offline").unwrap();
+        assert_eq!(failure.provider().address(), "offline");
+
+        let fetched_at = catalog.provider("provider-1").unwrap().fetched_at();
+        let cached = crawler.crawl(&registry.participants()[..1]).await;
+        assert_eq!(cached.providers().count(), 1);
+        assert_eq!(
+            cached.provider("provider-1").unwrap().fetched_at(),
+            fetched_at
+        );
+
+        crawler.invalidate("provider-1");
+        assert!(crawler.catalog().provider("provider-1").is_none());
+        assert!(crawler.catalog().failure("offline").is_some());
+    }
+
+    #[tokio::test]
+    async fn should_progress_negotiation_and_transfer() {
+        let connector = MockConnector::start().await.unwrap();
+        let client = connector.client().unwrap();
+
+        let negotiation_id = negotiate(&client).await;
+
+        let negotiation = client
+            .contract_negotiations()
+            .wait_for_state_with_opts(&negotiation_id, ContractNegotiationState::Finalized, wait())
+            .await
+            .unwrap();
+        let agreement_id = negotiation.contract_agreement_id().unwrap();
+
+        let agreement = client
+            .contract_agreements()
+            .get(agreement_id)
+            .await
+            .unwrap();
+        assert_eq!(agreement.asset_id(), "asset");
+        assert_eq!(agreement.provider_id(), "provider");
+
+        let request = TransferRequest::builder()
+            .counter_party_address("http://provider/protocol")
+            .contract_id(agreement_id)
+            .transfer_type("HttpData-PULL")
+            .destination(DataAddress::builder().kind("HttpProxy").build().unwrap())
+            .build();
+        let transfer_id = client
+            .transfer_processes()
+            .initiate(&request)
+            .await
+            .unwrap()
+            .id()
+            .to_string();
+
+        client
+            .transfer_processes()
+            .wait_for_state_with_opts(&transfer_id, TransferProcessState::Started, wait())
+            .await
+            .unwrap();
+
+        let entry = client.edrs().get_entry(&transfer_id).await.unwrap();
+        assert_eq!(entry.agreement_id(), agreement_id);
+        assert!(client
+            .edrs()
+            .get_entry("unknown")
+            .await
+            .unwrap_err()
+            .is_not_found());
+
+        let data_address = client.edrs().get_data_address(&transfer_id).await.unwrap();
+        let refreshed = client.edrs().refresh(&transfer_id).await.unwrap();
+        assert_ne!(data_address.authorization(), refreshed.authorization());
+    }
+
+    #[tokio::test]
+    async fn should_force_states() {
+        let connector = MockConnector::start_with_opts(
+            MockOptions::builder()
+                .progression(Progression::Immediate)
+                .build(),
+        )
+        .await
+        .unwrap();
+        let client = connector.client().unwrap();
+
+        let negotiation_id = negotiate(&client).await;
+
+        assert_eq!(
+            client
+                .contract_negotiations()
+                .get_state(&negotiation_id)
+                .await
+                .unwrap(),
+            ContractNegotiationState::Finalized
+        );
+
+        assert!(
+            connector.set_negotiation_state(&negotiation_id, ContractNegotiationState::Terminated)
+        );
+        assert!(!connector.set_negotiation_state("unknown", ContractNegotiationState::Terminated));
+
+        let result = client
+            .contract_negotiations()
+            .wait_for_state_with_opts(&negotiation_id, ContractNegotiationState::Finalized, wait())
+            .await;
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,296 @@
+#[allow(clippy::unwrap_used)]
+mod pagination {
+    use edc_connector_client::{
+        types::{
+            catalog::CatalogRequest,
+            query::{Pagination, Query},
+        },
+        EdcConnectorClient, Error,
+    };
+    use futures::{StreamExt, TryStreamExt};
+    use serde_json::{json, Value};
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, Request, Respond, ResponseTemplate,
+    };
+
+    struct PagedAssets {
+        total: u64,
+        fail_at_offset: Option<u64>,
+    }
+
+    impl Respond for PagedAssets {
+        fn respond(&self, request: &Request) -> ResponseTemplate {
+            let body = serde_json::from_slice::<Value>(&request.body).unwrap();
+            let offset = body["offset"].as_u64().unwrap();
+            let limit = body["limit"].as_u64().unwrap();
+
+            if Some(offset) == self.fail_at_offset {
+                return ResponseTemplate::new(500);
+            }
+
+            let assets = (offset..(offset + limit).min(self.total))
+                .map(|id| {
+                    json!({
+                        "@context": { "@vocab": "https://w3id.org/edc/v0.0.1/ns/" },
+                        "@id": id.to_string(),
+                        "@type": "Asset",
+                        "properties": {},
+                        "dataAddress": { "@type": "DataAddress", "type": "HttpData" }
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            ResponseTemplate::new(200).set_body_json(assets)
+        }
+    }
+
+    async fn setup(total: u64, fail_at_offset: Option<u64>) -> (MockServer, EdcConnectorClient) {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/v3/assets/request"))
+            .respond_with(PagedAssets {
+                total,
+                fail_at_offset,
+            })
+            .mount(&server)
+            .await;
+
+        let client = EdcConnectorClient::builder()
+            .management_url(server.uri())
+            .build()
+            .unwrap();
+
+        (server, client)
+    }
+
+    fn ids(count: u64) -> Vec<String> {
+        (0..count).map(|id| id.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn should_query_all_pages() {
+        let (server, client) = setup(7, None).await;
+
+        let assets = client
+            .assets()
+            .query_all(Query::builder().limit(3).build())
+            .await
+            .unwrap();
+
+        let found = assets
+            .iter()
+            .map(|a| a.id().to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(found, ids(7));
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn should_stop_on_empty_page() {
+        let (server, client) = setup(6, None).await;
+
+        let assets = client
+            .assets()
+            .query_all(Query::builder().limit(3).build())
+            .await
+            .unwrap();
+
+        assert_eq!(assets.len(), 6);
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn should_start_from_query_offset() {
+        let (_server, client) = setup(5, None).await;
+
+        let assets = client
+            .assets()
+            .query_all(Query::builder().offset(2).limit(2).build())
+            .await
+            .unwrap();
+
+        let found = assets
+            .iter()
+            .map(|a| a.id().to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(found, vec!["2", "3", "4"]);
+    }
+
+    #[tokio::test]
+    async fn should_cap_items() {
+        let (server, client) = setup(100, None).await;
+
+        let assets = client
+            .assets()
+            .query_stream_with_opts(
+                Query::builder().limit(10).build(),
+                Pagination::builder().max_items(15).build(),
+            )
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_eq!(assets.len(), 15);
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn should_preserve_order_with_concurrency() {
+        let (_server, client) = setup(25, None).await;
+
+        let assets = client
+            .assets()
+            .query_stream_with_opts(
+                Query::builder().limit(4).build(),
+                Pagination::builder().concurrency(3).build(),
+            )
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        let found = assets
+            .iter()
+            .map(|a| a.id().to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(found, ids(25));
+    }
+
+    #[tokio::test]
+    async fn should_stop_on_error() {
+        let (_server, client) = setup(10, Some(4)).await;
+
+        let results = client
+            .assets()
+            .query_stream(Query::builder().limit(2).build())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(results.len(), 5);
+        assert!(results[..4].iter().all(Result::is_ok));
+        assert!(matches!(results[4], Err(Error::ServerError(_))));
+    }
+
+    #[tokio::test]
+    async fn should_stream_from_spawned_task() {
+        let (_server, client) = setup(5, None).await;
+
+        let assets = tokio::spawn(async move {
+            client
+                .assets()
+                .query_stream(Query::builder().limit(2).build())
+                .try_collect::<Vec<_>>()
+                .await
+        })
+        .await
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(assets.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn should_stop_when_catalog_ignores_offset() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v3/catalog/request"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "@context": { "@vocab": "https://w3id.org/edc/v0.0.1/ns/" },
+                "@id": "catalog",
+                "@type": "dcat:Catalog",
+                "dcat:dataset": [
+                    { "@id": "asset-1", "@type": "dcat:Dataset", "odrl:hasPolicy": [] },
+                    { "@id": "asset-2", "@type": "dcat:Dataset", "odrl:hasPolicy": [] }
+                ]
+            })))
+            .mount(&server)
+            .await;
+        let client = EdcConnectorClient::builder()
+            .management_url(server.uri())
+            .build()
+            .unwrap();
+
+        let request = CatalogRequest::builder()
+            .counter_party_address("http://provider/protocol")
+            .query_spec(Query::builder().limit(2).build())
+            .build();
+
+        let datasets = client
+            .catalogue()
+            .datasets_stream(&request)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        let found = datasets
+            .iter()
+            .map(|dataset| dataset.id())
+            .collect::<Vec<_>>();
+
+        assert_eq!(found, ["asset-1", "asset-2"]);
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn should_cap_catalog_datasets_after_flattening() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v3/catalog/request"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "@context": { "@vocab": "https://w3id.org/edc/v0.0.1/ns/" },
+                "@id": "catalog",
+                "@type": "dcat:Catalog",
+                "dcat:dataset": [
+                    { "@id": "asset-1", "@type": "dcat:Dataset", "odrl:hasPolicy": [] },
+                    { "@id": "asset-1", "@type": "dcat:Dataset", "odrl:hasPolicy": [] },
+                    {
+                        "@id": "nested",
+                        "@type": "dcat:Catalog",
+                        "dcat:dataset": [
+                            { "@id": "asset-2", "@type": "dcat:Dataset", "odrl:hasPolicy": [] },
+                            { "@id": "asset-3", "@type": "dcat:Dataset", "odrl:hasPolicy": [] }
+                        ]
+                    },
+                    { "@id": "asset-4", "@type": "dcat:Dataset", "odrl:hasPolicy": [] }
+                ]
+            })))
+            .mount(&server)
+            .await;
+        let client = EdcConnectorClient::builder()
+            .management_url(server.uri())
+            .build()
+            .unwrap();
+
+        let request = CatalogRequest::builder()
+            .counter_party_address("http://provider/protocol")
+            .query_spec(Query::builder().limit(10).build())
+            .build();
+
+        for (max_items, expected) in [
+            (2, vec!["asset-1", "asset-4"]),
+            (3, vec!["asset-1", "asset-4", "asset-2"]),
+            (10, vec!["asset-1", "asset-4", "asset-2", "asset-3"]),
+        ] {
+            let datasets = client
+                .catalogue()
+                .datasets_stream_with_opts(
+                    &request,
+                    Pagination::builder().max_items(max_items).build(),
+                )
+                .try_collect::<Vec<_>>()
+                .await
+                .unwrap();
+
+            let found = datasets
+                .iter()
+                .map(|dataset| dataset.id())
+                .collect::<Vec<_>>();
+
+            assert_eq!(found, expected);
+        }
+    }
+}
@@ -58,7 +58,7 @@ mod create {
 
         assert!(matches!(
             response,
-            Err(Error::ManagementApi(ManagementApiError {
+            Err(Error::Conflict(ManagementApiError {
                 status_code: StatusCode::CONFLICT,
                 error_detail: ManagementApiErrorDetailKind::Parsed(..)
             }))
@@ -114,7 +114,7 @@ mod delete {
 
         assert!(matches!(
             response,
-            Err(Error::ManagementApi(ManagementApiError {
+            Err(Error::NotFound(ManagementApiError {
                 status_code: StatusCode::NOT_FOUND,
                 error_detail: ManagementApiErrorDetailKind::Parsed(..)
             }))
@@ -219,7 +219,7 @@ mod get {
 
         assert!(matches!(
             response,
-            Err(Error::ManagementApi(ManagementApiError {
+            Err(Error::NotFound(ManagementApiError {
                 status_code: StatusCode::NOT_FOUND,
                 error_detail: ManagementApiErrorDetailKind::Parsed(..)
             }))
@@ -295,7 +295,7 @@ mod update {
 
         assert!(matches!(
             response,
-            Err(Error::ManagementApi(ManagementApiError {
+            Err(Error::NotFound(ManagementApiError {
                 status_code: StatusCode::NOT_FOUND,
                 error_detail: ManagementApiErrorDetailKind::Parsed(..)
             }))
@@ -0,0 +1,155 @@
+#[allow(clippy::unwrap_used)]
+mod provisioning {
+    use edc_connector_client::{
+        api::{Operation, ReconcileOptions},
+        mock::MockConnector,
+        types::{
+            asset::NewAsset, data_address::DataAddress, manifest::Manifest, manifest::ResourceKind,
+        },
+    };
+
+    const MANIFEST: &str = r#"
+secrets:
+  - "@id": api-key
+    value: secret
+assets:
+  - "@id": asset-1
+    properties:
+      name: Asset 1
+    dataAddress:
+      type: HttpData
+      baseUrl: https://example.com
+policyDefinitions:
+  - "@id": use
+    policy:
+      permission:
+        - action: use
+contractDefinitions:
+  - "@id": asset-1-use
+    accessPolicyId: use
+    contractPolicyId: use
+    assetsSelector:
+      - operandLeft: https://w3id.org/edc/v0.0.1/ns/id
+        operator: "="
+        operandRight: asset-1
+"#;
+
+    fn summary(plan: &edc_connector_client::api::Plan) -> Vec<(Operation, ResourceKind, String)> {
+        plan.changes()
+            .iter()
+            .map(|change| (change.operation(), change.kind(), change.id().to_string()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn should_create_resources_in_dependency_order() {
+        let connector = MockConnector::start().await.unwrap();
+        let client = connector.client().unwrap();
+        let manifest = Manifest::from_yaml(MANIFEST).unwrap();
+
+        let plan = client.provisioning().plan(&manifest).await.unwrap();
+        assert_eq!(
+            summary(&plan),
+            vec![
+                (
+                    Operation::Create,
+                    ResourceKind::Secret,
+                    "api-key".to_string()
+                ),
+                (
+                    Operation::Create,
+                    ResourceKind::Asset,
+                    "asset-1".to_string()
+                ),
+                (
+                    Operation::Create,
+                    ResourceKind::PolicyDefinition,
+                    "use".to_string()
+                ),
+                (
+                    Operation::Create,
+                    ResourceKind::ContractDefinition,
+                    "asset-1-use".to_string()
+                ),
+            ]
+        );
+
+        // planning is a dry run
+        assert!(client.assets().get("asset-1").await.is_err());
+
+        client.provisioning().apply_plan(&plan).await.unwrap();
+
+        let asset = client.assets().get("asset-1").await.unwrap();
+        assert_eq!(
+            asset.property::<String>("name").unwrap(),
+            Some("Asset 1".to_string())
+        );
+        assert_eq!(
+            client
+                .contract_definitions()
+                .get("asset-1-use")
+                .await
+                .unwrap()
+                .access_policy_id(),
+            "use"
+        );
+
+        let plan = client.provisioning().plan(&manifest).await.unwrap();
+        assert!(plan.is_empty());
+        assert_eq!(plan.unchanged().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn should_update_and_prune_resources() {
+        let connector = MockConnector::start().await.unwrap();
+        let client = connector.client().unwrap();
+        client
+            .provisioning()
+            .apply(&Manifest::from_yaml(MANIFEST).unwrap())
+            .await
+            .unwrap();
+
+        let stale = NewAsset::builder()
+            .id("stale")
+            .data_address(DataAddress::builder().kind("HttpData").build().unwrap())
+            .build();
+        client.assets().create(&stale).await.unwrap();
+
+        let manifest =
+            Manifest::from_yaml(&MANIFEST.replace("name: Asset 1", "name: Renamed")).unwrap();
+
+        let plan = client.provisioning().plan(&manifest).await.unwrap();
+        assert_eq!(
+            summary(&plan),
+            vec![(
+                Operation::Update,
+                ResourceKind::Asset,
+                "asset-1".to_string()
+            )]
+        );
+
+        let plan = client
+            .provisioning()
+            .apply_with_opts(&manifest, ReconcileOptions::builder().prune(true).build())
+            .await
+            .unwrap();
+        assert_eq!(
+            summary(&plan),
+            vec![
+                (
+                    Operation::Update,
+                    ResourceKind::Asset,
+                    "asset-1".to_string()
+                ),
+                (Operation::Delete, ResourceKind::Asset, "stale".to_string()),
+            ]
+        );
+
+        let asset = client.assets().get("asset-1").await.unwrap();
+        assert_eq!(
+            asset.property::<String>("name").unwrap(),
+            Some("Renamed".to_string())
+        );
+        assert!(client.assets().get("stale").await.is_err());
+    }
+}
@@ -0,0 +1,231 @@
+#[allow(clippy::unwrap_used)]
+mod retry {
+    use std::time::Duration;
+
+    use edc_connector_client::{
+        types::{
+            contract_negotiation::ContractRequest,
+            policy::{Policy, PolicyKind},
+            query::Query,
+        },
+        EdcConnectorClient, Error, ManagementApiError, RetryPolicy,
+    };
+    use reqwest::StatusCode;
+    use serde_json::json;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    fn asset_json(id: &str) -> serde_json::Value {
+        json!({
+            "@context": { "@vocab": "https://w3id.org/edc/v0.0.1/ns/" },
+            "@id": id,
+            "@type": "Asset",
+            "properties": { "name": "test" },
+            "dataAddress": { "@type": "DataAddress", "type": "HttpData" }
+        })
+    }
+
+    fn client(server: &MockServer, retry: RetryPolicy) -> EdcConnectorClient {
+        EdcConnectorClient::builder()
+            .management_url(server.uri())
+            .with_retry(retry)
+            .build()
+            .unwrap()
+    }
+
+    fn retry_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy::builder()
+            .max_attempts(max_attempts)
+            .initial_backoff(Duration::from_millis(10))
+            .build()
+    }
+
+    async fn fail_first(server: &MockServer, http_method: &str, at: &str, times: u64, status: u16) {
+        Mock::given(method(http_method))
+            .and(path(at))
+            .respond_with(ResponseTemplate::new(status))
+            .up_to_n_times(times)
+            .with_priority(1)
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn should_retry_get_until_success() {
+        let server = MockServer::start().await;
+
+        fail_first(&server, "GET", "/v3/assets/1", 2, 503).await;
+
+        Mock::given(method("GET"))
+            .and(path("/v3/assets/1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(asset_json("1")))
+            .mount(&server)
+            .await;
+
+        let asset = client(&server, retry_policy(3))
+            .assets()
+            .get("1")
+            .await
+            .unwrap();
+
+        assert_eq!(asset.id(), "1");
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn should_fail_after_max_attempts() {
+        let server = MockServer::start().await;
+
+        fail_first(&server, "GET", "/v3/assets/1", 5, 502).await;
+
+        let response = client(&server, retry_policy(3)).assets().get("1").await;
+
+        assert!(matches!(
+            response,
+            Err(Error::ServerError(ManagementApiError {
+                status_code: StatusCode::BAD_GATEWAY,
+                ..
+            }))
+        ));
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn should_not_retry_by_default() {
+        let server = MockServer::start().await;
+
+        fail_first(&server, "GET", "/v3/assets/1", 1, 503).await;
+
+        let response = EdcConnectorClient::builder()
+            .management_url(server.uri())
+            .build()
+            .unwrap()
+            .assets()
+            .get("1")
+            .await;
+
+        assert!(response.is_err());
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn should_not_retry_non_retryable_status() {
+        let server = MockServer::start().await;
+
+        fail_first(&server, "GET", "/v3/assets/1", 1, 404).await;
+
+        let response = client(&server, retry_policy(3)).assets().get("1").await;
+
+        assert!(response.is_err());
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn should_retry_query() {
+        let server = MockServer::start().await;
+
+        fail_first(&server, "POST", "/v3/assets/request", 1, 503).await;
+
+        Mock::given(method("POST"))
+            .and(path("/v3/assets/request"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([asset_json("1")])))
+            .mount(&server)
+            .await;
+
+        let assets = client(&server, retry_policy(3))
+            .assets()
+            .query(Query::default())
+            .await
+            .unwrap();
+
+        assert_eq!(assets.len(), 1);
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn should_not_retry_initiate() {
+        let server = MockServer::start().await;
+
+        fail_first(&server, "POST", "/v3/contractnegotiations", 1, 503).await;
+
+        let request = ContractRequest::builder()
+            .counter_party_address("http://provider")
+            .counter_party_id("provider")
+            .policy(Policy::builder().kind(PolicyKind::Offer).build())
+            .build();
+
+        let response = client(&server, retry_policy(3))
+            .contract_negotiations()
+            .initiate(&request)
+            .await;
+
+        assert!(response.is_err());
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn should_retry_initiate_when_non_idempotent_enabled() {
+        let server = MockServer::start().await;
+
+        fail_first(&server, "POST", "/v3/contractnegotiations", 1, 503).await;
+
+        Mock::given(method("POST"))
+            .and(path("/v3/contractnegotiations"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "@context": { "@vocab": "https://w3id.org/edc/v0.0.1/ns/" },
+                "@id": "negotiation",
+                "createdAt": 1
+            })))
+            .mount(&server)
+            .await;
+
+        let request = ContractRequest::builder()
+            .counter_party_address("http://provider")
+            .counter_party_id("provider")
+            .policy(Policy::builder().kind(PolicyKind::Offer).build())
+            .build();
+
+        let retry = RetryPolicy::builder()
+            .initial_backoff(Duration::from_millis(10))
+            .retry_non_idempotent(true)
+            .build();
+
+        let response = client(&server, retry)
+            .contract_negotiations()
+            .initiate(&request)
+            .await
+            .unwrap();
+
+        assert_eq!(response.id(), "negotiation");
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn should_retry_on_connection_error() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+
+        let started = std::time::Instant::now();
+
+        let response = EdcConnectorClient::builder()
+            .management_url(format!("http://{}", address))
+            .with_retry(
+                RetryPolicy::builder()
+                    .max_attempts(3)
+                    .initial_backoff(Duration::from_millis(50))
+                    .jitter(false)
+                    .build(),
+            )
+            .build()
+            .unwrap()
+            .assets()
+            .get("1")
+            .await;
+
+        assert!(matches!(response, Err(Error::Reqwest(err)) if err.is_connect()));
+        assert!(started.elapsed() >= Duration::from_millis(150));
+    }
+}
@@ -49,7 +49,7 @@ mod secrets {
 
             assert!(matches!(
                 response,
-                Err(Error::ManagementApi(ManagementApiError {
+                Err(Error::Conflict(ManagementApiError {
                     status_code: StatusCode::CONFLICT,
                     error_detail: ManagementApiErrorDetailKind::Parsed(..)
                 }))
@@ -96,7 +96,7 @@ mod secrets {
 
             assert!(matches!(
                 response,
-                Err(Error::ManagementApi(ManagementApiError {
+                Err(Error::NotFound(ManagementApiError {
                     status_code: StatusCode::NOT_FOUND,
                     error_detail: ManagementApiErrorDetailKind::Parsed(..)
                 }))
@@ -142,7 +142,7 @@ mod secrets {
 
             assert!(matches!(
                 response,
-                Err(Error::ManagementApi(ManagementApiError {
+                Err(Error::NotFound(ManagementApiError {
                     status_code: StatusCode::NOT_FOUND,
                     error_detail: ManagementApiErrorDetailKind::Parsed(..)
                 }))
@@ -195,7 +195,7 @@ mod secrets {
 
             assert!(matches!(
                 response,
-                Err(Error::ManagementApi(ManagementApiError {
+                Err(Error::NotFound(ManagementApiError {
                     status_code: StatusCode::NOT_FOUND,
                     error_detail: ManagementApiErrorDetailKind::Parsed(..)
                 }))
@@ -76,7 +76,7 @@ mod transfer_processes {
 
             assert!(matches!(
                 response,
-                Err(Error::ManagementApi(ManagementApiError {
+                Err(Error::BadRequest(ManagementApiError {
                     status_code: StatusCode::BAD_REQUEST,
                     error_detail: ManagementApiErrorDetailKind::Parsed(..)
                 }))
@@ -0,0 +1,140 @@
+#[allow(clippy::unwrap_used)]
+mod transport {
+    use std::time::Duration;
+
+    use edc_connector_client::{Auth, BuilderError, EdcConnectorClient, Error, OAuth2Config};
+    use reqwest::{
+        header::{HeaderMap, HeaderValue},
+        Client,
+    };
+    use serde_json::json;
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    fn asset_json() -> serde_json::Value {
+        json!({
+            "@context": { "@vocab": "https://w3id.org/edc/v0.0.1/ns/" },
+            "@id": "1",
+            "@type": "Asset",
+            "properties": {},
+            "dataAddress": { "@type": "DataAddress", "type": "HttpData" }
+        })
+    }
+
+    fn custom_client() -> Client {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Custom-Client", HeaderValue::from_static("true"));
+        Client::builder()
+            .default_headers(headers)
+            .user_agent("edc-test")
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn should_use_custom_http_client() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/v3/assets/1"))
+            .and(header("X-Custom-Client", "true"))
+            .and(header("User-Agent", "edc-test"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(asset_json()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = EdcConnectorClient::builder()
+            .management_url(server.uri())
+            .with_http_client(custom_client())
+            .build()
+            .unwrap();
+
+        client.assets().get("1").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn should_share_custom_http_client_with_oauth2() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .and(header("X-Custom-Client", "true"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "access_token": "token",
+                "token_type": "bearer",
+                "expires_in": 3600
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/v3/assets/1"))
+            .and(header("Authorization", "Bearer token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(asset_json()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let auth = Auth::oauth(
+            OAuth2Config::builder()
+                .client_id("client")
+                .client_secret("secret")
+                .token_url(format!("{}/token", server.uri()))
+                .build(),
+        )
+        .unwrap();
+
+        let client = EdcConnectorClient::builder()
+            .management_url(server.uri())
+            .with_auth(auth)
+            .with_http_client(custom_client())
+            .build()
+            .unwrap();
+
+        client.assets().get("1").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn should_time_out_slow_requests() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/v3/assets/1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(asset_json())
+                    .set_delay(Duration::from_secs(2)),
+            )
+            .mount(&server)
+            .await;
+
+        let client = EdcConnectorClient::builder()
+            .management_url(server.uri())
+            .timeout(Duration::from_millis(100))
+            .connect_timeout(Duration::from_millis(100))
+            .build()
+            .unwrap();
+
+        let response = client.assets().get("1").await;
+
+        assert!(matches!(response, Err(Error::Timeout(err)) if err.is_timeout()));
+    }
+
+    #[test]
+    fn should_fail_when_combining_custom_client_and_transport_settings() {
+        let result = EdcConnectorClient::builder()
+            .management_url("http://localhost")
+            .with_http_client(Client::new())
+            .timeout(Duration::from_secs(1))
+            .build();
+
+        assert!(matches!(
+            result,
+            Err(BuilderError::ConflictingProperty(property)) if property == "timeout"
+        ));
+    }
+}
@@ -0,0 +1,254 @@
+#[allow(clippy::unwrap_used)]
+mod wait {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use edc_connector_client::{
+        api::WaitOptions,
+        types::{
+            contract_negotiation::ContractNegotiationState, transfer_process::TransferProcessState,
+        },
+        EdcConnectorClient, Error, WaitError,
+    };
+    use serde_json::{json, Value};
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, Request, Respond, ResponseTemplate,
+    };
+
+    struct States {
+        states: Vec<&'static str>,
+        polls: AtomicUsize,
+        body: fn(&str) -> Value,
+    }
+
+    impl Respond for States {
+        fn respond(&self, _request: &Request) -> ResponseTemplate {
+            let poll = self.polls.fetch_add(1, Ordering::SeqCst);
+            let state = self.states[poll.min(self.states.len() - 1)];
+            ResponseTemplate::new(200).set_body_json((self.body)(state))
+        }
+    }
+
+    fn negotiation(state: &str) -> Value {
+        json!({
+            "@context": { "@vocab": "https://w3id.org/edc/v0.0.1/ns/" },
+            "@id": "negotiation",
+            "@type": "ContractNegotiation",
+            "state": state,
+            "contractAgreementId": "agreement",
+            "counterPartyId": "provider",
+            "counterPartyAddress": "http://provider",
+            "protocol": "dataspace-protocol-http:2025-1",
+            "createdAt": 1,
+            "callbackAddresses": [],
+            "type": "CONSUMER"
+        })
+    }
+
+    fn transfer(state: &str) -> Value {
+        json!({
+            "@context": { "@vocab": "https://w3id.org/edc/v0.0.1/ns/" },
+            "@id": "transfer",
+            "@type": "TransferProcess",
+            "state": state,
+            "stateTimestamp": 1,
+            "assetId": "asset",
+            "contractId": "agreement",
+            "callbackAddresses": [],
+            "transferType": "HttpData-PULL",
+            "type": "CONSUMER"
+        })
+    }
+
+    async fn setup(
+        at: &str,
+        states: Vec<&'static str>,
+        body: fn(&str) -> Value,
+    ) -> (MockServer, EdcConnectorClient) {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path(at))
+            .respond_with(States {
+                states,
+                polls: AtomicUsize::new(0),
+                body,
+            })
+            .mount(&server)
+            .await;
+
+        let client = EdcConnectorClient::builder()
+            .management_url(server.uri())
+            .build()
+            .unwrap();
+
+        (server, client)
+    }
+
+    fn options() -> WaitOptions {
+        WaitOptions::builder()
+            .interval(Duration::from_millis(10))
+            .timeout(Duration::from_millis(500))
+            .build()
+    }
+
+    #[tokio::test]
+    async fn should_wait_for_negotiation_state() {
+        let (server, client) = setup(
+            "/v3/contractnegotiations/negotiation",
+            vec!["REQUESTED", "AGREED", "VERIFIED", "FINALIZED"],
+            negotiation,
+        )
+        .await;
+
+        let negotiation = client
+            .contract_negotiations()
+            .wait_for_state_with_opts(
+                "negotiation",
+                ContractNegotiationState::Finalized,
+                options(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(negotiation.state(), &ContractNegotiationState::Finalized);
+        assert_eq!(
+            negotiation.contract_agreement_id().map(String::as_str),
+            Some("agreement")
+        );
+        assert_eq!(server.received_requests().await.unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_negotiation_is_terminated() {
+        let (_server, client) = setup(
+            "/v3/contractnegotiations/negotiation",
+            vec!["REQUESTED", "TERMINATED"],
+            negotiation,
+        )
+        .await;
+
+        let result = client
+            .contract_negotiations()
+            .wait_for_state_with_opts(
+                "negotiation",
+                ContractNegotiationState::Finalized,
+                options(),
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(Error::Wait(WaitError::UnexpectedState { expected, actual }))
+                if expected == "FINALIZED" && actual == "TERMINATED"
+        ));
+    }
+
+    #[tokio::test]
+    async fn should_time_out_waiting_for_transfer_state() {
+        let (_server, client) = setup(
+            "/v3/transferprocesses/transfer",
+            vec!["REQUESTED"],
+            transfer,
+        )
+        .await;
+
+        let result = client
+            .transfer_processes()
+            .wait_for_state_with_opts("transfer", TransferProcessState::Started, options())
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(Error::Wait(WaitError::Timeout { expected, last, elapsed }))
+                if expected == "STARTED" && last == "REQUESTED" && elapsed >= Duration::from_millis(500)
+        ));
+    }
+
+    #[tokio::test]
+    async fn should_wait_for_transfer_state_with_backoff() {
+        let (_server, client) = setup(
+            "/v3/transferprocesses/transfer",
+            vec!["REQUESTING", "REQUESTED", "STARTING", "STARTED"],
+            transfer,
+        )
+        .await;
+
+        let transfer = client
+            .transfer_processes()
+            .wait_for_state_with_opts(
+                "transfer",
+                TransferProcessState::Started,
+                WaitOptions::builder()
+                    .interval(Duration::from_millis(5))
+                    .backoff(2.0)
+                    .unwrap()
+                    .max_interval(Duration::from_millis(20))
+                    .timeout(Duration::from_secs(1))
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(transfer.state(), &TransferProcessState::Started);
+        assert_eq!(transfer.transfer_type(), "HttpData-PULL");
+    }
+
+    #[tokio::test]
+    async fn should_cap_huge_backoff_to_max_interval() {
+        let (_server, client) = setup(
+            "/v3/transferprocesses/transfer",
+            vec!["REQUESTING", "REQUESTED", "STARTING", "STARTED"],
+            transfer,
+        )
+        .await;
+
+        let transfer = client
+            .transfer_processes()
+            .wait_for_state_with_opts(
+                "transfer",
+                TransferProcessState::Started,
+                WaitOptions::builder()
+                    .interval(Duration::from_millis(5))
+                    .backoff(1e300)
+                    .unwrap()
+                    .max_interval(Duration::from_millis(20))
+                    .timeout(Duration::from_secs(1))
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(transfer.state(), &TransferProcessState::Started);
+    }
+
+    #[test]
+    fn should_reject_invalid_backoff() {
+        for backoff in [0.5, f64::NAN, f64::INFINITY] {
+            assert!(WaitOptions::builder().backoff(backoff).is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_transfer_is_terminated() {
+        let (_server, client) = setup(
+            "/v3/transferprocesses/transfer",
+            vec!["REQUESTED", "TERMINATED"],
+            transfer,
+        )
+        .await;
+
+        let result = client
+            .transfer_processes()
+            .wait_for_state_with_opts("transfer", TransferProcessState::Started, options())
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(Error::Wait(WaitError::UnexpectedState { .. }))
+        ));
+    }
+}